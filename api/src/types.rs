@@ -24,6 +24,7 @@
 //! module.
 
 
+use std::cmp;
 use std::fmt;
 use std::os::raw::{c_int, c_uint, c_ulong};
 
@@ -63,9 +64,9 @@ impl fmt::Display for Geometry {
 
 /// A screen is simply defined by its width and height.
 ///
-/// We assume there is only a single screen at all times, so there is no need
-/// to track the position of a screen relative to the origin or another
-/// screen.
+/// A screen does not track its position relative to the origin or another
+/// screen. Window managers supporting multiple monitors use an
+/// [`Output`](struct.Output.html), which places a screen at an offset.
 #[derive(Copy, Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq, Hash)]
 pub struct Screen {
     /// The width of the screen.
@@ -95,6 +96,126 @@ impl Screen {
     }
 }
 
+/// The areas along the edges of the screen reserved by a panel or dock,
+/// like the X11 `_NET_WM_STRUT` property.
+///
+/// Every field is the thickness of the reserved area along that edge, e.g. a
+/// status bar of 24 pixels at the top reserves `top: 24`.
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq, Hash)]
+pub struct Strut {
+    /// The space reserved along the top edge.
+    pub top: c_uint,
+    /// The space reserved along the right edge.
+    pub right: c_uint,
+    /// The space reserved along the bottom edge.
+    pub bottom: c_uint,
+    /// The space reserved along the left edge.
+    pub left: c_uint,
+}
+
+impl Strut {
+    /// Create a new `Strut` with the given arguments.
+    pub fn new(top: c_uint, right: c_uint, bottom: c_uint, left: c_uint) -> Strut {
+        Strut {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+
+    /// Create a `Strut` which reserves nothing.
+    pub fn none() -> Strut {
+        Strut::new(0, 0, 0, 0)
+    }
+
+    /// Return the strut reserving the areas of both struts.
+    ///
+    /// Struts stack: two bars at the same edge are put next to each other,
+    /// so their thicknesses add up.
+    pub fn stack(&self, other: &Strut) -> Strut {
+        Strut::new(self.top + other.top,
+                   self.right + other.right,
+                   self.bottom + other.bottom,
+                   self.left + other.left)
+    }
+
+    /// Return the area of the screen which is not reserved.
+    ///
+    /// When the struts along two opposite edges together cover the screen,
+    /// the usable area is empty in that direction.
+    pub fn get_usable_area(&self, screen: &Screen) -> Geometry {
+        let left = cmp::min(self.left, screen.width);
+        let top = cmp::min(self.top, screen.height);
+        Geometry {
+            x: left as c_int,
+            y: top as c_int,
+            width: screen.width.saturating_sub(left).saturating_sub(self.right),
+            height: screen.height.saturating_sub(top).saturating_sub(self.bottom),
+        }
+    }
+}
+
+/// The identifier of an output, e.g. a monitor.
+///
+/// Used by [`MultiOutputSupport`](../wm/trait.MultiOutputSupport.html) to
+/// refer to the outputs.
+pub type OutputId = c_uint;
+
+/// An output is a `Screen` placed at an offset from the origin.
+///
+/// Multiple outputs together form the area the window manager manages, e.g. a
+/// dual-head setup consists of two outputs, where the second one has an `x`
+/// offset equal to the width of the first one.
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq, Hash)]
+pub struct Output {
+    /// The identifier of the output.
+    pub id: OutputId,
+    /// X-coordinate of the top-left corner of the output.
+    pub x: c_int,
+    /// Y-coordinate of the top-left corner of the output.
+    pub y: c_int,
+    /// The size of the output.
+    pub screen: Screen,
+}
+
+impl fmt::Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f,
+               "Output {} {}x{} at {},{}",
+               self.id,
+               self.screen.width,
+               self.screen.height,
+               self.x,
+               self.y)
+    }
+}
+
+impl Output {
+    /// Create a new `Output` with the given arguments.
+    pub fn new(id: OutputId, x: c_int, y: c_int, screen: Screen) -> Output {
+        Output {
+            id,
+            x,
+            y,
+            screen,
+        }
+    }
+
+    /// Return a `Geometry` covering the whole output.
+    ///
+    /// Unlike `Screen::to_geometry`, `x` and `y` are the offset of the
+    /// output.
+    pub fn to_geometry(&self) -> Geometry {
+        Geometry {
+            x: self.x,
+            y: self.y,
+            width: self.screen.width,
+            height: self.screen.height,
+        }
+    }
+}
+
 /// A type that is either *float* or *tile*.
 ///
 /// Using a simple data type like this instead of a boolean is much clearer
@@ -107,6 +228,122 @@ pub enum FloatOrTile {
     Tile,
 }
 
+/// The size constraints of a window, like the ICCCM `WM_NORMAL_HINTS`.
+///
+/// Every hint is optional. Sizes are `(width, height)` pairs and aspect
+/// ratios are `(numerator, denominator)` pairs of the ratio width / height.
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq, Hash)]
+pub struct SizeHints {
+    /// The minimum size of the window.
+    pub min_size: Option<(c_uint, c_uint)>,
+    /// The maximum size of the window.
+    pub max_size: Option<(c_uint, c_uint)>,
+    /// The size from which the increments are counted, the minimum size
+    /// when missing.
+    pub base_size: Option<(c_uint, c_uint)>,
+    /// The steps in which the window wants to grow, e.g. the size of a
+    /// character cell of a terminal.
+    pub size_increment: Option<(c_uint, c_uint)>,
+    /// The minimum aspect ratio of the window.
+    pub min_aspect: Option<(c_uint, c_uint)>,
+    /// The maximum aspect ratio of the window.
+    pub max_aspect: Option<(c_uint, c_uint)>,
+}
+
+/// Snap the size down to the base size plus a multiple of the increment.
+fn snap_to_increment(size: c_uint, base: c_uint, increment: c_uint) -> c_uint {
+    if increment == 0 || size <= base {
+        size
+    } else {
+        base + (size - base) / increment * increment
+    }
+}
+
+impl SizeHints {
+    /// Size hints without any constraint.
+    pub fn none() -> SizeHints {
+        SizeHints {
+            min_size: None,
+            max_size: None,
+            base_size: None,
+            size_increment: None,
+            min_aspect: None,
+            max_aspect: None,
+        }
+    }
+
+    /// Return the size closest to the given size that meets the hints.
+    ///
+    /// A wrong aspect ratio is fixed by shrinking one side, the size is then
+    /// snapped down to the increments and finally clamped between the
+    /// minimum and the maximum size, so the minimum size always wins.
+    pub fn constrain(&self, width: c_uint, height: c_uint) -> (c_uint, c_uint) {
+        let (mut width, mut height) = (width as u64, height as u64);
+        match self.min_aspect {
+            Some((num, den)) if num > 0 && den > 0 => {
+                let (num, den) = (num as u64, den as u64);
+                // too tall
+                if width * den < height * num {
+                    height = width * den / num;
+                }
+            }
+            _ => {}
+        }
+        match self.max_aspect {
+            Some((num, den)) if num > 0 && den > 0 => {
+                let (num, den) = (num as u64, den as u64);
+                // too wide
+                if width * den > height * num {
+                    width = height * num / den;
+                }
+            }
+            _ => {}
+        }
+        let (mut width, mut height) = (width as c_uint, height as c_uint);
+        match self.size_increment {
+            None => {}
+            Some((width_inc, height_inc)) => {
+                let (base_width, base_height) =
+                    self.base_size.or(self.min_size).unwrap_or((0, 0));
+                width = snap_to_increment(width, base_width, width_inc);
+                height = snap_to_increment(height, base_height, height_inc);
+            }
+        }
+        match self.max_size {
+            None => {}
+            Some((max_width, max_height)) => {
+                width = cmp::min(width, max_width);
+                height = cmp::min(height, max_height);
+            }
+        }
+        match self.min_size {
+            None => {}
+            Some((min_width, min_height)) => {
+                width = cmp::max(width, min_width);
+                height = cmp::max(height, min_height);
+            }
+        }
+        (width, height)
+    }
+
+    /// Fit the window in the given slot of a tiling layout.
+    ///
+    /// The hints are met as far as the slot allows: the window never grows
+    /// beyond its slot, so it cannot cover its neighbours. The window is
+    /// centered in the slot.
+    pub fn fit_in(&self, slot: Geometry) -> Geometry {
+        let (width, height) = self.constrain(slot.width, slot.height);
+        let width = cmp::min(width, slot.width);
+        let height = cmp::min(height, slot.height);
+        Geometry {
+            x: slot.x + ((slot.width - width) / 2) as c_int,
+            y: slot.y + ((slot.height - height) / 2) as c_int,
+            width,
+            height,
+        }
+    }
+}
+
 /// A `WindowWithInfo` is the combination of a `Window` with additional
 /// information: its `Geometry`, whether it should float or not
 /// (`float_or_tile`), and whether it should be displayed fullscreen or not
@@ -123,10 +360,14 @@ pub enum FloatOrTile {
 /// [`FullscreenSupport`](../wm/trait.FullscreenSupport.html) can safely
 /// ignore this.
 ///
+/// The `size_hints` are the size constraints of the window. Tiling layouts
+/// try to meet them within the space of the tile, floating windows are
+/// clamped to them.
+///
 /// This is a separate type used by the `add_window` and `get_window_info`
 /// methods of the [`WindowManager`](../wm/trait.WindowManager.html) trait,
 /// and will also be useful when defining a window manager data type yourself.
-#[derive(Copy, Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq, Hash)]
 pub struct WindowWithInfo {
     /// The window.
     pub window: Window,
@@ -136,6 +377,12 @@ pub struct WindowWithInfo {
     pub float_or_tile: FloatOrTile,
     /// Indicate whether the window should be displayed fullscreen or not.
     pub fullscreen: bool,
+    /// The size constraints of the window.
+    pub size_hints: SizeHints,
+    /// The identity of the window. Window managers match it against their
+    /// window rules when the window is added, `get_window_info` does not
+    /// have to return it.
+    pub properties: WindowProperties,
 }
 
 impl WindowWithInfo {
@@ -153,7 +400,8 @@ impl WindowWithInfo {
     pub fn new_fullscreen(window: Window, geometry: Geometry) -> WindowWithInfo {
         Self::new(window, geometry, FloatOrTile::Tile, true)
     }
-    /// Create a new `WindowWithInfo` with the given arguments.
+    /// Create a new `WindowWithInfo` with the given arguments and without
+    /// size hints.
     pub fn new(window: Window,
                geometry: Geometry,
                float_or_tile: FloatOrTile,
@@ -164,8 +412,66 @@ impl WindowWithInfo {
             geometry: geometry,
             float_or_tile: float_or_tile,
             fullscreen: fullscreen,
+            size_hints: SizeHints::none(),
+            properties: WindowProperties::new(),
+        }
+    }
+    /// Return this `WindowWithInfo` with the given size hints.
+    pub fn with_size_hints(self, size_hints: SizeHints) -> WindowWithInfo {
+        WindowWithInfo { size_hints, ..self }
+    }
+    /// Return this `WindowWithInfo` with the given identity properties.
+    pub fn with_properties(self, properties: WindowProperties) -> WindowWithInfo {
+        WindowWithInfo { properties, ..self }
+    }
+}
+
+/// The identity of a window as set by its client, e.g. the X11 `WM_CLASS`,
+/// `WM_NAME` and `WM_WINDOW_ROLE` properties.
+///
+/// Every property is optional. Window managers use them to match window
+/// rules when adding a window, see `WindowWithInfo::properties`.
+#[derive(Clone, Default, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq, Hash)]
+pub struct WindowProperties {
+    /// The class of the application, e.g. `Firefox`.
+    pub class: Option<String>,
+    /// The instance of the application, e.g. `Navigator`.
+    pub instance: Option<String>,
+    /// The title of the window.
+    pub title: Option<String>,
+    /// The role of the window within its application, e.g. `browser`.
+    pub role: Option<String>,
+}
+
+impl WindowProperties {
+    /// Construct window properties without any property.
+    pub fn new() -> WindowProperties {
+        WindowProperties {
+            class: None,
+            instance: None,
+            title: None,
+            role: None,
+        }
+    }
+
+    /// Return these properties with the given class and instance.
+    pub fn with_class(self, class: &str, instance: &str) -> WindowProperties {
+        WindowProperties {
+            class: Some(class.to_string()),
+            instance: Some(instance.to_string()),
+            ..self
         }
     }
+
+    /// Return these properties with the given title.
+    pub fn with_title(self, title: &str) -> WindowProperties {
+        WindowProperties { title: Some(title.to_string()), ..self }
+    }
+
+    /// Return these properties with the given role.
+    pub fn with_role(self, role: &str) -> WindowProperties {
+        WindowProperties { role: Some(role.to_string()), ..self }
+    }
 }
 
 /// As explained in the assignment, the `WindowLayout` struct fully describes
@@ -211,6 +517,86 @@ impl WindowLayout {
     }
 }
 
+/// A tab in the tab bar of a tabbed layout.
+///
+/// Window managers with a tabbed layout return these next to their
+/// [`WindowLayout`](struct.WindowLayout.html), so the backend can draw the
+/// tab bar, see [`TabSupport`](../wm/trait.TabSupport.html).
+#[derive(Clone, Copy, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq, Hash)]
+pub struct Tab {
+    /// The window this tab belongs to.
+    pub window: Window,
+    /// The geometry of the tab in the tab bar.
+    pub geometry: Geometry,
+    /// Whether the window of this tab is the one shown by the layout.
+    pub focused: bool,
+}
+
+/// A colour as `0xRRGGBB`.
+pub type Colour = c_uint;
+
+/// The state of a window that determines the colour of its border.
+#[derive(Clone, Copy, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq, Hash)]
+pub enum BorderState {
+    /// The focused window.
+    Focused,
+    /// Any other window that does not demand attention.
+    Unfocused,
+    /// A window that is not focused but demands attention.
+    Urgent,
+}
+
+/// The border colour of the windows in every
+/// [`BorderState`](enum.BorderState.html).
+#[derive(Clone, Copy, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq, Hash)]
+pub struct BorderColours {
+    /// The border colour of the focused window.
+    pub focused: Colour,
+    /// The border colour of the other windows.
+    pub unfocused: Colour,
+    /// The border colour of the windows demanding attention.
+    pub urgent: Colour,
+}
+
+impl BorderColours {
+    /// Return the colour of a border in the given state.
+    pub fn get_colour(&self, state: BorderState) -> Colour {
+        match state {
+            BorderState::Focused => self.focused,
+            BorderState::Unfocused => self.unfocused,
+            BorderState::Urgent => self.urgent,
+        }
+    }
+}
+
+/// A visible window together with its border.
+#[derive(Clone, Copy, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq, Hash)]
+pub struct BorderedWindow {
+    /// The window.
+    pub window: Window,
+    /// The geometry of the window with the border included, i.e. the
+    /// geometry of the window in the `WindowLayout` grown by `border_width`
+    /// at every side.
+    pub geometry: Geometry,
+    /// The width of the border, 0 when the window has no border.
+    pub border_width: BorderWidth,
+    /// The state of the window.
+    pub state: BorderState,
+    /// The colour of the border.
+    pub colour: Colour,
+}
+
+/// A [`WindowLayout`](struct.WindowLayout.html) extended with the border of
+/// every window, see [`BorderSupport`](../wm/trait.BorderSupport.html).
+#[derive(Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq, Hash)]
+pub struct BorderedWindowLayout {
+    /// The focused window or `None` in which case no window is focused.
+    pub focused_window: Option<Window>,
+    /// The visible windows with their borders, in the same order as the
+    /// windows of the `WindowLayout`.
+    pub windows: Vec<BorderedWindow>,
+}
+
 /// A type that is either *previous* or *next*.
 ///
 /// Using a simple data type like this instead of a boolean is much clearer
@@ -235,11 +621,108 @@ impl PrevOrNext {
     }
 }
 
+/// A direction on the screen, used to move the focus or a window to the
+/// window next to it.
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Left
+    Left,
+    /// Right
+    Right,
+    /// Up
+    Up,
+    /// Down
+    Down,
+}
+
+impl Direction {
+    /// Return the opposite of the given direction, e.g. the opposite of
+    /// `Left` is `Right`.
+    pub fn opposite(&self) -> Self {
+        use self::Direction::*;
+        match *self {
+            Left => Right,
+            Right => Left,
+            Up => Down,
+            Down => Up,
+        }
+    }
+
+    /// Return the window next to the given window in this direction, based
+    /// on the given geometries in stacking order.
+    ///
+    /// The candidates are the windows with their centre beyond the centre of
+    /// the given window in this direction. They are ranked by:
+    ///
+    /// 1. whether they overlap the given window across this direction, i.e.
+    ///    lie in the same row or column, those come first;
+    /// 2. the distance between the centres in this direction;
+    /// 3. the distance between the centres across this direction;
+    /// 4. the stacking order, the highest window comes first.
+    ///
+    /// When there is no candidate and `wrap` is true, the search wraps around
+    /// at the edge of the screen: the candidates are the windows with their
+    /// centre beyond the given window in the opposite direction, and the one
+    /// furthest away is the nearest.
+    ///
+    /// Returns `None` when there is no neighbour or when the given window is
+    /// not in `windows`.
+    pub fn find_neighbour(&self,
+                          window: Window,
+                          windows: &[(Window, Geometry)],
+                          wrap: bool)
+                          -> Option<Window> {
+        windows.iter().find(|&&(w, _)| w == window).and_then(|&(_, geometry)| {
+            let find = |ahead: bool| {
+                windows.iter()
+                    .enumerate()
+                    .filter(|&(_, &(w, _))| w != window)
+                    .map(|(index, &(w, other))| (w, self.rank(&geometry, &other, index)))
+                    .filter(|&(_, (_, distance, _, _))| distance != 0 && (distance > 0) == ahead)
+                    .min_by_key(|&(_, rank)| rank)
+                    .map(|(w, _)| w)
+            };
+            find(true).or_else(|| if wrap { find(false) } else { None })
+        })
+    }
+
+    /// Rank the other geometry as a neighbour of the geometry, see
+    /// `find_neighbour`. The centres are doubled so they stay integers.
+    fn rank(&self,
+            geometry: &Geometry,
+            other: &Geometry,
+            index: usize)
+            -> (bool, i64, i64, i64) {
+        use self::Direction::*;
+        // the start and the size along and across this direction
+        let axes = |g: &Geometry| match *self {
+            Left | Right => (g.x as i64, g.width as i64, g.y as i64, g.height as i64),
+            Up | Down => (g.y as i64, g.height as i64, g.x as i64, g.width as i64),
+        };
+        let (along, length, across, breadth) = axes(geometry);
+        let (other_along, other_length, other_across, other_breadth) = axes(other);
+        let distance = (2 * other_along + other_length) - (2 * along + length);
+        let overlap = other_across < across + breadth && across < other_across + other_breadth;
+        (!overlap,
+         match *self {
+            Left | Up => -distance,
+            Right | Down => distance,
+        },
+         ((2 * other_across + other_breadth) - (2 * across + breadth)).abs(),
+         -(index as i64))
+    }
+}
+
 /// The size of a gap.
 ///
 /// Note that a gap cannot be negative.
 pub type GapSize = c_uint;
 
+/// The width of a window border.
+///
+/// Note that a border width cannot be negative.
+pub type BorderWidth = c_uint;
+
 /// The type of a workspace index.
 ///
 /// Used by the
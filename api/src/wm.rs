@@ -25,7 +25,8 @@ use rustc_serialize::{Decodable, Encodable};
 use std::error;
 use std::fmt::Debug;
 
-use types::{GapSize, Geometry, PrevOrNext, Screen, Window, WindowLayout, WindowWithInfo,
+use types::{BorderColours, BorderWidth, BorderedWindowLayout, Direction, GapSize, Geometry, Output,
+            OutputId, PrevOrNext, Screen, Strut, Tab, Window, WindowLayout, WindowWithInfo,
             WorkspaceIndex};
 
 /// A basic window manager.
@@ -304,7 +305,8 @@ pub trait WindowManager: Encodable + Decodable + Debug + Clone {
     ///
     /// This is called whenever the resolution of the screen is changed.
     ///
-    /// Note that we do not support multiple monitors.
+    /// Window managers supporting multiple monitors resize the focused output,
+    /// see [`MultiOutputSupport`](trait.MultiOutputSupport.html).
     ///
     /// **Invariant**: after `resize_screen` is called with a screen,
     /// `get_screen()` must return the same screen.
@@ -441,6 +443,42 @@ pub trait TilingSupport: WindowManager {
     /// **Invariant**: calling `swap_windows(dir)` and then
     /// `swap_windows(dir.opposite())` will not change the window layout.
     fn swap_windows(&mut self, dir: PrevOrNext);
+
+    /// Focus the window next to the focused window in the given direction.
+    ///
+    /// The neighbour is found with [`Direction::find_neighbour`] from the
+    /// geometries of the window layout, so floating windows are included.
+    /// When `wrap` is true, the focus wraps around at the edge of the screen.
+    ///
+    /// Do nothing when no window is focused or when there is no window in
+    /// that direction. The errors of focusing the neighbour are returned.
+    ///
+    /// [`Direction::find_neighbour`]:
+    /// ../types/enum.Direction.html#method.find_neighbour
+    fn focus_direction(&mut self, dir: Direction, wrap: bool) -> Result<(), Self::Error> {
+        let layout = self.get_window_layout();
+        let neighbour = layout.focused_window
+            .and_then(|window| dir.find_neighbour(window, &layout.windows, wrap));
+        match neighbour {
+            None => Ok(()),
+            Some(window) => self.focus_window(Some(window)),
+        }
+    }
+
+    /// Swap the focused window with the window next to it in the given
+    /// direction, found like in `focus_direction`.
+    ///
+    /// Tiled windows swap their tiles and floating windows swap their
+    /// positions. A tiled window never swaps with a floating window, so only
+    /// the windows of the same kind are candidates. Do nothing when no window
+    /// is focused or when there is no window in that direction.
+    ///
+    /// **Invariant**: calling `swap_direction(dir, wrap)` for any `dir` will
+    /// not change the focused window.
+    ///
+    /// This function is *allowed* to return an appropriate error when the
+    /// windows can not swap.
+    fn swap_direction(&mut self, dir: Direction, wrap: bool) -> Result<(), Self::Error>;
 }
 
 /// A window manager that supports floating windows.
@@ -765,3 +803,181 @@ pub trait MultiWorkspaceSupport<WM: WindowManager>: WindowManager {
     /// MAX_WORKSPACE_INDEX` is not true.
     fn switch_workspace(&mut self, index: WorkspaceIndex) -> Result<(), Self::Error>;
 }
+
+
+/// A window manager that supports multiple outputs, e.g. monitors.
+///
+/// Every output is a [`Screen`] placed at an offset, see [`Output`]. The
+/// window manager starts with a single output with id 0 at the origin, the
+/// size of the `Screen` passed to `new`. Outputs can be added, removed and
+/// resized at run-time, e.g. when a monitor is plugged in.
+///
+/// The geometries in the window layout returned by `get_window_layout` are
+/// relative to the origin, so the windows shown on an output are offset by
+/// the position of that output. The same holds for the geometries passed to
+/// and returned by the other traits.
+///
+/// There is always exactly one focused output, the output of the focused
+/// window. The `get_screen` and `resize_screen` methods of
+/// [`WindowManager`](trait.WindowManager.html) act on the focused output.
+///
+/// [`Screen`]: ../types/struct.Screen.html
+/// [`Output`]: ../types/struct.Output.html
+pub trait MultiOutputSupport: WindowManager {
+    /// Return all the outputs.
+    ///
+    /// **Invariant**: the returned vector is never empty.
+    fn get_outputs(&self) -> Vec<Output>;
+
+    /// Return the id of the focused output.
+    ///
+    /// **Invariant**: the vector returned by `get_outputs` contains an output
+    /// with this id.
+    fn get_focused_output(&self) -> OutputId;
+
+    /// Add a new output.
+    ///
+    /// The focused output does not change.
+    ///
+    /// This function *should* return an appropriate error when there already
+    /// is an output with the same id.
+    fn add_output(&mut self, output: Output) -> Result<(), Self::Error>;
+
+    /// Remove the output with the given id.
+    ///
+    /// When the focused output is removed, another output becomes focused.
+    ///
+    /// This function *should* return an appropriate error when there is no
+    /// output with the given id, or when it is the last output.
+    fn remove_output(&mut self, id: OutputId) -> Result<(), Self::Error>;
+
+    /// Move and/or resize the output with the id of the given output.
+    ///
+    /// **Invariant**: after `resize_output(o)` succeeds, `get_outputs()`
+    /// contains `o`.
+    ///
+    /// This function *should* return an appropriate error when there is no
+    /// output with the same id.
+    fn resize_output(&mut self, output: Output) -> Result<(), Self::Error>;
+
+    /// Focus the output with the given id.
+    ///
+    /// **Invariant**: after `focus_output(id)` succeeds,
+    /// `get_focused_output() == id`.
+    ///
+    /// This function *should* return an appropriate error when there is no
+    /// output with the given id.
+    fn focus_output(&mut self, id: OutputId) -> Result<(), Self::Error>;
+
+    /// Return the window layout of the output with the given id.
+    ///
+    /// The geometries are relative to the origin, like in
+    /// `get_window_layout`. Only the layout of the focused output can have a
+    /// focused window.
+    ///
+    /// This function *should* return an appropriate error when there is no
+    /// output with the given id.
+    fn get_output_layout(&self, id: OutputId) -> Result<WindowLayout, Self::Error>;
+}
+
+/// A window manager that supports tabbed layouts.
+///
+/// A tabbed layout shows one tiled window at a time and reserves a strip of
+/// the screen for a tab bar with a tab for every tiled window. The window
+/// layout returned by `get_window_layout` does not contain the tab bar, the
+/// backend draws it using the tabs returned by `get_tabs`.
+///
+/// [`Tab`]: ../types/struct.Tab.html
+pub trait TabSupport: WindowManager {
+    /// Return the tabs of the tab bar, from left to right.
+    ///
+    /// Returns an empty vector when the current layout has no tab bar. A
+    /// window manager with multiple outputs returns the tabs of all of them.
+    ///
+    /// **Invariant**: the window of every tab is in the window layout, and
+    /// every tab bar has at most one focused tab.
+    fn get_tabs(&self) -> Vec<Tab>;
+}
+
+/// A window manager that draws a border around its windows.
+///
+/// The geometries in the window layout returned by `get_window_layout` are
+/// those of the windows without their border. The layouts make room for the
+/// border of every tiled window, so tiles and their borders never overlap.
+/// Floating windows keep their geometry and get the border around it. The
+/// fullscreen window has no border. Initially the border width is 0.
+///
+/// The backend draws the borders using the layout returned by
+/// `get_bordered_window_layout`.
+pub trait BorderSupport: WindowManager {
+    /// Return the current border width.
+    fn get_border_width(&self) -> BorderWidth;
+
+    /// Set the border width.
+    ///
+    /// **Invariant**: after `set_border_width(w)`, `get_border_width() == w`.
+    fn set_border_width(&mut self, width: BorderWidth);
+
+    /// Return the border colours.
+    fn get_border_colours(&self) -> BorderColours;
+
+    /// Set the border colours.
+    fn set_border_colours(&mut self, colours: BorderColours);
+
+    /// Return true if the given window demands attention.
+    fn is_urgent(&self, window: Window) -> bool;
+
+    /// Mark the given window as demanding attention or not.
+    ///
+    /// The focused window always gets the focused border, even when it is
+    /// urgent. This function *should* return an appropriate error when the
+    /// window is not managed by the window manager.
+    fn set_urgent(&mut self, window: Window, urgent: bool) -> Result<(), Self::Error>;
+
+    /// Return the window layout with the border of every window.
+    ///
+    /// **Invariant**: the bordered layout contains the same windows in the
+    /// same order as `get_window_layout`, with the same focused window. The
+    /// geometry of every bordered window is its geometry in the window
+    /// layout grown by its border width at every side.
+    fn get_bordered_window_layout(&self) -> BorderedWindowLayout;
+}
+
+/// A window manager that keeps its windows clear of panels and docks.
+///
+/// A panel or dock reserves an area along an edge of the screen with a
+/// [`Strut`]. The struts are registered per window, e.g. by the dock window
+/// itself, or as a static strut, e.g. from the configuration. All struts
+/// stack, so two bars at the top edge reserve the space of both.
+///
+/// The tiled windows and the fullscreen window are placed in the usable
+/// area: the screen without the reserved areas. Floating windows can still
+/// be placed anywhere on the screen. Initially nothing is reserved.
+///
+/// [`Strut`]: ../types/struct.Strut.html
+pub trait StrutSupport: WindowManager {
+    /// Return the part of the screen which is not reserved by any strut.
+    fn get_usable_area(&self) -> Geometry;
+
+    /// Return the static strut.
+    fn get_static_strut(&self) -> Strut;
+
+    /// Set the static strut, which is not tied to a window.
+    ///
+    /// **Invariant**: after `set_static_strut(s)`, `get_static_strut() == s`.
+    fn set_static_strut(&mut self, strut: Strut);
+
+    /// Return the strut of the given window, if it has one.
+    fn get_window_strut(&self, window: Window) -> Option<Strut>;
+
+    /// Reserve the area of the strut for the given window, replacing its
+    /// previous strut.
+    ///
+    /// The window does not have to be managed by the window manager, docks
+    /// usually are not. When a managed window is removed, its strut is
+    /// removed as well.
+    fn set_window_strut(&mut self, window: Window, strut: Strut);
+
+    /// Remove the strut of the given window, if it has one.
+    fn remove_window_strut(&mut self, window: Window);
+}
@@ -20,7 +20,8 @@
 //!
 
 use std::collections::{BTreeMap, VecDeque};
-use cplwm_api::types::{FloatOrTile, PrevOrNext, Screen, Window, WindowLayout, WindowWithInfo};
+use cplwm_api::types::{FloatOrTile, Geometry, PrevOrNext, Screen, Window, WindowLayout,
+                       WindowWithInfo};
 use cplwm_api::wm::WindowManager;
 use wm_common::Manager;
 use wm_common::error::StandardError;
@@ -62,9 +63,8 @@ impl WindowManager for FullscreenWM {
     }
 
     fn add_window(&mut self, window_with_info: WindowWithInfo) -> Result<(), Self::Error> {
-        self.focus_manager.add_window(window_with_info).and_then(|_| {
+        self.focus_manager.add_window(window_with_info.clone()).map(|_| {
             self.window_to_info.insert(window_with_info.window, window_with_info);
-            Ok(())
         })
     }
 
@@ -120,6 +120,8 @@ impl WindowManager for FullscreenWM {
                     geometry: self.screen.to_geometry(),
                     float_or_tile: FloatOrTile::Tile,
                     fullscreen: true,
+                    size_hints: w.size_hints,
+                    properties: w.properties.clone(),
                 })
             }
         }
@@ -136,6 +138,31 @@ impl WindowManager for FullscreenWM {
     }
 }
 
+/// The order in which cycle_focus goes through the windows
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusPolicy {
+    /// rotate through the focus history, the layout may decide which tile comes next
+    History,
+    /// the stacking order of the window layout, from bottom to top
+    StackOrder,
+    /// most recently used: Next steps from the previously focused window to the least recently
+    /// used one, like alt-tab, Prev steps the other way round
+    MostRecentlyUsed,
+    /// reading order of the windows on the screen: top to bottom, left to right
+    ReadingOrder,
+}
+
+/// The window which gets the focus when the focused window is removed
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemovalFocus {
+    /// the previously focused window
+    PreviousFocused,
+    /// the next tile, or the previous one when the removed tile was the last one
+    NextTile,
+    /// the master
+    Master,
+}
+
 /// A manager who is solely occupied with managing which window is focused
 #[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
 pub struct FocusManager {
@@ -144,6 +171,10 @@ pub struct FocusManager {
     pub windows: VecDeque<Window>,
     /// Currently focused window.
     pub focused_window: Option<Window>,
+    /// The order in which cycle_focus_in goes through the windows, History by default
+    pub focus_policy: FocusPolicy,
+    /// Which window is focused after removing the focused window, PreviousFocused by default
+    pub removal_focus: RemovalFocus,
 }
 
 impl Manager for FocusManager {
@@ -197,6 +228,8 @@ impl FocusManager {
         FocusManager {
             windows: VecDeque::new(),
             focused_window: None,
+            focus_policy: FocusPolicy::History,
+            removal_focus: RemovalFocus::PreviousFocused,
         }
     }
 
@@ -205,7 +238,10 @@ impl FocusManager {
         self.focused_window
     }
 
-
+    /// The windows from the most to the least recently focused one, the focused window first
+    pub fn get_recently_used(&self) -> Vec<Window> {
+        self.focused_window.into_iter().chain(self.windows.iter().rev().cloned()).collect()
+    }
 
     /// focus anohter window
     pub fn focus_window(&mut self, window: Option<Window>) -> Result<(), StandardError> {
@@ -233,6 +269,14 @@ impl FocusManager {
         }
     }
 
+    /// Unfocus the focused window, if any. Unlike `focus_window(None)` this can not fail.
+    pub fn unfocus(&mut self) {
+        match self.focused_window.take() {
+            None => {}
+            Some(w) => self.windows.push_back(w),
+        }
+    }
+
     /// cycle focus
     pub fn cycle_focus(&mut self, dir: PrevOrNext) {
         match dir {
@@ -252,13 +296,80 @@ impl FocusManager {
             }
         }
     }
+
+    /// Cycle the focus according to the focus policy. The windows are the window layout, the
+    /// neighbour is the window the layout wants to focus next, which is only followed with the
+    /// History policy. The policies other than History only cycle through the windows in the
+    /// layout, when the focused window is not among them Next focuses the first one and Prev the
+    /// last one.
+    pub fn cycle_focus_in(&mut self,
+                          dir: PrevOrNext,
+                          windows: &[(Window, Geometry)],
+                          neighbour: Option<Window>)
+                          -> Result<(), StandardError> {
+        let order = match self.focus_policy {
+            FocusPolicy::History => {
+                return match neighbour {
+                    None => {
+                        self.cycle_focus(dir);
+                        Ok(())
+                    }
+                    Some(window) => self.focus_window(Some(window)),
+                };
+            }
+            FocusPolicy::MostRecentlyUsed => {
+                // rotate the history the other way round, so Next steps from the most to the
+                // least recently used window and reaches every window
+                self.cycle_focus(dir.opposite());
+                return Ok(());
+            }
+            FocusPolicy::StackOrder => windows.iter().map(|&(w, _)| w).collect::<Vec<_>>(),
+            FocusPolicy::ReadingOrder => {
+                let mut windows = windows.to_vec();
+                // sorting is stable, so windows at the same position keep their stacking order
+                windows.sort_by_key(|&(_, geometry)| (geometry.y, geometry.x));
+                windows.into_iter().map(|(w, _)| w).collect::<Vec<_>>()
+            }
+        };
+        if order.is_empty() {
+            return Ok(());
+        }
+        let position = self.focused_window.and_then(|f| order.iter().position(|w| *w == f));
+        let index = match (position, dir) {
+            (None, PrevOrNext::Next) => 0,
+            (None, PrevOrNext::Prev) => order.len() - 1,
+            (Some(i), PrevOrNext::Next) => (i + 1) % order.len(),
+            (Some(i), PrevOrNext::Prev) => (i + order.len() - 1) % order.len(),
+        };
+        self.focus_window(Some(order[index]))
+    }
+
+    /// Focus the window the removal focus asks for after the focused window was removed, the
+    /// previously focused window stays focused when there is no such window. The next tile is
+    /// the tile after the removed one and the master is the master after removing it.
+    pub fn focus_after_removal(&mut self,
+                               next_tile: Option<Window>,
+                               master: Option<Window>)
+                               -> Result<(), StandardError> {
+        let window = match self.removal_focus {
+            RemovalFocus::PreviousFocused => None,
+            RemovalFocus::NextTile => next_tile,
+            RemovalFocus::Master => master,
+        };
+        match window {
+            None => Ok(()),
+            Some(window) => self.focus_window(Some(window)),
+        }
+    }
 }
 
 
 #[cfg(test)]
 mod tests {
     use wm_common::tests::window_manager;
-    use super::FullscreenWM;
+    use wm_common::Manager;
+    use super::{FocusManager, FullscreenWM};
+    use cplwm_api::types::*;
 
     #[test]
     fn test_empty_tiling_wm(){
@@ -294,4 +405,23 @@ mod tests {
     fn test_resize_screen(){
         window_manager::test_resize_screen::<FullscreenWM>();
     }
+
+    #[test]
+    fn test_unfocus() {
+        let mut focus_manager = FocusManager::new();
+        let geometry = Geometry {
+            x: 0,
+            y: 0,
+            width: 100,
+            height: 100,
+        };
+        assert!(focus_manager.add_window(WindowWithInfo::new_tiled(1, geometry)).is_ok());
+        assert!(focus_manager.add_window(WindowWithInfo::new_tiled(2, geometry)).is_ok());
+
+        focus_manager.unfocus();
+        assert_eq!(None, focus_manager.get_focused_window());
+        assert_eq!(vec![2, 1], focus_manager.get_recently_used());
+        focus_manager.unfocus();
+        assert_eq!(vec![1, 2], focus_manager.get_windows());
+    }
 }
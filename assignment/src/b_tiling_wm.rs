@@ -18,13 +18,18 @@
 //!
 
 // Add imports here
-use cplwm_api::types::{FloatOrTile, Geometry, PrevOrNext, Screen, Window, WindowLayout,
-                       WindowWithInfo};
-use cplwm_api::wm::{TilingSupport, WindowManager};
+use cplwm_api::types::{BorderWidth, Direction, FloatOrTile, GapSize, Geometry, PrevOrNext,
+                       Screen, SizeHints, Strut, Tab, Window, WindowLayout, WindowWithInfo};
+use cplwm_api::wm::{StrutSupport, TabSupport, TilingSupport, WindowManager};
 
-use wm_common::{LayoutManager, Manager, TilingLayout, TilingTrait};
+use wm_common::{BorderTrait, FocusPolicySupport, GapTrait, LayoutManager, LayoutMessage,
+                LayoutMessageSupport, LayoutSwitchTrait, Manager, MasterArea, MasterTrait,
+                TilingLayout, TilingTrait};
 use wm_common::error::StandardError;
-use a_fullscreen_wm::FocusManager;
+use a_fullscreen_wm::{FocusManager, FocusPolicy, RemovalFocus};
+use layouts::selector::LayoutSelector;
+use struts::StrutManager;
+use std::cmp;
 use std::collections::{HashMap, VecDeque};
 
 /// The public type.
@@ -32,13 +37,16 @@ pub type WMName = TilingWM;
 
 
 /// The TilingWM as described in the assignment. Will implement the
-/// WindowManager and the TilingSupport
+/// WindowManager and the TilingSupport. It starts with the VerticalLayout and can switch to the
+/// other layouts of the LayoutSelector while running.
 #[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
 pub struct TilingWM {
     /// The manager used to manage the current focus
     pub focus_manager: FocusManager,
     /// The managar used to manage the tiles
-    pub tile_manager: TileManager<VerticalLayout>,
+    pub tile_manager: TileManager<LayoutSelector>,
+    /// The manager for the areas reserved by panels and docks
+    pub strut_manager: StrutManager,
 }
 
 impl WindowManager for TilingWM {
@@ -49,7 +57,8 @@ impl WindowManager for TilingWM {
     fn new(screen: Screen) -> TilingWM {
         TilingWM {
             focus_manager: FocusManager::new(),
-            tile_manager: TileManager::new(screen, VerticalLayout {}),
+            tile_manager: TileManager::new(screen, LayoutSelector::standard()),
+            strut_manager: StrutManager::new(),
         }
     }
 
@@ -62,14 +71,24 @@ impl WindowManager for TilingWM {
     }
     fn add_window(&mut self, window_with_info: WindowWithInfo) -> Result<(), Self::Error> {
         self.focus_manager
-            .add_window(window_with_info)
+            .add_window(window_with_info.clone())
             .and_then(|_| self.tile_manager.add_window(window_with_info))
     }
 
     fn remove_window(&mut self, window: Window) -> Result<(), Self::Error> {
+        let was_focused = self.get_focused_window() == Some(window);
+        let next_tile = self.tile_manager.get_next_tile(window);
         self.focus_manager
             .remove_window(window)
             .and_then(|_| self.tile_manager.remove_window(window))
+            .and_then(|_| if was_focused {
+                let master = self.tile_manager.get_master_window();
+                self.focus_manager.focus_after_removal(next_tile, master)
+            } else {
+                Ok(())
+            })
+            .and_then(|_| self.tile_manager.focus_shifted(self.focus_manager.get_focused_window()))
+            .map(|_| self.remove_window_strut(window))
     }
 
     fn get_window_layout(&self) -> WindowLayout {
@@ -80,11 +99,27 @@ impl WindowManager for TilingWM {
     }
 
     fn focus_window(&mut self, window: Option<Window>) -> Result<(), Self::Error> {
-        self.focus_manager.focus_window(window)
+        self.focus_manager
+            .focus_window(window)
+            .and_then(|_| self.tile_manager.focus_shifted(window))
     }
 
     fn cycle_focus(&mut self, dir: PrevOrNext) {
-        self.focus_manager.cycle_focus(dir)
+        // the layout may decide which tile comes next, e.g. to respect its containers
+        let neighbour = self.focus_manager
+            .get_focused_window()
+            .and_then(|window| self.tile_manager.get_focus_neighbour(window, dir));
+        let windows = self.tile_manager.get_window_layout();
+        match self.focus_manager
+            .cycle_focus_in(dir, &windows, neighbour)
+            .and_then(|_| {
+                let focused = self.focus_manager.get_focused_window();
+                self.tile_manager.focus_shifted(focused)
+            }) {
+            Ok(_) => {}
+            // rather no focused window than one the layout does not know is focused
+            Err(_) => self.focus_manager.unfocus(),
+        }
     }
 
     fn get_window_info(&self, window: Window) -> Result<WindowWithInfo, Self::Error> {
@@ -112,6 +147,113 @@ impl TilingSupport for TilingWM {
     fn swap_windows(&mut self, dir: PrevOrNext) {
         self.tile_manager.swap_windows(dir, &self.focus_manager)
     }
+
+    fn swap_direction(&mut self, dir: Direction, wrap: bool) -> Result<(), Self::Error> {
+        self.tile_manager.swap_direction(dir, wrap, &self.focus_manager)
+    }
+}
+
+impl LayoutMessageSupport for TilingWM {
+    fn send_layout_message(&mut self, message: LayoutMessage) -> bool {
+        self.tile_manager.send_layout_message(message)
+    }
+}
+
+impl FocusPolicySupport for TilingWM {
+    fn get_focus_policy(&self) -> FocusPolicy {
+        self.focus_manager.focus_policy
+    }
+
+    fn set_focus_policy(&mut self, policy: FocusPolicy) {
+        self.focus_manager.focus_policy = policy
+    }
+
+    fn get_removal_focus(&self) -> RemovalFocus {
+        self.focus_manager.removal_focus
+    }
+
+    fn set_removal_focus(&mut self, removal_focus: RemovalFocus) {
+        self.focus_manager.removal_focus = removal_focus
+    }
+}
+
+impl TabSupport for TilingWM {
+    fn get_tabs(&self) -> Vec<Tab> {
+        self.tile_manager.get_tabs()
+    }
+}
+
+impl MasterTrait for TilingWM {
+    fn get_master_ratio(&self) -> u32 {
+        self.tile_manager.get_master_ratio()
+    }
+
+    fn get_master_count(&self) -> usize {
+        self.tile_manager.get_master_count()
+    }
+
+    fn shrink_master(&mut self) {
+        self.tile_manager.shrink_master()
+    }
+
+    fn expand_master(&mut self) {
+        self.tile_manager.expand_master()
+    }
+
+    fn inc_master(&mut self) {
+        self.tile_manager.inc_master()
+    }
+
+    fn dec_master(&mut self) {
+        self.tile_manager.dec_master()
+    }
+}
+
+impl LayoutSwitchTrait for TilingWM {
+    fn get_layout_name(&self) -> &str {
+        self.tile_manager.get_layout_name()
+    }
+
+    fn next_layout(&mut self) {
+        self.tile_manager.next_layout()
+    }
+
+    fn prev_layout(&mut self) {
+        self.tile_manager.prev_layout()
+    }
+
+    fn select_layout(&mut self, name: &str) -> bool {
+        self.tile_manager.select_layout(name)
+    }
+}
+
+impl StrutSupport for TilingWM {
+    fn get_usable_area(&self) -> Geometry {
+        self.tile_manager.get_usable_area()
+    }
+
+    fn get_static_strut(&self) -> Strut {
+        self.strut_manager.static_strut
+    }
+
+    fn set_static_strut(&mut self, strut: Strut) {
+        self.strut_manager.static_strut = strut;
+        self.tile_manager.set_strut(self.strut_manager.get_strut())
+    }
+
+    fn get_window_strut(&self, window: Window) -> Option<Strut> {
+        self.strut_manager.get_window_strut(window)
+    }
+
+    fn set_window_strut(&mut self, window: Window, strut: Strut) {
+        self.strut_manager.set_window_strut(window, strut);
+        self.tile_manager.set_strut(self.strut_manager.get_strut())
+    }
+
+    fn remove_window_strut(&mut self, window: Window) {
+        self.strut_manager.remove_window_strut(window);
+        self.tile_manager.set_strut(self.strut_manager.get_strut())
+    }
 }
 
 /// A manager for managing the tiling of windows
@@ -126,6 +268,8 @@ pub struct TileManager<TL: TilingLayout> {
     pub layout: TL,
     /// the screen
     pub screen: Screen,
+    /// the areas reserved by panels and docks, the tiles are kept out of them
+    pub strut: Strut,
 }
 
 impl<TL> Manager for TileManager<TL>
@@ -137,10 +281,13 @@ impl<TL> Manager for TileManager<TL>
         self.tiles.iter().map(|w| *w).collect()
     }
 
+    /// The added window gets the focus, so the layout puts it on top
     fn add_window(&mut self, window_with_info: WindowWithInfo) -> Result<(), StandardError> {
         if !self.is_managed(window_with_info.window) {
             self.tiles.push_back(window_with_info.window);
-            self.originals.insert(window_with_info.window, window_with_info);
+            self.originals.insert(window_with_info.window, window_with_info.clone());
+            self.layout.tile_added(window_with_info.window);
+            self.layout.focus_shifted(Some(window_with_info.window));
             Ok(())
         } else {
             Err(StandardError::AlReadyManagedWindow(window_with_info.window))
@@ -153,6 +300,7 @@ impl<TL> Manager for TileManager<TL>
             Some(i) => {
                 self.tiles.remove(i);
                 self.originals.remove(&window);
+                self.layout.tile_removed(window);
                 Ok(())
             }
         }
@@ -167,14 +315,31 @@ impl<TL> LayoutManager for TileManager<TL>
     }
 
     fn resize_screen(&mut self, screen: Screen) {
-        self.screen = screen
+        self.screen = screen;
+        self.layout.screen_resized(&self.get_usable_screen())
+    }
+
+    fn get_strut(&self) -> Strut {
+        self.strut
     }
 
+    fn set_strut(&mut self, strut: Strut) {
+        self.strut = strut;
+        self.layout.screen_resized(&self.get_usable_screen())
+    }
+
+    /// The layout lays out all tiles at once
     fn get_window_layout(&self) -> Vec<(Window, Geometry)> {
-        self.get_windows().iter()
-            // We know for sure the window argument in get_window_geometry is a managed window,
-            // because it comes directly from get_windows.
-            .map(|window| (*window, self.get_window_geometry(*window).unwrap()))
+        let tiles: HashMap<Window, Geometry> = self.layout
+            .get_window_geometries(&self.get_usable_screen(), &self.tiles)
+            .into_iter()
+            .collect();
+        self.layout
+            .get_stacking_order(&self.tiles)
+            .into_iter()
+            .filter_map(|window| {
+                tiles.get(&window).map(|tile| (window, self.fit_in_tile(window, *tile)))
+            })
             .collect()
     }
 
@@ -185,12 +350,22 @@ impl<TL> LayoutManager for TileManager<TL>
                 geometry: geometry,
                 float_or_tile: FloatOrTile::Tile,
                 fullscreen: false,
+                size_hints: self.get_size_hints(window),
+                properties: self.originals
+                    .get(&window)
+                    .map(|info| info.properties.clone())
+                    .unwrap_or_default(),
             })
         })
     }
 
-    fn focus_shifted(&mut self, _: Option<Window>) -> Result<(), Self::Error> {
-        // When the focus shifts, this LayoutManager does not need to do anything
+    /// Tell the layout about the focused tile, the focus of other windows is ignored so the
+    /// layout keeps showing the last focused tile.
+    fn focus_shifted(&mut self, window: Option<Window>) -> Result<(), Self::Error> {
+        match window {
+            Some(w) if self.is_managed(w) => self.layout.focus_shifted(window),
+            _ => {}
+        }
         Ok(())
     }
 }
@@ -211,6 +386,7 @@ impl<TL> TilingTrait for TileManager<TL>
         self.layout
             .swap_with_master(window, &mut self.tiles)
             .and_then(|_| focus_manager.focus_window(Some(window)))
+            .and_then(|_| self.focus_shifted(Some(window)))
     }
 
     /// Swap currently focused window in the focus_manager with the next or previous tile
@@ -220,6 +396,113 @@ impl<TL> TilingTrait for TileManager<TL>
             Some(())
         });
     }
+
+    /// Let the layout handle the message
+    fn send_layout_message(&mut self, message: LayoutMessage) -> bool {
+        self.layout.handle_message(message)
+    }
+
+    fn get_tabs(&self) -> Vec<Tab> {
+        self.layout
+            .get_tabs(&self.get_usable_screen(), &self.tiles)
+            .into_iter()
+            .map(|tab| Tab { geometry: self.to_usable_area(tab.geometry), ..tab })
+            .collect()
+    }
+
+    fn get_focus_neighbour(&self, window: Window, dir: PrevOrNext) -> Option<Window> {
+        self.layout.get_focus_neighbour(window, dir, &self.tiles)
+    }
+
+    fn get_next_tile(&self, window: Window) -> Option<Window> {
+        self.tiles.iter().position(|w| *w == window).and_then(|i| {
+            self.tiles.get(i + 1).or_else(|| i.checked_sub(1).and_then(|i| self.tiles.get(i)))
+        }).copied()
+    }
+
+    /// Let the layout swap the places of the tiles
+    fn swap_places(&mut self, window: Window, other: Window) -> Result<(), StandardError> {
+        match [window, other].iter().find(|w| !self.tiles.contains(w)) {
+            Some(unknown) => Err(StandardError::UnknownWindow(*unknown)),
+            None => {
+                self.layout.swap_tiles(window, other, &mut self.tiles);
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<TL> MasterTrait for TileManager<TL>
+    where TL: TilingLayout<Error = StandardError> + MasterTrait
+{
+    fn get_master_ratio(&self) -> u32 {
+        self.layout.get_master_ratio()
+    }
+
+    fn get_master_count(&self) -> usize {
+        self.layout.get_master_count()
+    }
+
+    fn shrink_master(&mut self) {
+        self.layout.shrink_master()
+    }
+
+    fn expand_master(&mut self) {
+        self.layout.expand_master()
+    }
+
+    fn inc_master(&mut self) {
+        self.layout.inc_master()
+    }
+
+    fn dec_master(&mut self) {
+        self.layout.dec_master()
+    }
+}
+
+/// Switching the layout keeps the order of the tiles
+impl<TL> LayoutSwitchTrait for TileManager<TL>
+    where TL: TilingLayout<Error = StandardError> + LayoutSwitchTrait
+{
+    fn get_layout_name(&self) -> &str {
+        self.layout.get_layout_name()
+    }
+
+    fn next_layout(&mut self) {
+        self.layout.next_layout()
+    }
+
+    fn prev_layout(&mut self) {
+        self.layout.prev_layout()
+    }
+
+    fn select_layout(&mut self, name: &str) -> bool {
+        self.layout.select_layout(name)
+    }
+}
+
+impl<TL> BorderTrait for TileManager<TL>
+    where TL: TilingLayout<Error = StandardError> + BorderTrait
+{
+    fn get_border_width(&self) -> BorderWidth {
+        self.layout.get_border_width()
+    }
+
+    fn set_border_width(&mut self, width: BorderWidth) {
+        self.layout.set_border_width(width)
+    }
+}
+
+impl<TL> GapTrait for TileManager<TL>
+    where TL: TilingLayout<Error = StandardError> + GapTrait
+{
+    fn get_gap(&self) -> GapSize {
+        self.layout.get_gap()
+    }
+
+    fn set_gap(&mut self, gap: GapSize) {
+        self.layout.set_gap(gap)
+    }
 }
 
 
@@ -227,12 +510,14 @@ impl<TL> TileManager<TL>
     where TL: TilingLayout<Error = StandardError>
 {
     /// A new, empty TileManager
-    pub fn new(screen: Screen, layout: TL) -> TileManager<TL> {
+    pub fn new(screen: Screen, mut layout: TL) -> TileManager<TL> {
+        layout.screen_resized(&screen);
         TileManager {
             tiles: VecDeque::new(),
             originals: HashMap::new(),
             layout: layout,
             screen: screen,
+            strut: Strut::none(),
         }
     }
 
@@ -240,18 +525,97 @@ impl<TL> TileManager<TL>
     pub fn get_original_window_info(&self,
                                     window: Window)
                                     -> Result<WindowWithInfo, StandardError> {
-        self.originals.get(&window).map(|w| *w).ok_or(StandardError::UnknownWindow(window))
+        self.originals.get(&window).cloned().ok_or(StandardError::UnknownWindow(window))
+    }
+
+    /// Return the size hints the given window was added with
+    fn get_size_hints(&self, window: Window) -> SizeHints {
+        self.originals.get(&window).map_or(SizeHints::none(), |w| w.size_hints)
+    }
+
+    /// The usable area as a screen, which is the screen the layout tiles
+    fn get_usable_screen(&self) -> Screen {
+        let area = self.get_usable_area();
+        Screen {
+            width: area.width,
+            height: area.height,
+        }
     }
 
-    /// Return the current Geometry for the given window
+    /// Move a geometry on the usable screen to the usable area of the real screen
+    fn to_usable_area(&self, geometry: Geometry) -> Geometry {
+        let area = self.get_usable_area();
+        Geometry {
+            x: geometry.x + area.x,
+            y: geometry.y + area.y,
+            ..geometry
+        }
+    }
+
+    /// Return the current Geometry for the given window, the window is fitted in its tile
+    /// according to its size hints. The tiles are kept in the usable area of the screen.
     pub fn get_window_geometry(&self, window: Window) -> Result<Geometry, StandardError> {
-        self.layout.get_window_geometry(window, &self.get_screen(), &self.tiles)
+        self.layout
+            .get_window_geometry(window, &self.get_usable_screen(), &self.tiles)
+            .map(|tile| self.fit_in_tile(window, tile))
+    }
+
+    /// The geometry of the window in the given tile of the layout, according to the size hints
+    /// of the window
+    fn fit_in_tile(&self, window: Window, tile: Geometry) -> Geometry {
+        self.get_size_hints(window).fit_in(self.to_usable_area(tile))
     }
 }
 
 /// A Layout algorithm for Tiling window managers as described in assigment b.
+///
+/// The master area can be shrunk and expanded in steps of its `ratio_step` percent and can hold
+/// more than one window, like the Tall layout of xmonad.
 #[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
-pub struct VerticalLayout {}
+pub struct VerticalLayout {
+    /// the master area, see MasterTrait
+    pub master: MasterArea,
+}
+
+impl VerticalLayout {
+    /// A VerticalLayout with one master window taking half of the screen, resizable in steps of
+    /// 5 percent
+    pub fn new() -> VerticalLayout {
+        VerticalLayout { master: MasterArea::new() }
+    }
+}
+
+impl Default for VerticalLayout {
+    fn default() -> VerticalLayout {
+        VerticalLayout::new()
+    }
+}
+
+impl MasterTrait for VerticalLayout {
+    fn get_master_ratio(&self) -> u32 {
+        self.master.get_master_ratio()
+    }
+
+    fn get_master_count(&self) -> usize {
+        self.master.get_master_count()
+    }
+
+    fn shrink_master(&mut self) {
+        self.master.shrink_master()
+    }
+
+    fn expand_master(&mut self) {
+        self.master.expand_master()
+    }
+
+    fn inc_master(&mut self) {
+        self.master.inc_master()
+    }
+
+    fn dec_master(&mut self) {
+        self.master.dec_master()
+    }
+}
 
 impl TilingLayout for VerticalLayout {
     type Error = StandardError;
@@ -260,7 +624,7 @@ impl TilingLayout for VerticalLayout {
         return tiles.front().map(|w| *w);
     }
 
-    fn swap_with_master(&self,
+    fn swap_with_master(&mut self,
                         window: Window,
                         tiles: &mut VecDeque<Window>)
                         -> Result<(), Self::Error> {
@@ -283,7 +647,7 @@ impl TilingLayout for VerticalLayout {
         }
     }
 
-    fn swap_windows(&self, window: Window, dir: PrevOrNext, tiles: &mut VecDeque<Window>) {
+    fn swap_windows(&mut self, window: Window, dir: PrevOrNext, tiles: &mut VecDeque<Window>) {
         tiles.iter().position(|w| *w == window).and_then(|index| {
             let n = tiles.len() as i32;
             let neighbour = (neighbour_of(&(index as i32), dir) + n) % n;
@@ -298,50 +662,57 @@ impl TilingLayout for VerticalLayout {
                            screen: &Screen,
                            tiles: &VecDeque<Window>)
                            -> Result<Geometry, Self::Error> {
-        let only_master = tiles.len() <= 1;
-        let master_tile_width = screen.width / if only_master { 1 } else { 2 };
+        let masters = cmp::min(self.master.count, tiles.len());
+        // without side tiles the master area takes the whole screen.
+        let master_tile_width = if tiles.len() <= masters {
+            screen.width
+        } else {
+            screen.width * self.master.ratio / 100
+        };
         match tiles.iter().position(|w| *w == window) {
             None => Err(StandardError::UnknownWindow(window)),
-            Some(0) => {
-                Ok(Geometry {
-                    x: 0,
-                    y: 0,
-                    width: master_tile_width,
-                    height: screen.height,
-                })
+            Some(index) if index < masters => {
+                Ok(column_geometry(0, master_tile_width, screen.height, index, masters))
             }
             Some(index) => {
                 // side tiles should get the remaining width of the screen.
-                let remaining_width = screen.width - master_tile_width;
-                let last_index = tiles.len() - 1;
-                let side_tile_height = if tiles.len() > 1 {
-                    screen.height / (tiles.len() - 1) as u32
-                } else {
-                    0
-                };
-                if index != last_index {
-                    Ok(Geometry {
-                        x: (screen.width / 2) as i32,
-                        y: (index as i32 - 1) * side_tile_height as i32,
-                        width: remaining_width,
-                        height: side_tile_height,
-                    })
-                } else {
-                    // the last side tile should get the remaining height of the screen.
-                    let remaining_height = (screen.height as i32 -
-                                            side_tile_height as i32 *
-                                            (last_index as i32 -
-                                             1)) as u32;
-                    Ok(Geometry {
-                        x: (screen.width / 2) as i32,
-                        y: (index as i32 - 1) * side_tile_height as i32,
-                        width: screen.width - (screen.width / 2),
-                        height: remaining_height,
-                    })
-                }
+                Ok(column_geometry(master_tile_width as i32,
+                                   screen.width - master_tile_width,
+                                   screen.height,
+                                   index - masters,
+                                   tiles.len() - masters))
             }
         }
     }
+
+    /// Handles the messages to adjust the master area
+    fn handle_message(&mut self, message: LayoutMessage) -> bool {
+        match message {
+            LayoutMessage::ShrinkMaster => self.shrink_master(),
+            LayoutMessage::ExpandMaster => self.expand_master(),
+            LayoutMessage::IncMaster => self.inc_master(),
+            LayoutMessage::DecMaster => self.dec_master(),
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// The geometry of the tile at the given index in a column of the given amount of tiles.
+fn column_geometry(x: i32, width: u32, height: u32, index: usize, count: usize) -> Geometry {
+    let tile_height = height / count as u32;
+    let y = index as i32 * tile_height as i32;
+    Geometry {
+        x,
+        y,
+        width,
+        // the last tile should get the remaining height of the screen.
+        height: if index == count - 1 {
+            height - y as u32
+        } else {
+            tile_height
+        },
+    }
 }
 
 fn neighbour_of(&index: &i32, dir: PrevOrNext) -> i32 {
@@ -354,7 +725,7 @@ fn neighbour_of(&index: &i32, dir: PrevOrNext) -> i32 {
 #[cfg(test)]
 mod vertical_layout_tests {
     use super::VerticalLayout;
-    use wm_common::TilingLayout;
+    use wm_common::{MasterArea, MasterTrait, TilingLayout};
     use std::collections::VecDeque;
     use cplwm_api::types::*;
 
@@ -371,7 +742,7 @@ mod vertical_layout_tests {
     #[test]
     fn test_vertical_layout_no_window() {
         // Initialize new VerticalLayout strategy
-        let layout = VerticalLayout {};
+        let layout = VerticalLayout::new();
         // Initialize empty tile Deque
         let tiles = VecDeque::new();
 
@@ -382,7 +753,7 @@ mod vertical_layout_tests {
     #[test]
     fn test_vertical_layout_one_window() {
         // Initialize new VerticalLayout strategy
-        let layout = VerticalLayout {};
+        let layout = VerticalLayout::new();
         // Initialize empty tile Deque
         let mut tiles = VecDeque::new();
         // Push one window on the Deque
@@ -401,7 +772,7 @@ mod vertical_layout_tests {
     #[test]
     fn test_vertical_layout_two_windows() {
         // Initialize new VerticalLayout strategy
-        let layout = VerticalLayout {};
+        let layout = VerticalLayout::new();
         // Initialize empty tile Deque
         let mut tiles = VecDeque::new();
         // Push 2 tiles on the Deque, the first one will be the master in this layout.
@@ -432,7 +803,7 @@ mod vertical_layout_tests {
     #[test]
     fn test_vertical_layout_multiple_windows_regular_screen() {
         // Initialize new VerticalLayout strategy
-        let layout = VerticalLayout {};
+        let layout = VerticalLayout::new();
         // Initialize empty tile Deque
         let mut tiles = VecDeque::new();
         // Push 4 tiles on the Deque, the first one will be the master in this layout.
@@ -479,7 +850,7 @@ mod vertical_layout_tests {
     #[test]
     fn test_vertical_layout_multiple_windows_irregular_screen() {
         // Initialize new VerticalLayout strategy
-        let layout = VerticalLayout {};
+        let layout = VerticalLayout::new();
         // Initialize empty tile Deque
         let mut tiles = VecDeque::new();
         // Push 4 tiles on the Deque, the first one will be the master in this layout.
@@ -522,12 +893,120 @@ mod vertical_layout_tests {
                    },
                    layout.get_window_geometry(4, &SCREEN2, &tiles).ok().unwrap());
     }
+
+    #[test]
+    fn test_vertical_layout_shrunk_master() {
+        let mut layout = VerticalLayout::new();
+        layout.shrink_master();
+        layout.shrink_master();
+        assert_eq!(40, layout.get_master_ratio());
+        let mut tiles = VecDeque::new();
+        tiles.push_back(1);
+        tiles.push_back(2);
+        tiles.push_back(3);
+
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 120,
+                       height: 401,
+                   },
+                   layout.get_window_geometry(1, &SCREEN2, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 120,
+                       y: 0,
+                       width: 181,
+                       height: 200,
+                   },
+                   layout.get_window_geometry(2, &SCREEN2, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 120,
+                       y: 200,
+                       width: 181,
+                       height: 201,
+                   },
+                   layout.get_window_geometry(3, &SCREEN2, &tiles).ok().unwrap());
+    }
+
+    #[test]
+    fn test_vertical_layout_ratio_bounds() {
+        let mut layout = VerticalLayout {
+            master: MasterArea {
+                ratio: 50,
+                ratio_step: 20,
+                count: 1,
+            },
+        };
+        layout.expand_master();
+        layout.expand_master();
+        // 50 + 3 * 20 would cover the whole screen
+        layout.expand_master();
+        assert_eq!(90, layout.get_master_ratio());
+        for _ in 0..5 {
+            layout.shrink_master();
+        }
+        assert_eq!(10, layout.get_master_ratio());
+    }
+
+    #[test]
+    fn test_vertical_layout_multiple_masters_irregular_screen() {
+        let mut layout = VerticalLayout::new();
+        layout.inc_master();
+        layout.inc_master();
+        assert_eq!(3, layout.get_master_count());
+        let mut tiles = VecDeque::new();
+        tiles.push_back(1);
+        tiles.push_back(2);
+        tiles.push_back(3);
+        tiles.push_back(4);
+
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 150,
+                       height: 133,
+                   },
+                   layout.get_window_geometry(1, &SCREEN2, &tiles).ok().unwrap());
+
+        // last master should get remaining screen space.
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 266,
+                       width: 150,
+                       height: 135,
+                   },
+                   layout.get_window_geometry(3, &SCREEN2, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 150,
+                       y: 0,
+                       width: 151,
+                       height: 401,
+                   },
+                   layout.get_window_geometry(4, &SCREEN2, &tiles).ok().unwrap());
+
+        // the master count never drops below one
+        layout.dec_master();
+        layout.dec_master();
+        layout.dec_master();
+        assert_eq!(1, layout.get_master_count());
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use wm_common::tests::window_manager;
+    use wm_common::tests::layout_message_support;
+    use wm_common::tests::tab_support;
+    use wm_common::tests::layout_switch_support;
+    use wm_common::tests::master_support;
     use wm_common::tests::tiling_support;
+    use wm_common::tests::size_hint_support;
+    use wm_common::tests::direction_support;
+    use wm_common::tests::focus_policy_support;
+    use wm_common::tests::strut_support;
     use super::TilingWM;
     use super::VerticalLayout;
 
@@ -579,11 +1058,86 @@ mod tests {
 
     #[test]
     fn test_swap_windows() {
-        tiling_support::test_swap_windows::<TilingWM, VerticalLayout>(VerticalLayout {});
+        tiling_support::test_swap_windows::<TilingWM, VerticalLayout>(VerticalLayout::new());
     }
 
     #[test]
     fn test_tiling_layout() {
-        tiling_support::test_get_window_info::<TilingWM, VerticalLayout>(VerticalLayout {});
+        tiling_support::test_get_window_info::<TilingWM, VerticalLayout>(VerticalLayout::new());
+    }
+
+    #[test]
+    fn test_tiled_size_hints() {
+        size_hint_support::test_tiled_size_hints::<TilingWM>();
+    }
+
+    #[test]
+    fn test_tiles_in_usable_area() {
+        strut_support::test_tiles_in_usable_area::<TilingWM>();
+    }
+
+    #[test]
+    fn test_remove_window_with_strut() {
+        strut_support::test_remove_window_with_strut::<TilingWM>();
+    }
+
+    #[test]
+    fn test_focus_direction() {
+        direction_support::test_focus_direction::<TilingWM>();
+    }
+
+    #[test]
+    fn test_swap_direction() {
+        direction_support::test_swap_direction::<TilingWM>();
+    }
+
+    #[test]
+    fn test_stack_order() {
+        focus_policy_support::test_stack_order::<TilingWM>();
+    }
+
+    #[test]
+    fn test_most_recently_used() {
+        focus_policy_support::test_most_recently_used::<TilingWM>();
+    }
+
+    #[test]
+    fn test_removal_focus() {
+        focus_policy_support::test_removal_focus::<TilingWM>();
+    }
+
+    #[test]
+    fn test_shrink_and_expand_master() {
+        master_support::test_shrink_and_expand_master::<TilingWM>();
+    }
+
+    #[test]
+    fn test_inc_and_dec_master() {
+        master_support::test_inc_and_dec_master::<TilingWM>();
+    }
+
+    #[test]
+    fn test_master_messages() {
+        layout_message_support::test_master_messages::<TilingWM>();
+    }
+
+    #[test]
+    fn test_no_tabs_without_tab_bar() {
+        tab_support::test_no_tabs_without_tab_bar::<TilingWM>();
+    }
+
+    #[test]
+    fn test_select_layout() {
+        layout_switch_support::test_select_layout::<TilingWM>();
+    }
+
+    #[test]
+    fn test_next_and_prev_layout() {
+        layout_switch_support::test_next_and_prev_layout::<TilingWM>();
+    }
+
+    #[test]
+    fn test_select_bsp_layout() {
+        layout_switch_support::test_select_bsp_layout::<TilingWM>();
     }
 }
@@ -26,14 +26,22 @@
 //!
 
 // Add imports here
-use cplwm_api::types::{FloatOrTile, Geometry, PrevOrNext, Screen, Window, WindowLayout,
+use cplwm_api::types::{BorderColours, BorderWidth, BorderedWindowLayout, Direction, FloatOrTile,
+                       GapSize, Geometry, PrevOrNext, Screen, Strut, Tab, Window, WindowLayout,
                        WindowWithInfo};
-use cplwm_api::wm::{FloatSupport, TilingSupport, WindowManager};
+use cplwm_api::wm::{BorderSupport, FloatSupport, GapSupport, StrutSupport, TabSupport,
+                    TilingSupport, WindowManager};
 
-use wm_common::{FloatAndTileTrait, FloatTrait, LayoutManager, Manager, TilingLayout, TilingTrait};
+use wm_common::{BorderTrait, FloatAndTileTrait, FloatTrait, FocusPolicySupport, GapTrait,
+                LayoutManager, LayoutMessage, LayoutMessageSupport, LayoutSwitchTrait, Manager,
+                MasterTrait, TilingLayout, TilingTrait};
 use wm_common::error::{FloatWMError, StandardError};
-use a_fullscreen_wm::FocusManager;
-use b_tiling_wm::{TileManager, VerticalLayout};
+use a_fullscreen_wm::{FocusManager, FocusPolicy, RemovalFocus};
+use b_tiling_wm::TileManager;
+use f_gaps::GapLayout;
+use layouts::border::{BorderLayout, BorderManager};
+use layouts::selector::LayoutSelector;
+use struts::StrutManager;
 
 /// The public type.
 pub type WMName = FloatWM;
@@ -45,7 +53,11 @@ pub struct FloatWM {
     /// The Manager for the current focus
     pub focus_manager: FocusManager,
     /// The manager to manage the tiles and floating windows
-    pub float_or_tile_manager: FloatOrTileManager<VerticalLayout>,
+    pub float_or_tile_manager: FloatOrTileManager<BorderLayout<GapLayout<LayoutSelector>>>,
+    /// The manager for the border colours and the urgent windows
+    pub border_manager: BorderManager,
+    /// The manager for the areas reserved by panels and docks
+    pub strut_manager: StrutManager,
 }
 
 
@@ -54,9 +66,12 @@ impl WindowManager for FloatWM {
     type Error = FloatWMError;
 
     fn new(screen: Screen) -> FloatWM {
+        let layout = BorderLayout::new(GapLayout::new(LayoutSelector::standard(), 0), 0);
         FloatWM {
             focus_manager: FocusManager::new(),
-            float_or_tile_manager: FloatOrTileManager::new(screen, VerticalLayout {}),
+            float_or_tile_manager: FloatOrTileManager::new(screen, layout),
+            border_manager: BorderManager::new(),
+            strut_manager: StrutManager::new(),
         }
     }
 
@@ -70,15 +85,34 @@ impl WindowManager for FloatWM {
 
     fn add_window(&mut self, window_with_info: WindowWithInfo) -> Result<(), Self::Error> {
         self.focus_manager
-            .add_window(window_with_info)
+            .add_window(window_with_info.clone())
             .map_err(|error| error.to_float_error())
             .and_then(|_| self.float_or_tile_manager.add_window(window_with_info))
     }
 
     fn remove_window(&mut self, window: Window) -> Result<(), Self::Error> {
+        let was_focused = self.get_focused_window() == Some(window);
+        let next_tile = self.float_or_tile_manager.get_next_tile(window);
         match self.focus_manager.remove_window(window) {
             Err(error) => Err(error.to_float_error()),
-            Ok(_) => self.float_or_tile_manager.remove_window(window),
+            Ok(_) => {
+                self.border_manager.remove_window(window);
+                self.remove_window_strut(window);
+                self.float_or_tile_manager
+                    .remove_window(window)
+                    .and_then(|_| if was_focused {
+                        let master = self.float_or_tile_manager.get_master_window();
+                        self.focus_manager
+                            .focus_after_removal(next_tile, master)
+                            .map_err(|error| error.to_float_error())
+                    } else {
+                        Ok(())
+                    })
+                    .and_then(|_| {
+                        let focused = self.focus_manager.get_focused_window();
+                        self.float_or_tile_manager.focus_shifted(focused)
+                    })
+            }
         }
     }
 
@@ -97,8 +131,22 @@ impl WindowManager for FloatWM {
     }
 
     fn cycle_focus(&mut self, dir: PrevOrNext) {
-        self.focus_manager.cycle_focus(dir);
-        self.float_or_tile_manager.focus_shifted(self.focus_manager.get_focused_window()).is_ok();
+        // the layout may decide which tile comes next, e.g. to respect its containers
+        let neighbour = self.focus_manager
+            .get_focused_window()
+            .and_then(|window| self.float_or_tile_manager.get_focus_neighbour(window, dir));
+        let windows = self.float_or_tile_manager.get_window_layout();
+        match self.focus_manager
+            .cycle_focus_in(dir, &windows, neighbour)
+            .map_err(|error| error.to_float_error())
+            .and_then(|_| {
+                let focused = self.focus_manager.get_focused_window();
+                self.float_or_tile_manager.focus_shifted(focused)
+            }) {
+            Ok(_) => {}
+            // rather no focused window than one the layout does not know is focused
+            Err(_) => self.focus_manager.unfocus(),
+        }
     }
 
     fn get_window_info(&self, window: Window) -> Result<WindowWithInfo, Self::Error> {
@@ -126,6 +174,159 @@ impl TilingSupport for FloatWM {
     fn swap_windows(&mut self, dir: PrevOrNext) {
         self.float_or_tile_manager.swap_windows(dir, &self.focus_manager)
     }
+
+    fn swap_direction(&mut self, dir: Direction, wrap: bool) -> Result<(), Self::Error> {
+        self.float_or_tile_manager.swap_direction(dir, wrap, &self.focus_manager)
+    }
+}
+
+impl LayoutMessageSupport for FloatWM {
+    fn send_layout_message(&mut self, message: LayoutMessage) -> bool {
+        self.float_or_tile_manager.send_layout_message(message)
+    }
+}
+
+impl FocusPolicySupport for FloatWM {
+    fn get_focus_policy(&self) -> FocusPolicy {
+        self.focus_manager.focus_policy
+    }
+
+    fn set_focus_policy(&mut self, policy: FocusPolicy) {
+        self.focus_manager.focus_policy = policy
+    }
+
+    fn get_removal_focus(&self) -> RemovalFocus {
+        self.focus_manager.removal_focus
+    }
+
+    fn set_removal_focus(&mut self, removal_focus: RemovalFocus) {
+        self.focus_manager.removal_focus = removal_focus
+    }
+}
+
+impl TabSupport for FloatWM {
+    fn get_tabs(&self) -> Vec<Tab> {
+        self.float_or_tile_manager.get_tabs()
+    }
+}
+
+impl MasterTrait for FloatWM {
+    fn get_master_ratio(&self) -> u32 {
+        self.float_or_tile_manager.get_master_ratio()
+    }
+
+    fn get_master_count(&self) -> usize {
+        self.float_or_tile_manager.get_master_count()
+    }
+
+    fn shrink_master(&mut self) {
+        self.float_or_tile_manager.shrink_master()
+    }
+
+    fn expand_master(&mut self) {
+        self.float_or_tile_manager.expand_master()
+    }
+
+    fn inc_master(&mut self) {
+        self.float_or_tile_manager.inc_master()
+    }
+
+    fn dec_master(&mut self) {
+        self.float_or_tile_manager.dec_master()
+    }
+}
+
+impl LayoutSwitchTrait for FloatWM {
+    fn get_layout_name(&self) -> &str {
+        self.float_or_tile_manager.get_layout_name()
+    }
+
+    fn next_layout(&mut self) {
+        self.float_or_tile_manager.next_layout()
+    }
+
+    fn prev_layout(&mut self) {
+        self.float_or_tile_manager.prev_layout()
+    }
+
+    fn select_layout(&mut self, name: &str) -> bool {
+        self.float_or_tile_manager.select_layout(name)
+    }
+}
+
+impl BorderSupport for FloatWM {
+    fn get_border_width(&self) -> BorderWidth {
+        self.float_or_tile_manager.get_border_width()
+    }
+
+    fn set_border_width(&mut self, width: BorderWidth) {
+        self.float_or_tile_manager.set_border_width(width)
+    }
+
+    fn get_border_colours(&self) -> BorderColours {
+        self.border_manager.colours
+    }
+
+    fn set_border_colours(&mut self, colours: BorderColours) {
+        self.border_manager.colours = colours
+    }
+
+    fn is_urgent(&self, window: Window) -> bool {
+        self.border_manager.is_urgent(window)
+    }
+
+    fn set_urgent(&mut self, window: Window, urgent: bool) -> Result<(), Self::Error> {
+        if self.is_managed(window) {
+            self.border_manager.set_urgent(window, urgent);
+            Ok(())
+        } else {
+            Err(FloatWMError::UnknownWindow(window))
+        }
+    }
+
+    fn get_bordered_window_layout(&self) -> BorderedWindowLayout {
+        self.border_manager.get_bordered_window_layout(self, self.get_border_width())
+    }
+}
+
+impl GapSupport for FloatWM {
+    fn get_gap(&self) -> GapSize {
+        self.float_or_tile_manager.get_gap()
+    }
+
+    fn set_gap(&mut self, gap: GapSize) {
+        self.float_or_tile_manager.set_gap(gap)
+    }
+}
+
+/// Only the tiles are kept out of the reserved areas, floating windows may cover them.
+impl StrutSupport for FloatWM {
+    fn get_usable_area(&self) -> Geometry {
+        self.float_or_tile_manager.get_usable_area()
+    }
+
+    fn get_static_strut(&self) -> Strut {
+        self.strut_manager.static_strut
+    }
+
+    fn set_static_strut(&mut self, strut: Strut) {
+        self.strut_manager.static_strut = strut;
+        self.float_or_tile_manager.set_strut(self.strut_manager.get_strut())
+    }
+
+    fn get_window_strut(&self, window: Window) -> Option<Strut> {
+        self.strut_manager.get_window_strut(window)
+    }
+
+    fn set_window_strut(&mut self, window: Window, strut: Strut) {
+        self.strut_manager.set_window_strut(window, strut);
+        self.float_or_tile_manager.set_strut(self.strut_manager.get_strut())
+    }
+
+    fn remove_window_strut(&mut self, window: Window) {
+        self.strut_manager.remove_window_strut(window);
+        self.float_or_tile_manager.set_strut(self.strut_manager.get_strut())
+    }
 }
 
 impl FloatSupport for FloatWM {
@@ -197,7 +398,9 @@ impl<T: TilingLayout<Error = StandardError>> LayoutManager for FloatOrTileManage
                 if self.float_manager.is_managed(w) {
                     self.float_manager.focus_shifted(Some(w))
                 } else {
-                    Ok(())
+                    self.tile_manager
+                        .focus_shifted(Some(w))
+                        .map_err(|error| error.to_float_error())
                 }
             }
         }
@@ -218,6 +421,15 @@ impl<T: TilingLayout<Error = StandardError>> LayoutManager for FloatOrTileManage
         self.tile_manager.resize_screen(screen);
         self.float_manager.resize_screen(screen);
     }
+
+    fn get_strut(&self) -> Strut {
+        self.tile_manager.get_strut()
+    }
+
+    /// only the tiles are kept out of the reserved areas
+    fn set_strut(&mut self, strut: Strut) {
+        self.tile_manager.set_strut(strut)
+    }
 }
 
 impl<T: TilingLayout<Error = StandardError>> TilingTrait for FloatOrTileManager<T> {
@@ -259,6 +471,75 @@ impl<T: TilingLayout<Error = StandardError>> TilingTrait for FloatOrTileManager<
             }
         }
     }
+
+    fn send_layout_message(&mut self, message: LayoutMessage) -> bool {
+        self.tile_manager.send_layout_message(message)
+    }
+
+    fn get_tabs(&self) -> Vec<Tab> {
+        self.tile_manager.get_tabs()
+    }
+
+    /// only the layout of the tiles decides, floating windows are cycled as usual
+    fn get_focus_neighbour(&self, window: Window, dir: PrevOrNext) -> Option<Window> {
+        if self.is_tiled(window) {
+            self.tile_manager.get_focus_neighbour(window, dir)
+        } else {
+            None
+        }
+    }
+
+    /// floating windows have no next tile
+    fn get_next_tile(&self, window: Window) -> Option<Window> {
+        if self.is_tiled(window) {
+            self.tile_manager.get_next_tile(window)
+        } else {
+            None
+        }
+    }
+
+    /// Tiles swap their places in the layout, floating windows swap their positions and keep
+    /// their sizes.
+    fn swap_places(&mut self, window: Window, other: Window) -> Result<(), FloatWMError> {
+        if !self.can_swap(window, other) {
+            Ok(())
+        } else if self.is_tiled(window) {
+            self.tile_manager
+                .swap_places(window, other)
+                .map_err(|error| error.to_float_error())
+        } else {
+            self.float_manager
+                .get_window_info(window)
+                .and_then(|info| {
+                    self.float_manager
+                        .get_window_info(other)
+                        .map(|other_info| (info.geometry, other_info.geometry))
+                })
+                .and_then(|(geometry, other_geometry)| {
+                    self.float_manager
+                        .set_window_geometry(window,
+                                             Geometry {
+                                                 x: other_geometry.x,
+                                                 y: other_geometry.y,
+                                                 ..geometry
+                                             })
+                        .and_then(|_| {
+                            self.float_manager.set_window_geometry(other,
+                                                                   Geometry {
+                                                                       x: geometry.x,
+                                                                       y: geometry.y,
+                                                                       ..other_geometry
+                                                                   })
+                        })
+                })
+        }
+    }
+
+    /// a tile only swaps with a tile, a floating window only with a floating window
+    fn can_swap(&self, window: Window, other: Window) -> bool {
+        (self.is_tiled(window) && self.is_tiled(other)) ||
+        (self.is_floating(window) && self.is_floating(other))
+    }
 }
 
 impl<T: TilingLayout<Error = StandardError>> FloatTrait for FloatOrTileManager<T> {
@@ -301,10 +582,8 @@ impl<T: TilingLayout<Error = StandardError>> FloatAndTileTrait for FloatOrTileMa
                         self.float_manager.remove_window(window).and_then(|_| {
                             self.tile_manager
                                 .add_window(WindowWithInfo {
-                                    window: window_with_info.window,
-                                    geometry: window_with_info.geometry,
                                     float_or_tile: FloatOrTile::Tile,
-                                    fullscreen: window_with_info.fullscreen,
+                                    ..window_with_info
                                 })
                                 .map_err(|error| error.to_float_error())
                         })
@@ -319,10 +598,8 @@ impl<T: TilingLayout<Error = StandardError>> FloatAndTileTrait for FloatOrTileMa
                                 .map_err(|error| error.to_float_error())
                                 .and_then(|_| {
                                     self.float_manager.add_window(WindowWithInfo {
-                                        window: window_with_info.window,
-                                        geometry: window_with_info.geometry,
                                         float_or_tile: FloatOrTile::Float,
-                                        fullscreen: window_with_info.fullscreen,
+                                        ..window_with_info
                                     })
                                 })
                         })
@@ -333,6 +610,74 @@ impl<T: TilingLayout<Error = StandardError>> FloatAndTileTrait for FloatOrTileMa
     }
 }
 
+impl<T: TilingLayout<Error = StandardError> + MasterTrait> MasterTrait for FloatOrTileManager<T> {
+    fn get_master_ratio(&self) -> u32 {
+        self.tile_manager.get_master_ratio()
+    }
+
+    fn get_master_count(&self) -> usize {
+        self.tile_manager.get_master_count()
+    }
+
+    fn shrink_master(&mut self) {
+        self.tile_manager.shrink_master()
+    }
+
+    fn expand_master(&mut self) {
+        self.tile_manager.expand_master()
+    }
+
+    fn inc_master(&mut self) {
+        self.tile_manager.inc_master()
+    }
+
+    fn dec_master(&mut self) {
+        self.tile_manager.dec_master()
+    }
+}
+
+impl<T> LayoutSwitchTrait for FloatOrTileManager<T>
+    where T: TilingLayout<Error = StandardError> + LayoutSwitchTrait
+{
+    fn get_layout_name(&self) -> &str {
+        self.tile_manager.get_layout_name()
+    }
+
+    fn next_layout(&mut self) {
+        self.tile_manager.next_layout()
+    }
+
+    fn prev_layout(&mut self) {
+        self.tile_manager.prev_layout()
+    }
+
+    fn select_layout(&mut self, name: &str) -> bool {
+        self.tile_manager.select_layout(name)
+    }
+}
+
+/// Only the tiles make room for their border, floating windows keep their geometry.
+impl<T: TilingLayout<Error = StandardError> + BorderTrait> BorderTrait for FloatOrTileManager<T> {
+    fn get_border_width(&self) -> BorderWidth {
+        self.tile_manager.get_border_width()
+    }
+
+    fn set_border_width(&mut self, width: BorderWidth) {
+        self.tile_manager.set_border_width(width)
+    }
+}
+
+/// Only the tiles get the gap, floating windows keep their geometry.
+impl<T: TilingLayout<Error = StandardError> + GapTrait> GapTrait for FloatOrTileManager<T> {
+    fn get_gap(&self) -> GapSize {
+        self.tile_manager.get_gap()
+    }
+
+    fn set_gap(&mut self, gap: GapSize) {
+        self.tile_manager.set_gap(gap)
+    }
+}
+
 impl<T: TilingLayout<Error = StandardError>> FloatOrTileManager<T> {
     /// creates empty FloatOrTileManager
     pub fn new(screen: Screen, tiling_layout: T) -> FloatOrTileManager<T> {
@@ -394,7 +739,7 @@ impl LayoutManager for FloatManager {
     fn get_window_info(&self, window: Window) -> Result<WindowWithInfo, FloatWMError> {
         match self.floaters.iter().position(|w| w.window == window) {
             None => Err(FloatWMError::UnknownWindow(window)),
-            Some(i) => Ok(self.floaters[i]),
+            Some(i) => Ok(self.floaters[i].clone()),
         }
     }
 
@@ -412,6 +757,13 @@ impl LayoutManager for FloatManager {
     fn resize_screen(&mut self, screen: Screen) {
         self.screen = screen
     }
+
+    /// Floating windows may cover the reserved areas, so nothing is reserved for them
+    fn get_strut(&self) -> Strut {
+        Strut::none()
+    }
+
+    fn set_strut(&mut self, _: Strut) {}
 }
 
 impl FloatTrait for FloatManager {
@@ -422,7 +774,15 @@ impl FloatTrait for FloatManager {
         match self.floaters.iter().position(|w| w.window == window) {
             None => Err(FloatWMError::UnknownWindow(window)),
             Some(i) => {
-                self.floaters[i].geometry = new_geometry;
+                // the size is clamped to the size hints, the position is kept
+                let (width, height) = self.floaters[i]
+                    .size_hints
+                    .constrain(new_geometry.width, new_geometry.height);
+                self.floaters[i].geometry = Geometry {
+                    width,
+                    height,
+                    ..new_geometry
+                };
                 Ok(())
             }
         }
@@ -441,11 +801,22 @@ impl FloatManager {
 #[cfg(test)]
 mod tests {
     use wm_common::tests::window_manager;
+    use wm_common::tests::layout_message_support;
+    use wm_common::tests::tab_support;
+    use wm_common::tests::layout_switch_support;
+    use wm_common::tests::master_support;
+    use wm_common::tests::gap_support;
+    use wm_common::tests::border_support;
     use wm_common::tests::tiling_support;
+    use wm_common::tests::size_hint_support;
+    use wm_common::tests::direction_support;
+    use wm_common::tests::focus_policy_support;
+    use wm_common::tests::strut_support;
     use wm_common::tests::float_support;
     use wm_common::tests::float_and_tile_support;
     use super::FloatWM;
     use b_tiling_wm::VerticalLayout;
+    use f_gaps::GapLayout;
 
     #[test]
     fn test_empty_tiling_wm() {
@@ -495,12 +866,67 @@ mod tests {
 
     #[test]
     fn test_swap_windows() {
-        tiling_support::test_swap_windows::<FloatWM, VerticalLayout>(VerticalLayout {});
+        tiling_support::test_swap_windows::<FloatWM, VerticalLayout>(VerticalLayout::new());
     }
 
     #[test]
     fn test_tiling_layout() {
-        tiling_support::test_get_window_info::<FloatWM, VerticalLayout>(VerticalLayout {});
+        tiling_support::test_get_window_info::<FloatWM, VerticalLayout>(VerticalLayout::new());
+    }
+
+    #[test]
+    fn test_tiled_size_hints() {
+        size_hint_support::test_tiled_size_hints::<FloatWM>();
+    }
+
+    #[test]
+    fn test_tiles_in_usable_area() {
+        strut_support::test_tiles_in_usable_area::<FloatWM>();
+    }
+
+    #[test]
+    fn test_remove_window_with_strut() {
+        strut_support::test_remove_window_with_strut::<FloatWM>();
+    }
+
+    #[test]
+    fn test_floating_window_ignores_struts() {
+        strut_support::test_floating_window_ignores_struts::<FloatWM>();
+    }
+
+    #[test]
+    fn test_focus_direction() {
+        direction_support::test_focus_direction::<FloatWM>();
+    }
+
+    #[test]
+    fn test_swap_direction() {
+        direction_support::test_swap_direction::<FloatWM>();
+    }
+
+    #[test]
+    fn test_direction_with_floats() {
+        direction_support::test_direction_with_floats::<FloatWM>();
+    }
+
+    #[test]
+    fn test_stack_order() {
+        focus_policy_support::test_stack_order::<FloatWM>();
+    }
+
+    #[test]
+    fn test_most_recently_used() {
+        focus_policy_support::test_most_recently_used::<FloatWM>();
+    }
+
+    #[test]
+    fn test_reading_order() {
+        focus_policy_support::test_reading_order::<FloatWM>();
+    }
+
+    #[test]
+    fn test_removal_focus() {
+        focus_policy_support::test_removal_focus::<FloatWM>();
     }
 
     #[test]
@@ -518,6 +944,11 @@ mod tests {
         float_support::test_set_window_geometry::<FloatWM>();
     }
 
+    #[test]
+    fn test_floating_size_hints() {
+        size_hint_support::test_floating_size_hints::<FloatWM>();
+    }
+
     #[test]
     fn test_window_layout_order() {
         float_support::test_window_layout_order::<FloatWM>();
@@ -552,4 +983,65 @@ mod tests {
     fn test_toggle_floating_focus() {
         float_and_tile_support::test_toggle_floating_focus::<FloatWM>();
     }
+
+    #[test]
+    fn test_set_border_width() {
+        border_support::test_set_border_width::<FloatWM>();
+    }
+
+    #[test]
+    fn test_floating_window_border() {
+        border_support::test_floating_window_border::<FloatWM>();
+    }
+
+    #[test]
+    fn test_border_states() {
+        border_support::test_border_states::<FloatWM>();
+    }
+
+    #[test]
+    fn test_set_gap() {
+        let layout = GapLayout::new(VerticalLayout::new(), 0);
+        gap_support::test_set_gap::<FloatWM, GapLayout<VerticalLayout>>(layout);
+    }
+
+    #[test]
+    fn test_gap_ignores_floating_windows() {
+        gap_support::test_gap_ignores_floating_windows::<FloatWM>();
+    }
+
+    #[test]
+    fn test_shrink_and_expand_master() {
+        master_support::test_shrink_and_expand_master::<FloatWM>();
+    }
+
+    #[test]
+    fn test_inc_and_dec_master() {
+        master_support::test_inc_and_dec_master::<FloatWM>();
+    }
+
+    #[test]
+    fn test_master_messages() {
+        layout_message_support::test_master_messages::<FloatWM>();
+    }
+
+    #[test]
+    fn test_no_tabs_without_tab_bar() {
+        tab_support::test_no_tabs_without_tab_bar::<FloatWM>();
+    }
+
+    #[test]
+    fn test_select_layout() {
+        layout_switch_support::test_select_layout::<FloatWM>();
+    }
+
+    #[test]
+    fn test_next_and_prev_layout() {
+        layout_switch_support::test_next_and_prev_layout::<FloatWM>();
+    }
+
+    #[test]
+    fn test_select_bsp_layout() {
+        layout_switch_support::test_select_bsp_layout::<FloatWM>();
+    }
 }
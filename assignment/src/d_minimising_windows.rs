@@ -20,17 +20,31 @@
 //!
 //! COMMENTS:
 //!
+//! The window rules are applied to every added window. There is a single workspace, so the
+//! rules sending a window to a workspace are ignored, and so are the rules making a window
+//! fullscreen.
 //!
 
 // Add imports here
-use cplwm_api::types::{Geometry, PrevOrNext, Screen, Window, WindowLayout, WindowWithInfo};
-use cplwm_api::wm::{FloatSupport, MinimiseSupport, TilingSupport, WindowManager};
-
-use wm_common::{FloatAndTileTrait, FloatTrait, LayoutManager, Manager, TilingTrait};
+use cplwm_api::types::{BorderColours, BorderWidth, BorderedWindowLayout, Direction, GapSize,
+                       Geometry, PrevOrNext, Screen, Strut, Tab, Window, WindowLayout,
+                       WindowWithInfo};
+use cplwm_api::wm::{BorderSupport, FloatSupport, GapSupport, MinimiseSupport, StrutSupport,
+                    TabSupport, TilingSupport, WindowManager};
+
+use wm_common::{BorderTrait, FloatAndTileTrait, FloatTrait, FocusPolicySupport, GapTrait,
+                LayoutManager, LayoutMessage, LayoutMessageSupport, LayoutSwitchTrait, Manager,
+                MasterTrait, MinimiseTrait, TilingTrait, WindowRuleSupport,
+                WindowSwitcherSupport};
 use wm_common::error::FloatWMError;
-use a_fullscreen_wm::FocusManager;
-use b_tiling_wm::VerticalLayout;
+use a_fullscreen_wm::{FocusManager, FocusPolicy, RemovalFocus};
 use c_floating_windows::FloatOrTileManager;
+use f_gaps::GapLayout;
+use layouts::border::{BorderLayout, BorderManager};
+use layouts::selector::LayoutSelector;
+use rules::WindowRules;
+use struts::StrutManager;
+use switcher::WindowSwitcher;
 
 
 
@@ -43,17 +57,30 @@ pub struct MinimiseWM {
     /// focus manager
     pub focus_manager: FocusManager,
     /// the layout manager
-    pub minimise_manager: MinimiseManager<FloatOrTileManager<VerticalLayout>>,
+    pub minimise_manager:
+        MinimiseManager<FloatOrTileManager<BorderLayout<GapLayout<LayoutSelector>>>>,
+    /// the manager for the border colours and the urgent windows
+    pub border_manager: BorderManager,
+    /// the manager for the areas reserved by panels and docks
+    pub strut_manager: StrutManager,
+    /// the switch session, see WindowSwitcherSupport
+    pub switcher: WindowSwitcher,
+    /// the rules applied to the added windows
+    pub window_rules: WindowRules,
 }
 
 impl WindowManager for MinimiseWM {
     type Error = FloatWMError;
 
     fn new(screen: Screen) -> MinimiseWM {
+        let layout = BorderLayout::new(GapLayout::new(LayoutSelector::standard(), 0), 0);
         MinimiseWM {
             focus_manager: FocusManager::new(),
-            minimise_manager: MinimiseManager::new(FloatOrTileManager::new(screen,
-                                                                           VerticalLayout {})),
+            minimise_manager: MinimiseManager::new(FloatOrTileManager::new(screen, layout)),
+            border_manager: BorderManager::new(),
+            strut_manager: StrutManager::new(),
+            switcher: WindowSwitcher::new(),
+            window_rules: WindowRules::new(),
         }
     }
 
@@ -65,24 +92,57 @@ impl WindowManager for MinimiseWM {
         self.focus_manager.get_focused_window()
     }
 
+    /// The window rules matching the properties of the window are applied to it.
     fn add_window(&mut self, window_with_info: WindowWithInfo) -> Result<(), Self::Error> {
-        self.focus_manager
-            .add_window(window_with_info)
-            .map_err(|error| error.to_float_error())
-            .and_then(|_| self.minimise_manager.add_window(window_with_info))
+        let window = window_with_info.window;
+        let focused_window = self.get_focused_window();
+        let outcome = self.window_rules.apply(window_with_info);
+        self.add_window_without_rules(outcome.window_with_info.clone())
+            .and_then(|_| if outcome.minimised {
+                self.toggle_minimised(window)
+            } else {
+                Ok(())
+            })
+            .and_then(|_| if outcome.focus && !outcome.minimised {
+                Ok(())
+            } else {
+                self.focus_window(focused_window)
+            })
     }
 
     fn remove_window(&mut self, window: Window) -> Result<(), Self::Error> {
+        let was_focused = self.get_focused_window() == Some(window);
+        let next_tile = self.minimise_manager.get_next_tile(window);
         match self.focus_manager.remove_window(window) {
             Err(error) => Err(error.to_float_error()),
-            Ok(_) => self.minimise_manager.remove_window(window),
+            Ok(_) => {
+                self.switcher.remove_window(window);
+                self.border_manager.remove_window(window);
+                self.remove_window_strut(window);
+                self.minimise_manager
+                    .remove_window(window)
+                    .and_then(|_| if was_focused {
+                        let master = self.minimise_manager.get_master_window();
+                        self.focus_manager
+                            .focus_after_removal(next_tile, master)
+                            .map_err(|error| error.to_float_error())
+                    } else {
+                        Ok(())
+                    })
+                    .and_then(|_| {
+                        let focused = self.focus_manager.get_focused_window();
+                        self.minimise_manager.focus_shifted(focused)
+                    })
+            }
         }
     }
 
     fn get_window_layout(&self) -> WindowLayout {
         WindowLayout {
             focused_window: self.get_focused_window(),
-            windows: self.minimise_manager.get_window_layout(),
+            windows: self.switcher.preview(self.minimise_manager.get_window_layout(), |window| {
+                self.minimise_manager.get_window_info(window).ok().map(|info| info.geometry)
+            }),
         }
     }
 
@@ -100,8 +160,22 @@ impl WindowManager for MinimiseWM {
     }
 
     fn cycle_focus(&mut self, dir: PrevOrNext) {
-        self.focus_manager.cycle_focus(dir);
-        self.minimise_manager.focus_shifted(self.focus_manager.get_focused_window()).is_ok();
+        // the layout may decide which tile comes next, e.g. to respect its containers
+        let neighbour = self.focus_manager
+            .get_focused_window()
+            .and_then(|window| self.minimise_manager.get_focus_neighbour(window, dir));
+        let windows = self.minimise_manager.get_window_layout();
+        match self.focus_manager
+            .cycle_focus_in(dir, &windows, neighbour)
+            .map_err(|error| error.to_float_error())
+            .and_then(|_| {
+                let focused = self.focus_manager.get_focused_window();
+                self.minimise_manager.focus_shifted(focused)
+            }) {
+            Ok(_) => {}
+            // rather no focused window than one the layout does not know is focused
+            Err(_) => self.focus_manager.unfocus(),
+        }
     }
 
     fn get_window_info(&self, window: Window) -> Result<WindowWithInfo, Self::Error> {
@@ -129,6 +203,84 @@ impl TilingSupport for MinimiseWM {
     fn swap_windows(&mut self, dir: PrevOrNext) {
         self.minimise_manager.swap_windows(dir, &self.focus_manager)
     }
+
+    fn swap_direction(&mut self, dir: Direction, wrap: bool) -> Result<(), Self::Error> {
+        self.minimise_manager.swap_direction(dir, wrap, &self.focus_manager)
+    }
+}
+
+impl LayoutMessageSupport for MinimiseWM {
+    fn send_layout_message(&mut self, message: LayoutMessage) -> bool {
+        self.minimise_manager.send_layout_message(message)
+    }
+}
+
+impl FocusPolicySupport for MinimiseWM {
+    fn get_focus_policy(&self) -> FocusPolicy {
+        self.focus_manager.focus_policy
+    }
+
+    fn set_focus_policy(&mut self, policy: FocusPolicy) {
+        self.focus_manager.focus_policy = policy
+    }
+
+    fn get_removal_focus(&self) -> RemovalFocus {
+        self.focus_manager.removal_focus
+    }
+
+    fn set_removal_focus(&mut self, removal_focus: RemovalFocus) {
+        self.focus_manager.removal_focus = removal_focus
+    }
+}
+
+impl TabSupport for MinimiseWM {
+    fn get_tabs(&self) -> Vec<Tab> {
+        self.minimise_manager.get_tabs()
+    }
+}
+
+impl MasterTrait for MinimiseWM {
+    fn get_master_ratio(&self) -> u32 {
+        self.minimise_manager.get_master_ratio()
+    }
+
+    fn get_master_count(&self) -> usize {
+        self.minimise_manager.get_master_count()
+    }
+
+    fn shrink_master(&mut self) {
+        self.minimise_manager.shrink_master()
+    }
+
+    fn expand_master(&mut self) {
+        self.minimise_manager.expand_master()
+    }
+
+    fn inc_master(&mut self) {
+        self.minimise_manager.inc_master()
+    }
+
+    fn dec_master(&mut self) {
+        self.minimise_manager.dec_master()
+    }
+}
+
+impl LayoutSwitchTrait for MinimiseWM {
+    fn get_layout_name(&self) -> &str {
+        self.minimise_manager.get_layout_name()
+    }
+
+    fn next_layout(&mut self) {
+        self.minimise_manager.next_layout()
+    }
+
+    fn prev_layout(&mut self) {
+        self.minimise_manager.prev_layout()
+    }
+
+    fn select_layout(&mut self, name: &str) -> bool {
+        self.minimise_manager.select_layout(name)
+    }
 }
 
 impl FloatSupport for MinimiseWM {
@@ -148,6 +300,81 @@ impl FloatSupport for MinimiseWM {
     }
 }
 
+impl BorderSupport for MinimiseWM {
+    fn get_border_width(&self) -> BorderWidth {
+        self.minimise_manager.get_border_width()
+    }
+
+    fn set_border_width(&mut self, width: BorderWidth) {
+        self.minimise_manager.set_border_width(width)
+    }
+
+    fn get_border_colours(&self) -> BorderColours {
+        self.border_manager.colours
+    }
+
+    fn set_border_colours(&mut self, colours: BorderColours) {
+        self.border_manager.colours = colours
+    }
+
+    fn is_urgent(&self, window: Window) -> bool {
+        self.border_manager.is_urgent(window)
+    }
+
+    fn set_urgent(&mut self, window: Window, urgent: bool) -> Result<(), Self::Error> {
+        if self.is_managed(window) {
+            self.border_manager.set_urgent(window, urgent);
+            Ok(())
+        } else {
+            Err(FloatWMError::UnknownWindow(window))
+        }
+    }
+
+    fn get_bordered_window_layout(&self) -> BorderedWindowLayout {
+        self.border_manager.get_bordered_window_layout(self, self.get_border_width())
+    }
+}
+
+impl GapSupport for MinimiseWM {
+    fn get_gap(&self) -> GapSize {
+        self.minimise_manager.get_gap()
+    }
+
+    fn set_gap(&mut self, gap: GapSize) {
+        self.minimise_manager.set_gap(gap)
+    }
+}
+
+/// Only the tiles are kept out of the reserved areas, floating windows may cover them.
+impl StrutSupport for MinimiseWM {
+    fn get_usable_area(&self) -> Geometry {
+        self.minimise_manager.get_usable_area()
+    }
+
+    fn get_static_strut(&self) -> Strut {
+        self.strut_manager.static_strut
+    }
+
+    fn set_static_strut(&mut self, strut: Strut) {
+        self.strut_manager.static_strut = strut;
+        self.minimise_manager.set_strut(self.strut_manager.get_strut())
+    }
+
+    fn get_window_strut(&self, window: Window) -> Option<Strut> {
+        self.strut_manager.get_window_strut(window)
+    }
+
+    fn set_window_strut(&mut self, window: Window, strut: Strut) {
+        self.strut_manager.set_window_strut(window, strut);
+        self.minimise_manager.set_strut(self.strut_manager.get_strut())
+    }
+
+    fn remove_window_strut(&mut self, window: Window) {
+        self.strut_manager.remove_window_strut(window);
+        self.minimise_manager.set_strut(self.strut_manager.get_strut())
+    }
+}
+
 impl MinimiseSupport for MinimiseWM {
     fn get_minimised_windows(&self) -> Vec<Window> {
         self.minimise_manager.get_minimised_windows()
@@ -158,6 +385,50 @@ impl MinimiseSupport for MinimiseWM {
     }
 }
 
+impl WindowRuleSupport for MinimiseWM {
+    fn get_window_rules(&self) -> &WindowRules {
+        &self.window_rules
+    }
+
+    fn set_window_rules(&mut self, rules: WindowRules) {
+        self.window_rules = rules;
+    }
+
+    fn add_window_without_rules(&mut self,
+                                window_with_info: WindowWithInfo)
+                                -> Result<(), Self::Error> {
+        self.focus_manager
+            .add_window(window_with_info.clone())
+            .map_err(|error| error.to_float_error())
+            .and_then(|_| self.minimise_manager.add_window(window_with_info))
+    }
+}
+
+impl WindowSwitcherSupport for MinimiseWM {
+    fn begin_switch(&mut self) {
+        self.switcher.begin(self.focus_manager.get_recently_used())
+    }
+
+    fn step_switch(&mut self, dir: PrevOrNext) {
+        self.switcher.step(dir)
+    }
+
+    fn get_switch_candidate(&self) -> Option<Window> {
+        self.switcher.get_candidate()
+    }
+
+    fn commit_switch(&mut self) -> Result<(), Self::Error> {
+        match self.switcher.end() {
+            None => Ok(()),
+            Some(window) => self.focus_window(Some(window)),
+        }
+    }
+
+    fn cancel_switch(&mut self) {
+        self.switcher.end();
+    }
+}
+
 /// Manager to manage the minimised windows and wraps around a layout manager LayoutManager
 /// the minimise_assistant_manager is a helper to manage the minimised_windows
 #[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
@@ -218,6 +489,14 @@ impl<LM : LayoutManager<Error=FloatWMError> + FloatAndTileTrait> LayoutManager f
     fn resize_screen(&mut self, screen: Screen){
         self.layout_manager.resize_screen(screen);
     }
+
+    fn get_strut(&self) -> Strut{
+        self.layout_manager.get_strut()
+    }
+
+    fn set_strut(&mut self, strut: Strut){
+        self.layout_manager.set_strut(strut);
+    }
 }
 
 impl<LM : LayoutManager<Error=FloatWMError> + FloatAndTileTrait> TilingTrait for MinimiseManager<LM> {
@@ -236,6 +515,30 @@ impl<LM : LayoutManager<Error=FloatWMError> + FloatAndTileTrait> TilingTrait for
     fn swap_windows(&mut self, dir: PrevOrNext, focus_manager: &FocusManager){
         self.layout_manager.swap_windows(dir, focus_manager)
     }
+/// send a message to the layout
+    fn send_layout_message(&mut self, message: LayoutMessage) -> bool {
+        self.layout_manager.send_layout_message(message)
+    }
+/// get the tabs, minimised windows have no tab
+    fn get_tabs(&self) -> Vec<Tab> {
+        self.layout_manager.get_tabs()
+    }
+/// get the window the layout wants to focus next
+    fn get_focus_neighbour(&self, window: Window, dir: PrevOrNext) -> Option<Window> {
+        self.layout_manager.get_focus_neighbour(window, dir)
+    }
+
+    fn get_next_tile(&self, window: Window) -> Option<Window> {
+        self.layout_manager.get_next_tile(window)
+    }
+
+    fn swap_places(&mut self, window: Window, other: Window) -> Result<(), FloatWMError> {
+        self.layout_manager.swap_places(window, other)
+    }
+
+    fn can_swap(&self, window: Window, other: Window) -> bool {
+        self.layout_manager.can_swap(window, other)
+    }
 }
 
 impl<LM : LayoutManager<Error=FloatWMError> + FloatAndTileTrait> FloatTrait for MinimiseManager<LM> {
@@ -261,29 +564,11 @@ impl<LM : LayoutManager<Error=FloatWMError> + FloatAndTileTrait> FloatAndTileTra
 }
 
 
-impl<LM: LayoutManager<Error = FloatWMError> + FloatAndTileTrait> MinimiseManager<LM> {
-    fn new(layout_manager: LM) -> MinimiseManager<LM> {
-        MinimiseManager {
-            layout_manager: layout_manager,
-            minimise_assistant_manager: MinimiseAssistantManager::new(),
-        }
-    }
-
+impl<LM: LayoutManager<Error = FloatWMError> + FloatAndTileTrait> MinimiseTrait for MinimiseManager<LM> {
     fn get_minimised_windows(&self) -> Vec<Window> {
         self.minimise_assistant_manager.get_windows()
     }
 
-    fn maximise_if_minimised(&mut self,
-                             window: Window,
-                             focus_manager: &mut FocusManager)
-                             -> Result<(), FloatWMError> {
-        if self.minimise_assistant_manager.is_managed(window) {
-            self.toggle_minimised(window, focus_manager)
-        } else {
-            Ok(())
-        }
-    }
-
     fn toggle_minimised(&mut self,
                         window: Window,
                         focus_manager: &mut FocusManager)
@@ -328,6 +613,88 @@ impl<LM: LayoutManager<Error = FloatWMError> + FloatAndTileTrait> MinimiseManage
     }
 }
 
+impl<LM> MasterTrait for MinimiseManager<LM>
+    where LM: LayoutManager<Error = FloatWMError> + FloatAndTileTrait + MasterTrait
+{
+    fn get_master_ratio(&self) -> u32 {
+        self.layout_manager.get_master_ratio()
+    }
+
+    fn get_master_count(&self) -> usize {
+        self.layout_manager.get_master_count()
+    }
+
+    fn shrink_master(&mut self) {
+        self.layout_manager.shrink_master()
+    }
+
+    fn expand_master(&mut self) {
+        self.layout_manager.expand_master()
+    }
+
+    fn inc_master(&mut self) {
+        self.layout_manager.inc_master()
+    }
+
+    fn dec_master(&mut self) {
+        self.layout_manager.dec_master()
+    }
+}
+
+impl<LM> LayoutSwitchTrait for MinimiseManager<LM>
+    where LM: LayoutManager<Error = FloatWMError> + FloatAndTileTrait + LayoutSwitchTrait
+{
+    fn get_layout_name(&self) -> &str {
+        self.layout_manager.get_layout_name()
+    }
+
+    fn next_layout(&mut self) {
+        self.layout_manager.next_layout()
+    }
+
+    fn prev_layout(&mut self) {
+        self.layout_manager.prev_layout()
+    }
+
+    fn select_layout(&mut self, name: &str) -> bool {
+        self.layout_manager.select_layout(name)
+    }
+}
+
+impl<LM> BorderTrait for MinimiseManager<LM>
+    where LM: LayoutManager<Error = FloatWMError> + FloatAndTileTrait + BorderTrait
+{
+    fn get_border_width(&self) -> BorderWidth {
+        self.layout_manager.get_border_width()
+    }
+
+    fn set_border_width(&mut self, width: BorderWidth) {
+        self.layout_manager.set_border_width(width)
+    }
+}
+
+impl<LM> GapTrait for MinimiseManager<LM>
+    where LM: LayoutManager<Error = FloatWMError> + FloatAndTileTrait + GapTrait
+{
+    fn get_gap(&self) -> GapSize {
+        self.layout_manager.get_gap()
+    }
+
+    fn set_gap(&mut self, gap: GapSize) {
+        self.layout_manager.set_gap(gap)
+    }
+}
+
+impl<LM: LayoutManager<Error = FloatWMError> + FloatAndTileTrait> MinimiseManager<LM> {
+    /// creates a MinimiseManager around the given layout manager
+    pub fn new(layout_manager: LM) -> MinimiseManager<LM> {
+        MinimiseManager {
+            layout_manager,
+            minimise_assistant_manager: MinimiseAssistantManager::new(),
+        }
+    }
+}
+
 
 
 
@@ -382,7 +749,7 @@ impl MinimiseAssistantManager {
             .and_then(|index| {
                 self.minis
                     .get(index)
-                    .map(|w| *w)
+                    .cloned()
                     .ok_or(FloatWMError::UnknownWindow(window))
             })
     }
@@ -392,12 +759,40 @@ impl MinimiseAssistantManager {
 #[cfg(test)]
 mod tests {
     use wm_common::tests::window_manager;
+    use wm_common::tests::layout_message_support;
+    use wm_common::tests::tab_support;
+    use wm_common::tests::layout_switch_support;
+    use wm_common::tests::master_support;
+    use wm_common::tests::gap_support;
+    use wm_common::tests::border_support;
     use wm_common::tests::tiling_support;
+    use wm_common::tests::size_hint_support;
+    use wm_common::tests::direction_support;
+    use wm_common::tests::focus_policy_support;
+    use wm_common::tests::strut_support;
+    use wm_common::tests::window_switcher_support;
     use wm_common::tests::float_support;
     use wm_common::tests::float_and_tile_support;
     use wm_common::tests::minimise_support;
     use super::MinimiseWM;
     use b_tiling_wm::VerticalLayout;
+    use f_gaps::GapLayout;
+    use cplwm_api::types::*;
+    use cplwm_api::wm::*;
+    use rules::{PropertyPattern, RuleAction, WindowMatcher, WindowRules};
+    use wm_common::WindowRuleSupport;
+
+    static SCREEN: Screen = Screen {
+        width: 800,
+        height: 600,
+    };
+
+    static SOME_GEOM: Geometry = Geometry {
+        x: 10,
+        y: 10,
+        width: 100,
+        height: 100,
+    };
 
     #[test]
     fn test_empty_tiling_wm() {
@@ -447,12 +842,77 @@ mod tests {
 
     #[test]
     fn test_swap_windows() {
-        tiling_support::test_swap_windows::<MinimiseWM, VerticalLayout>(VerticalLayout {});
+        tiling_support::test_swap_windows::<MinimiseWM, VerticalLayout>(VerticalLayout::new());
     }
 
     #[test]
     fn test_tiling_layout() {
-        tiling_support::test_get_window_info::<MinimiseWM, VerticalLayout>(VerticalLayout {});
+        tiling_support::test_get_window_info::<MinimiseWM, VerticalLayout>(VerticalLayout::new());
+    }
+
+    #[test]
+    fn test_tiled_size_hints() {
+        size_hint_support::test_tiled_size_hints::<MinimiseWM>();
+    }
+
+    #[test]
+    fn test_tiles_in_usable_area() {
+        strut_support::test_tiles_in_usable_area::<MinimiseWM>();
+    }
+
+    #[test]
+    fn test_remove_window_with_strut() {
+        strut_support::test_remove_window_with_strut::<MinimiseWM>();
+    }
+
+    #[test]
+    fn test_floating_window_ignores_struts() {
+        strut_support::test_floating_window_ignores_struts::<MinimiseWM>();
+    }
+
+    #[test]
+    fn test_focus_direction() {
+        direction_support::test_focus_direction::<MinimiseWM>();
+    }
+
+    #[test]
+    fn test_swap_direction() {
+        direction_support::test_swap_direction::<MinimiseWM>();
+    }
+
+    #[test]
+    fn test_direction_with_floats() {
+        direction_support::test_direction_with_floats::<MinimiseWM>();
+    }
+
+    #[test]
+    fn test_stack_order() {
+        focus_policy_support::test_stack_order::<MinimiseWM>();
+    }
+
+    #[test]
+    fn test_most_recently_used() {
+        focus_policy_support::test_most_recently_used::<MinimiseWM>();
+    }
+
+    #[test]
+    fn test_reading_order() {
+        focus_policy_support::test_reading_order::<MinimiseWM>();
+    }
+
+    #[test]
+    fn test_removal_focus() {
+        focus_policy_support::test_removal_focus::<MinimiseWM>();
+    }
+
+    #[test]
+    fn test_switch_commit() {
+        window_switcher_support::test_switch_commit::<MinimiseWM>();
+    }
+
+    #[test]
+    fn test_switch_cancel() {
+        window_switcher_support::test_switch_cancel::<MinimiseWM>();
     }
 
     #[test]
@@ -470,6 +930,11 @@ mod tests {
         float_support::test_set_window_geometry::<MinimiseWM>();
     }
 
+    #[test]
+    fn test_floating_size_hints() {
+        size_hint_support::test_floating_size_hints::<MinimiseWM>();
+    }
+
     #[test]
     fn test_window_layout_order() {
         float_support::test_window_layout_order::<MinimiseWM>();
@@ -535,5 +1000,100 @@ mod tests {
         minimise_support::test_minimise_state_after_cycle_focus::<MinimiseWM>();
     }
 
+    #[test]
+    fn test_set_border_width() {
+        border_support::test_set_border_width::<MinimiseWM>();
+    }
+
+    #[test]
+    fn test_floating_window_border() {
+        border_support::test_floating_window_border::<MinimiseWM>();
+    }
+
+    #[test]
+    fn test_border_states() {
+        border_support::test_border_states::<MinimiseWM>();
+    }
+
+    #[test]
+    fn test_set_gap() {
+        let layout = GapLayout::new(VerticalLayout::new(), 0);
+        gap_support::test_set_gap::<MinimiseWM, GapLayout<VerticalLayout>>(layout);
+    }
+
+    #[test]
+    fn test_gap_ignores_floating_windows() {
+        gap_support::test_gap_ignores_floating_windows::<MinimiseWM>();
+    }
+
+    #[test]
+    fn test_shrink_and_expand_master() {
+        master_support::test_shrink_and_expand_master::<MinimiseWM>();
+    }
+
+    #[test]
+    fn test_inc_and_dec_master() {
+        master_support::test_inc_and_dec_master::<MinimiseWM>();
+    }
+
+    #[test]
+    fn test_master_messages() {
+        layout_message_support::test_master_messages::<MinimiseWM>();
+    }
+
+    #[test]
+    fn test_no_tabs_without_tab_bar() {
+        tab_support::test_no_tabs_without_tab_bar::<MinimiseWM>();
+    }
+
+    #[test]
+    fn test_select_layout() {
+        layout_switch_support::test_select_layout::<MinimiseWM>();
+    }
 
+    #[test]
+    fn test_next_and_prev_layout() {
+        layout_switch_support::test_next_and_prev_layout::<MinimiseWM>();
+    }
+
+    #[test]
+    fn test_select_bsp_layout() {
+        layout_switch_support::test_select_bsp_layout::<MinimiseWM>();
+    }
+
+    #[test]
+    fn test_window_rules() {
+        let mut wm = MinimiseWM::new(SCREEN);
+        wm.set_window_rules(WindowRules::new()
+            .with_rule(WindowMatcher::any()
+                           .with_class(PropertyPattern::Equals("Pavucontrol".to_string())),
+                       vec![RuleAction::Float, RuleAction::NoFocus])
+            .with_rule(WindowMatcher::any()
+                           .with_title(PropertyPattern::Contains("Downloads".to_string())),
+                       vec![RuleAction::Minimise])
+            .with_rule(WindowMatcher::any(), vec![RuleAction::Workspace(3)]));
+
+        // the workspace rule is ignored, there is a single workspace
+        assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+        assert_eq!(Some(1), wm.get_focused_window());
+
+        assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)
+                .with_properties(WindowProperties::new().with_class("Pavucontrol", "pavucontrol")))
+            .is_ok());
+        assert!(wm.is_floating(2));
+        assert_eq!(Some(1), wm.get_focused_window());
+
+        assert!(wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)
+                .with_properties(WindowProperties::new().with_title("Downloads")))
+            .is_ok());
+        assert_eq!(vec![3], wm.get_minimised_windows());
+        assert_eq!(Some(1), wm.get_focused_window());
+
+        // the rules are not applied to a window added without them
+        assert!(wm.add_window_without_rules(WindowWithInfo::new_tiled(4, SOME_GEOM)
+                .with_properties(WindowProperties::new().with_title("Downloads")))
+            .is_ok());
+        assert!(!wm.is_minimised(4));
+        assert_eq!(Some(4), wm.get_focused_window());
+    }
 }
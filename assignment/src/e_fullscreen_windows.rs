@@ -16,16 +16,1027 @@
 //!
 //! # Status
 //!
-//! COMPLETED: NO
+//! COMPLETED: YES
 //!
 //! COMMENTS:
-//! It would however be fairly easy. FullscreenWM would then become:
-//! FocusManager + MinimiseManager<FloatOrTileManager<LayoutManager>> + FullscreenManager.
-//! The FullscreenManager would keep track of the fullscreen window.
+//! FullscreenWM = FocusManager + FullscreenManager<MinimiseManager<FloatOrTileManager<Layout>>>.
+//! The FullscreenManager keeps track of the fullscreen window, the window itself stays in the
+//! wrapped manager so it keeps its tile or floating geometry for when it is restored.
+//!
+//! The choices made for the interaction with the other traits:
+//!
+//! * The fullscreen window must always be focused, so every action that focuses another window
+//!   (adding a non-fullscreen window, focus_window, cycle_focus, swap_with_master with another
+//!   window) ends the fullscreen state.
+//! * Removing another window keeps the fullscreen window fullscreen.
+//! * toggle_floating on the fullscreen window ends the fullscreen state, so the change is
+//!   visible.
+//! * Minimising the fullscreen window ends the fullscreen state, unminimising it makes it
+//!   fullscreen again.
+//! * Calling toggle_fullscreen on a minimised window unminimises it first.
+//! * The window rules are applied to every added window. There is a single workspace, so the
+//!   rules sending a window to a workspace are ignored. A window the rules keep from taking the
+//!   focus leaves the fullscreen window fullscreen.
 //!
 
 // Add imports here
+use cplwm_api::types::{Direction, Geometry, PrevOrNext, Screen, Strut, Tab, Window, WindowLayout,
+                       WindowWithInfo};
+use cplwm_api::wm::{FloatSupport, FullscreenSupport, MinimiseSupport, StrutSupport, TabSupport,
+                    TilingSupport, WindowManager};
+
+use wm_common::{FloatAndTileTrait, FloatTrait, FocusPolicySupport, FullscreenTrait, LayoutManager,
+                LayoutMessage, LayoutMessageSupport, Manager, MasterTrait, MinimiseTrait,
+                TilingTrait, WindowRuleSupport, WindowSwitcherSupport};
+use wm_common::error::FloatWMError;
+use a_fullscreen_wm::{FocusManager, FocusPolicy, RemovalFocus};
+use b_tiling_wm::VerticalLayout;
+use c_floating_windows::FloatOrTileManager;
+use d_minimising_windows::MinimiseManager;
+use rules::WindowRules;
+use struts::StrutManager;
+use switcher::WindowSwitcher;
+
+
+/// The public type.
+pub type WMName = FullscreenWM;
+
+/// struct for FullscreenWM = {Focus + Fullscreen<Minimise<TileOrFloat<Layout>>>}
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct FullscreenWM {
+    /// focus manager
+    pub focus_manager: FocusManager,
+    /// the layout manager
+    pub fullscreen_manager: FullscreenManager<MinimiseManager<FloatOrTileManager<VerticalLayout>>>,
+    /// the manager for the areas reserved by panels and docks
+    pub strut_manager: StrutManager,
+    /// the switch session, see WindowSwitcherSupport
+    pub switcher: WindowSwitcher,
+    /// the rules applied to the added windows
+    pub window_rules: WindowRules,
+}
+
+impl WindowManager for FullscreenWM {
+    type Error = FloatWMError;
+
+    fn new(screen: Screen) -> FullscreenWM {
+        let float_or_tile_manager = FloatOrTileManager::new(screen, VerticalLayout::new());
+        FullscreenWM {
+            focus_manager: FocusManager::new(),
+            fullscreen_manager: FullscreenManager::new(MinimiseManager::new(float_or_tile_manager)),
+            strut_manager: StrutManager::new(),
+            switcher: WindowSwitcher::new(),
+            window_rules: WindowRules::new(),
+        }
+    }
+
+    fn get_windows(&self) -> Vec<Window> {
+        self.focus_manager.get_windows()
+    }
+
+    fn get_focused_window(&self) -> Option<Window> {
+        self.focus_manager.get_focused_window()
+    }
+
+    /// The window rules matching the properties of the window are applied to it.
+    fn add_window(&mut self, window_with_info: WindowWithInfo) -> Result<(), Self::Error> {
+        let window = window_with_info.window;
+        let focused_window = self.get_focused_window();
+        let fullscreen_window = self.get_fullscreen_window();
+        let outcome = self.window_rules.apply(window_with_info);
+        self.add_window_without_rules(outcome.window_with_info.clone())
+            .and_then(|_| if outcome.minimised {
+                self.toggle_minimised(window)
+            } else {
+                Ok(())
+            })
+            .and_then(|_| if outcome.focus && !outcome.minimised {
+                Ok(())
+            } else {
+                self.focus_window(focused_window).and_then(|_| match fullscreen_window {
+                    Some(w) if self.get_fullscreen_window() != Some(w) => {
+                        self.toggle_fullscreen(w)
+                    }
+                    _ => Ok(()),
+                })
+            })
+    }
+
+    fn remove_window(&mut self, window: Window) -> Result<(), Self::Error> {
+        let was_focused = self.get_focused_window() == Some(window);
+        let next_tile = self.fullscreen_manager.get_next_tile(window);
+        match self.focus_manager.remove_window(window) {
+            Err(error) => Err(error.to_float_error()),
+            Ok(_) => {
+                self.switcher.remove_window(window);
+                self.remove_window_strut(window);
+                self.fullscreen_manager
+                    .remove_window(window)
+                    .and_then(|_| if was_focused {
+                        let master = self.fullscreen_manager.get_master_window();
+                        self.focus_manager
+                            .focus_after_removal(next_tile, master)
+                            .map_err(|error| error.to_float_error())
+                    } else {
+                        Ok(())
+                    })
+                    .and_then(|_| {
+                        let focused = self.focus_manager.get_focused_window();
+                        self.fullscreen_manager.focus_shifted(focused)
+                    })
+            }
+        }
+    }
+
+    fn get_window_layout(&self) -> WindowLayout {
+        WindowLayout {
+            focused_window: self.get_focused_window(),
+            windows: self.switcher.preview(self.fullscreen_manager.get_window_layout(), |window| {
+                self.fullscreen_manager.get_window_info(window).ok().map(|info| info.geometry)
+            }),
+        }
+    }
+
+    fn focus_window(&mut self, window: Option<Window>) -> Result<(), Self::Error> {
+        match window {
+                None => Ok(()),
+                Some(w) => self.fullscreen_manager.maximise_if_minimised(w, &mut self.focus_manager),
+            }
+            .and_then(|_| {
+                self.focus_manager
+                    .focus_window(window)
+                    .map_err(|error| error.to_float_error())
+                    .and_then(|_| self.fullscreen_manager.focus_shifted(window))
+            })
+    }
+
+    fn cycle_focus(&mut self, dir: PrevOrNext) {
+        // the layout may decide which tile comes next, e.g. to respect its containers
+        let neighbour = self.focus_manager
+            .get_focused_window()
+            .and_then(|window| self.fullscreen_manager.get_focus_neighbour(window, dir));
+        let windows = self.fullscreen_manager.get_window_layout();
+        match self.focus_manager
+            .cycle_focus_in(dir, &windows, neighbour)
+            .map_err(|error| error.to_float_error())
+            .and_then(|_| {
+                let focused = self.focus_manager.get_focused_window();
+                self.fullscreen_manager.focus_shifted(focused)
+            }) {
+            Ok(_) => {}
+            // rather no focused window than one the layout does not know is focused
+            Err(_) => self.focus_manager.unfocus(),
+        }
+    }
+
+    fn get_window_info(&self, window: Window) -> Result<WindowWithInfo, Self::Error> {
+        self.fullscreen_manager.get_window_info(window)
+    }
+
+    fn get_screen(&self) -> Screen {
+        self.fullscreen_manager.get_screen()
+    }
+
+    fn resize_screen(&mut self, screen: Screen) {
+        self.fullscreen_manager.resize_screen(screen);
+    }
+}
+
+impl TilingSupport for FullscreenWM {
+    fn get_master_window(&self) -> Option<Window> {
+        self.fullscreen_manager.get_master_window()
+    }
+
+    fn swap_with_master(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.fullscreen_manager.swap_with_master(window, &mut self.focus_manager)
+    }
+
+    fn swap_windows(&mut self, dir: PrevOrNext) {
+        self.fullscreen_manager.swap_windows(dir, &self.focus_manager)
+    }
+
+    fn swap_direction(&mut self, dir: Direction, wrap: bool) -> Result<(), Self::Error> {
+        self.fullscreen_manager.swap_direction(dir, wrap, &self.focus_manager)
+    }
+}
+
+impl LayoutMessageSupport for FullscreenWM {
+    fn send_layout_message(&mut self, message: LayoutMessage) -> bool {
+        self.fullscreen_manager.send_layout_message(message)
+    }
+}
+
+impl FocusPolicySupport for FullscreenWM {
+    fn get_focus_policy(&self) -> FocusPolicy {
+        self.focus_manager.focus_policy
+    }
+
+    fn set_focus_policy(&mut self, policy: FocusPolicy) {
+        self.focus_manager.focus_policy = policy
+    }
+
+    fn get_removal_focus(&self) -> RemovalFocus {
+        self.focus_manager.removal_focus
+    }
+
+    fn set_removal_focus(&mut self, removal_focus: RemovalFocus) {
+        self.focus_manager.removal_focus = removal_focus
+    }
+}
+
+impl TabSupport for FullscreenWM {
+    fn get_tabs(&self) -> Vec<Tab> {
+        self.fullscreen_manager.get_tabs()
+    }
+}
+
+impl MasterTrait for FullscreenWM {
+    fn get_master_ratio(&self) -> u32 {
+        self.fullscreen_manager.get_master_ratio()
+    }
+
+    fn get_master_count(&self) -> usize {
+        self.fullscreen_manager.get_master_count()
+    }
+
+    fn shrink_master(&mut self) {
+        self.fullscreen_manager.shrink_master()
+    }
+
+    fn expand_master(&mut self) {
+        self.fullscreen_manager.expand_master()
+    }
+
+    fn inc_master(&mut self) {
+        self.fullscreen_manager.inc_master()
+    }
+
+    fn dec_master(&mut self) {
+        self.fullscreen_manager.dec_master()
+    }
+}
+
+impl FloatSupport for FullscreenWM {
+    fn get_floating_windows(&self) -> Vec<Window> {
+        self.fullscreen_manager.get_floating_windows()
+    }
+
+    fn toggle_floating(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.fullscreen_manager.toggle_floating(window, &mut self.focus_manager)
+    }
+
+    fn set_window_geometry(&mut self,
+                           window: Window,
+                           new_geometry: Geometry)
+                           -> Result<(), Self::Error> {
+        self.fullscreen_manager.set_window_geometry(window, new_geometry)
+    }
+}
+
+impl MinimiseSupport for FullscreenWM {
+    fn get_minimised_windows(&self) -> Vec<Window> {
+        self.fullscreen_manager.get_minimised_windows()
+    }
+
+    fn toggle_minimised(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.fullscreen_manager.toggle_minimised(window, &mut self.focus_manager)
+    }
+}
+
+impl WindowSwitcherSupport for FullscreenWM {
+    fn begin_switch(&mut self) {
+        self.switcher.begin(self.focus_manager.get_recently_used())
+    }
+
+    fn step_switch(&mut self, dir: PrevOrNext) {
+        self.switcher.step(dir)
+    }
+
+    fn get_switch_candidate(&self) -> Option<Window> {
+        self.switcher.get_candidate()
+    }
+
+    fn commit_switch(&mut self) -> Result<(), Self::Error> {
+        match self.switcher.end() {
+            None => Ok(()),
+            Some(window) => self.focus_window(Some(window)),
+        }
+    }
+
+    fn cancel_switch(&mut self) {
+        self.switcher.end();
+    }
+}
+
+/// The tiles and the fullscreen window are kept out of the reserved areas, floating windows may
+/// cover them.
+impl StrutSupport for FullscreenWM {
+    fn get_usable_area(&self) -> Geometry {
+        self.fullscreen_manager.get_usable_area()
+    }
+
+    fn get_static_strut(&self) -> Strut {
+        self.strut_manager.static_strut
+    }
+
+    fn set_static_strut(&mut self, strut: Strut) {
+        self.strut_manager.static_strut = strut;
+        self.fullscreen_manager.set_strut(self.strut_manager.get_strut())
+    }
+
+    fn get_window_strut(&self, window: Window) -> Option<Strut> {
+        self.strut_manager.get_window_strut(window)
+    }
+
+    fn set_window_strut(&mut self, window: Window, strut: Strut) {
+        self.strut_manager.set_window_strut(window, strut);
+        self.fullscreen_manager.set_strut(self.strut_manager.get_strut())
+    }
+
+    fn remove_window_strut(&mut self, window: Window) {
+        self.strut_manager.remove_window_strut(window);
+        self.fullscreen_manager.set_strut(self.strut_manager.get_strut())
+    }
+}
+
+impl FullscreenSupport for FullscreenWM {
+    fn get_fullscreen_window(&self) -> Option<Window> {
+        self.fullscreen_manager.get_fullscreen_window()
+    }
+
+    fn toggle_fullscreen(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.fullscreen_manager.toggle_fullscreen(window, &mut self.focus_manager)
+    }
+}
+
+impl WindowRuleSupport for FullscreenWM {
+    fn get_window_rules(&self) -> &WindowRules {
+        &self.window_rules
+    }
+
+    fn set_window_rules(&mut self, rules: WindowRules) {
+        self.window_rules = rules;
+    }
+
+    fn add_window_without_rules(&mut self,
+                                window_with_info: WindowWithInfo)
+                                -> Result<(), Self::Error> {
+        self.focus_manager
+            .add_window(window_with_info.clone())
+            .map_err(|error| error.to_float_error())
+            .and_then(|_| self.fullscreen_manager.add_window(window_with_info))
+    }
+}
+
+/// Manager which keeps track of the fullscreen window and wraps around a layout manager which
+/// supports minimising. The fullscreen window stays managed by the wrapped manager, so its
+/// tile or floating state and geometry are restored when it stops being fullscreen.
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct FullscreenManager<LM: MinimiseTrait<Error = FloatWMError>> {
+    /// The wrapped layout manager
+    pub layout_manager: LM,
+    /// The current fullscreen window, if any
+    pub fullscreen_window: Option<Window>,
+    /// The window that was fullscreen when it got minimised, it becomes fullscreen again when it
+    /// is unminimised.
+    pub minimised_fullscreen_window: Option<Window>,
+}
+
+impl<LM: MinimiseTrait<Error = FloatWMError>> Manager for FullscreenManager<LM> {
+    type Error = FloatWMError;
+
+    fn get_windows(&self) -> Vec<Window> {
+        self.layout_manager.get_windows()
+    }
+
+    /// The added window will be focused, so the current fullscreen window stops being fullscreen,
+    /// unless the added window wants to be fullscreen itself.
+    fn add_window(&mut self, window_with_info: WindowWithInfo) -> Result<(), Self::Error> {
+        let (window, fullscreen) = (window_with_info.window, window_with_info.fullscreen);
+        self.layout_manager
+            .add_window(WindowWithInfo { fullscreen: false, ..window_with_info })
+            .map(|_| {
+                self.fullscreen_window = if fullscreen {
+                    Some(window)
+                } else {
+                    None
+                };
+            })
+    }
+
+    fn remove_window(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.layout_manager.remove_window(window).map(|_| {
+            if self.fullscreen_window == Some(window) {
+                self.fullscreen_window = None;
+            }
+            if self.minimised_fullscreen_window == Some(window) {
+                self.minimised_fullscreen_window = None;
+            }
+        })
+    }
+}
+
+impl<LM: MinimiseTrait<Error = FloatWMError>> LayoutManager for FullscreenManager<LM> {
+    /// The fullscreen window is put on top of all the other windows with the geometry of the
+    /// usable area of the screen.
+    fn get_window_layout(&self) -> Vec<(Window, Geometry)> {
+        let windows = self.layout_manager.get_window_layout();
+        match self.fullscreen_window {
+            None => windows,
+            Some(fullscreen) => {
+                let mut windows: Vec<(Window, Geometry)> =
+                    windows.into_iter().filter(|&(w, _)| w != fullscreen).collect();
+                windows.push((fullscreen, self.get_usable_area()));
+                windows
+            }
+        }
+    }
+
+    fn focus_shifted(&mut self, window: Option<Window>) -> Result<(), Self::Error> {
+        self.layout_manager.focus_shifted(window).map(|_| {
+            if window.is_some() && window == self.minimised_fullscreen_window {
+                // the wrapped manager unminimised the window by focusing it
+                self.fullscreen_window = window;
+                self.minimised_fullscreen_window = None;
+            } else {
+                self.end_fullscreen_unless_focused(window);
+            }
+        })
+    }
+
+    fn get_window_info(&self, window: Window) -> Result<WindowWithInfo, Self::Error> {
+        self.layout_manager.get_window_info(window).map(|info| {
+            if self.fullscreen_window == Some(window) {
+                WindowWithInfo {
+                    geometry: self.get_usable_area(),
+                    fullscreen: true,
+                    ..info
+                }
+            } else if self.minimised_fullscreen_window == Some(window) {
+                WindowWithInfo { fullscreen: true, ..info }
+            } else {
+                info
+            }
+        })
+    }
+
+    fn get_screen(&self) -> Screen {
+        self.layout_manager.get_screen()
+    }
+
+    fn resize_screen(&mut self, screen: Screen) {
+        self.layout_manager.resize_screen(screen);
+    }
+
+    fn get_strut(&self) -> Strut {
+        self.layout_manager.get_strut()
+    }
+
+    fn set_strut(&mut self, strut: Strut) {
+        self.layout_manager.set_strut(strut)
+    }
+}
+
+impl<LM: MinimiseTrait<Error = FloatWMError>> TilingTrait for FullscreenManager<LM> {
+    /// get the master
+    fn get_master_window(&self) -> Option<Window> {
+        self.layout_manager.get_master_window()
+    }
+
+    /// swap with the master, the master gets focused so the fullscreen window stays fullscreen
+    /// only if it is the given window.
+    fn swap_with_master(&mut self,
+                        window: Window,
+                        focus_manager: &mut FocusManager)
+                        -> Result<(), Self::Error> {
+        self.maximise_if_minimised(window, focus_manager)
+            .and_then(|_| self.layout_manager.swap_with_master(window, focus_manager))
+            .map(|_| {
+                self.end_fullscreen_unless_focused(focus_manager.get_focused_window());
+            })
+    }
+
+    /// swap windows, does not change the focus so the fullscreen window stays fullscreen
+    fn swap_windows(&mut self, dir: PrevOrNext, focus_manager: &FocusManager) {
+        self.layout_manager.swap_windows(dir, focus_manager)
+    }
+
+    fn send_layout_message(&mut self, message: LayoutMessage) -> bool {
+        self.layout_manager.send_layout_message(message)
+    }
+
+    /// the fullscreen window covers the tab bar, so there are no tabs while there is one
+    fn get_tabs(&self) -> Vec<Tab> {
+        match self.fullscreen_window {
+            None => self.layout_manager.get_tabs(),
+            Some(_) => Vec::new(),
+        }
+    }
+
+    fn get_focus_neighbour(&self, window: Window, dir: PrevOrNext) -> Option<Window> {
+        self.layout_manager.get_focus_neighbour(window, dir)
+    }
+
+    fn get_next_tile(&self, window: Window) -> Option<Window> {
+        self.layout_manager.get_next_tile(window)
+    }
+
+    fn swap_places(&mut self, window: Window, other: Window) -> Result<(), FloatWMError> {
+        self.layout_manager.swap_places(window, other)
+    }
+
+    fn can_swap(&self, window: Window, other: Window) -> bool {
+        self.layout_manager.can_swap(window, other)
+    }
+}
+
+impl<LM: MinimiseTrait<Error = FloatWMError>> FloatTrait for FullscreenManager<LM> {
+    /// change the geometry of the floater, a fullscreen floater will get this geometry when it is
+    /// restored.
+    fn set_window_geometry(&mut self,
+                           window: Window,
+                           new_geometry: Geometry)
+                           -> Result<(), Self::Error> {
+        self.layout_manager.set_window_geometry(window, new_geometry)
+    }
+}
+
+impl<LM: MinimiseTrait<Error = FloatWMError>> FloatAndTileTrait for FullscreenManager<LM> {
+    fn get_floating_windows(&self) -> Vec<Window> {
+        self.layout_manager.get_floating_windows()
+    }
+
+    fn get_tiled_windows(&self) -> Vec<Window> {
+        self.layout_manager.get_tiled_windows()
+    }
+
+    /// toggle floating on window, this always ends the fullscreen state of the window
+    fn toggle_floating(&mut self,
+                       window: Window,
+                       focus_manager: &mut FocusManager)
+                       -> Result<(), Self::Error> {
+        self.maximise_if_minimised(window, focus_manager)
+            .and_then(|_| self.layout_manager.toggle_floating(window, focus_manager))
+            .map(|_| {
+                if self.fullscreen_window == Some(window) {
+                    self.fullscreen_window = None;
+                }
+                self.end_fullscreen_unless_focused(focus_manager.get_focused_window());
+            })
+    }
+}
+
+impl<LM: MinimiseTrait<Error = FloatWMError>> MinimiseTrait for FullscreenManager<LM> {
+    fn get_minimised_windows(&self) -> Vec<Window> {
+        self.layout_manager.get_minimised_windows()
+    }
+
+    /// Minimising the fullscreen window ends the fullscreen state, unminimising it makes it
+    /// fullscreen again.
+    fn toggle_minimised(&mut self,
+                        window: Window,
+                        focus_manager: &mut FocusManager)
+                        -> Result<(), Self::Error> {
+        if self.is_minimised(window) {
+            self.layout_manager.toggle_minimised(window, focus_manager).map(|_| {
+                if self.minimised_fullscreen_window == Some(window) {
+                    self.fullscreen_window = Some(window);
+                    self.minimised_fullscreen_window = None;
+                } else {
+                    self.end_fullscreen_unless_focused(focus_manager.get_focused_window());
+                }
+            })
+        } else {
+            self.layout_manager.toggle_minimised(window, focus_manager).map(|_| {
+                if self.fullscreen_window == Some(window) {
+                    self.fullscreen_window = None;
+                    self.minimised_fullscreen_window = Some(window);
+                }
+            })
+        }
+    }
+}
+
+impl<LM: MinimiseTrait<Error = FloatWMError>> FullscreenTrait for FullscreenManager<LM> {
+    fn get_fullscreen_window(&self) -> Option<Window> {
+        self.fullscreen_window
+    }
+
+    /// Make the window fullscreen and focus it. A minimised window is unminimised first. When the
+    /// window is already fullscreen it is restored to its previous state.
+    fn toggle_fullscreen(&mut self,
+                         window: Window,
+                         focus_manager: &mut FocusManager)
+                         -> Result<(), Self::Error> {
+        if self.fullscreen_window == Some(window) {
+            self.fullscreen_window = None;
+            Ok(())
+        } else if !self.is_managed(window) {
+            Err(FloatWMError::UnknownWindow(window))
+        } else {
+            self.maximise_if_minimised(window, focus_manager)
+                .and_then(|_| {
+                    focus_manager.focus_window(Some(window))
+                        .map_err(|error| error.to_float_error())
+                })
+                .and_then(|_| self.layout_manager.focus_shifted(Some(window)))
+                .map(|_| {
+                    self.fullscreen_window = Some(window);
+                    self.minimised_fullscreen_window = None;
+                })
+        }
+    }
+}
+
+impl<LM: MinimiseTrait<Error = FloatWMError> + MasterTrait> MasterTrait for FullscreenManager<LM> {
+    fn get_master_ratio(&self) -> u32 {
+        self.layout_manager.get_master_ratio()
+    }
+
+    fn get_master_count(&self) -> usize {
+        self.layout_manager.get_master_count()
+    }
+
+    fn shrink_master(&mut self) {
+        self.layout_manager.shrink_master()
+    }
+
+    fn expand_master(&mut self) {
+        self.layout_manager.expand_master()
+    }
+
+    fn inc_master(&mut self) {
+        self.layout_manager.inc_master()
+    }
+
+    fn dec_master(&mut self) {
+        self.layout_manager.dec_master()
+    }
+}
+
+impl<LM: MinimiseTrait<Error = FloatWMError>> FullscreenManager<LM> {
+    /// creates a FullscreenManager around the given layout manager, without a fullscreen window
+    pub fn new(layout_manager: LM) -> FullscreenManager<LM> {
+        FullscreenManager {
+            layout_manager,
+            fullscreen_window: None,
+            minimised_fullscreen_window: None,
+        }
+    }
+
+    /// The fullscreen window must be focused, so when the focus is on another window it stops
+    /// being fullscreen.
+    fn end_fullscreen_unless_focused(&mut self, focused_window: Option<Window>) {
+        if self.fullscreen_window != focused_window {
+            self.fullscreen_window = None;
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use wm_common::tests::window_manager;
+    use wm_common::tests::layout_message_support;
+    use wm_common::tests::tab_support;
+    use wm_common::tests::master_support;
+    use wm_common::tests::tiling_support;
+    use wm_common::tests::size_hint_support;
+    use wm_common::tests::direction_support;
+    use wm_common::tests::focus_policy_support;
+    use wm_common::tests::strut_support;
+    use wm_common::tests::window_switcher_support;
+    use wm_common::tests::float_support;
+    use wm_common::tests::float_and_tile_support;
+    use wm_common::tests::minimise_support;
+    use wm_common::tests::fullscreen_support;
+    use super::FullscreenWM;
+    use b_tiling_wm::VerticalLayout;
+    use cplwm_api::types::*;
+    use cplwm_api::wm::*;
+    use rules::{PropertyPattern, RuleAction, WindowMatcher, WindowRules};
+    use wm_common::WindowRuleSupport;
+
+    static SCREEN: Screen = Screen {
+        width: 800,
+        height: 600,
+    };
+
+    static SOME_GEOM: Geometry = Geometry {
+        x: 10,
+        y: 10,
+        width: 100,
+        height: 100,
+    };
+
+    #[test]
+    fn test_empty_tiling_wm() {
+        window_manager::test_empty_wm::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_adding_and_removing_some_windows() {
+        window_manager::test_adding_and_removing_windows::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_focus_and_unfocus_window() {
+        window_manager::test_focus_and_unfocus_window::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_cycle_focus_none_and_one_window() {
+        window_manager::test_cycle_focus_none_and_one_window::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_cycle_focus_multiple_windows() {
+        window_manager::test_cycle_focus_multiple_windows::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_get_window_info() {
+        window_manager::test_get_window_info::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_resize_screen() {
+        window_manager::test_resize_screen::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_get_master_window() {
+        tiling_support::test_master_tile::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_swap_with_master_window() {
+        tiling_support::test_swap_with_master::<FullscreenWM>();
+    }
+
+
+    #[test]
+    fn test_swap_windows() {
+        tiling_support::test_swap_windows::<FullscreenWM, VerticalLayout>(VerticalLayout::new());
+    }
+
+    #[test]
+    fn test_tiling_layout() {
+        tiling_support::test_get_window_info::<FullscreenWM, VerticalLayout>(VerticalLayout::new());
+    }
+
+    #[test]
+    fn test_tiled_size_hints() {
+        size_hint_support::test_tiled_size_hints::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_tiles_in_usable_area() {
+        strut_support::test_tiles_in_usable_area::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_remove_window_with_strut() {
+        strut_support::test_remove_window_with_strut::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_floating_window_ignores_struts() {
+        strut_support::test_floating_window_ignores_struts::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_focus_direction() {
+        direction_support::test_focus_direction::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_swap_direction() {
+        direction_support::test_swap_direction::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_direction_with_floats() {
+        direction_support::test_direction_with_floats::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_stack_order() {
+        focus_policy_support::test_stack_order::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_most_recently_used() {
+        focus_policy_support::test_most_recently_used::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_reading_order() {
+        focus_policy_support::test_reading_order::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_removal_focus() {
+        focus_policy_support::test_removal_focus::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_switch_commit() {
+        window_switcher_support::test_switch_commit::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_switch_cancel() {
+        window_switcher_support::test_switch_cancel::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_fullscreen_in_usable_area() {
+        strut_support::test_fullscreen_in_usable_area::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_get_floating_windows() {
+        float_support::test_get_floating_windows::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_toggle_floating() {
+        float_support::test_toggle_floating::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_set_window_geometry() {
+        float_support::test_set_window_geometry::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_floating_size_hints() {
+        size_hint_support::test_floating_size_hints::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_window_layout_order() {
+        float_support::test_window_layout_order::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_focus_floating_window_order() {
+        float_support::test_focus_floating_window_order::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_swapping_master_with_floating_window_no_tiles() {
+        float_and_tile_support::test_swapping_master_with_floating_window_no_tiles::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_swapping_master_with_floating_window() {
+        float_and_tile_support::test_swapping_master_with_floating_window::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_swap_windows_on_floating() {
+        float_and_tile_support::test_swap_windows_on_floating::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_swap_windows_with_float_focused() {
+        float_and_tile_support::test_swap_windows_with_float_focused::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_toggle_floating_focus() {
+        float_and_tile_support::test_toggle_floating_focus::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_minimise() {
+        minimise_support::test_minimise::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_minimise_state_after_focus() {
+        minimise_support::test_minimise_state_after_focus::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_minimise_of_floating_window() {
+        minimise_support::test_minimise_of_floating_window::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_minimise_of_tiled_window() {
+        minimise_support::test_minimise_of_tiled_window::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_minimise_order() {
+        minimise_support::test_minimise_order::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_minimise_state_after_cycle_focus() {
+        minimise_support::test_minimise_state_after_cycle_focus::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_toggle_fullscreen() {
+        fullscreen_support::test_toggle_fullscreen::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_toggle_fullscreen_twice() {
+        fullscreen_support::test_toggle_fullscreen_twice::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_add_fullscreen_window() {
+        fullscreen_support::test_add_fullscreen_window::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_fullscreen_and_focus() {
+        fullscreen_support::test_fullscreen_and_focus::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_remove_fullscreen_window() {
+        fullscreen_support::test_remove_fullscreen_window::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_fullscreen_resize_screen() {
+        fullscreen_support::test_fullscreen_resize_screen::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_fullscreen_of_floating_window() {
+        fullscreen_support::test_fullscreen_of_floating_window::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_minimise_fullscreen_window() {
+        fullscreen_support::test_minimise_fullscreen_window::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_fullscreen_minimised_window() {
+        fullscreen_support::test_fullscreen_minimised_window::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_swap_with_master_fullscreen_window() {
+        fullscreen_support::test_swap_with_master_fullscreen_window::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_shrink_and_expand_master() {
+        master_support::test_shrink_and_expand_master::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_inc_and_dec_master() {
+        master_support::test_inc_and_dec_master::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_master_messages() {
+        layout_message_support::test_master_messages::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_no_tabs_without_tab_bar() {
+        tab_support::test_no_tabs_without_tab_bar::<FullscreenWM>();
+    }
+
+    #[test]
+    fn test_window_rules() {
+        let mut wm = FullscreenWM::new(SCREEN);
+        wm.set_window_rules(WindowRules::new()
+            .with_rule(WindowMatcher::any()
+                           .with_class(PropertyPattern::Equals("mpv".to_string())),
+                       vec![RuleAction::Fullscreen])
+            .with_rule(WindowMatcher::any()
+                           .with_title(PropertyPattern::Contains("Downloads".to_string())),
+                       vec![RuleAction::Minimise])
+            .with_rule(WindowMatcher::any(), vec![RuleAction::Workspace(3)]));
+
+        // the workspace rule is ignored, there is a single workspace
+        assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+        assert_eq!(Some(1), wm.get_focused_window());
 
+        assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)
+                .with_properties(WindowProperties::new().with_class("mpv", "mpv")))
+            .is_ok());
+        assert_eq!(Some(2), wm.get_fullscreen_window());
+        assert_eq!(Some(2), wm.get_focused_window());
 
-/// Replace `()` with the name of your window manager data type.
-pub type WMName = ();
+        assert!(wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)
+                .with_properties(WindowProperties::new().with_title("Downloads")))
+            .is_ok());
+        assert_eq!(vec![3], wm.get_minimised_windows());
+        assert_eq!(Some(2), wm.get_fullscreen_window());
+        assert_eq!(Some(2), wm.get_focused_window());
+    }
+}
@@ -20,11 +20,13 @@
 // Add imports here
 use std::cmp;
 use std::collections::VecDeque;
-use cplwm_api::types::{GapSize, Geometry, PrevOrNext, Screen, Window, WindowLayout, WindowWithInfo};
-use cplwm_api::wm::{GapSupport, TilingSupport, WindowManager};
-use wm_common::{GapTrait, LayoutManager, Manager, TilingLayout, TilingTrait};
+use cplwm_api::types::{Direction, GapSize, Geometry, PrevOrNext, Screen, Tab, Window,
+                       WindowLayout, WindowWithInfo};
+use cplwm_api::wm::{GapSupport, TabSupport, TilingSupport, WindowManager};
+use wm_common::{FocusPolicySupport, GapTrait, LayoutManager, LayoutMessage, LayoutMessageSupport,
+                LayoutSwitchTrait, Manager, MasterTrait, TilingLayout, TilingTrait};
 use wm_common::error::StandardError;
-use a_fullscreen_wm::FocusManager;
+use a_fullscreen_wm::{FocusManager, FocusPolicy, RemovalFocus};
 use b_tiling_wm::{TileManager, VerticalLayout};
 
 
@@ -50,11 +52,7 @@ impl WindowManager for TilingWM {
     fn new(screen: Screen) -> TilingWM {
         TilingWM {
             focus_manager: FocusManager::new(),
-            tile_manager: TileManager::new(screen,
-                                           GapLayout {
-                                               tiling_layout: VerticalLayout {},
-                                               gap: 0,
-                                           }),
+            tile_manager: TileManager::new(screen, GapLayout::new(VerticalLayout::new(), 0)),
         }
     }
 
@@ -67,14 +65,23 @@ impl WindowManager for TilingWM {
     }
     fn add_window(&mut self, window_with_info: WindowWithInfo) -> Result<(), Self::Error> {
         self.focus_manager
-            .add_window(window_with_info)
+            .add_window(window_with_info.clone())
             .and_then(|_| self.tile_manager.add_window(window_with_info))
     }
 
     fn remove_window(&mut self, window: Window) -> Result<(), Self::Error> {
+        let was_focused = self.get_focused_window() == Some(window);
+        let next_tile = self.tile_manager.get_next_tile(window);
         self.focus_manager
             .remove_window(window)
             .and_then(|_| self.tile_manager.remove_window(window))
+            .and_then(|_| if was_focused {
+                let master = self.tile_manager.get_master_window();
+                self.focus_manager.focus_after_removal(next_tile, master)
+            } else {
+                Ok(())
+            })
+            .and_then(|_| self.tile_manager.focus_shifted(self.focus_manager.get_focused_window()))
     }
 
     fn get_window_layout(&self) -> WindowLayout {
@@ -85,11 +92,27 @@ impl WindowManager for TilingWM {
     }
 
     fn focus_window(&mut self, window: Option<Window>) -> Result<(), Self::Error> {
-        self.focus_manager.focus_window(window)
+        self.focus_manager
+            .focus_window(window)
+            .and_then(|_| self.tile_manager.focus_shifted(window))
     }
 
     fn cycle_focus(&mut self, dir: PrevOrNext) {
-        self.focus_manager.cycle_focus(dir)
+        // the layout may decide which tile comes next, e.g. to respect its containers
+        let neighbour = self.focus_manager
+            .get_focused_window()
+            .and_then(|window| self.tile_manager.get_focus_neighbour(window, dir));
+        let windows = self.tile_manager.get_window_layout();
+        match self.focus_manager
+            .cycle_focus_in(dir, &windows, neighbour)
+            .and_then(|_| {
+                let focused = self.focus_manager.get_focused_window();
+                self.tile_manager.focus_shifted(focused)
+            }) {
+            Ok(_) => {}
+            // rather no focused window than one the layout does not know is focused
+            Err(_) => self.focus_manager.unfocus(),
+        }
     }
 
     fn get_window_info(&self, window: Window) -> Result<WindowWithInfo, Self::Error> {
@@ -117,6 +140,66 @@ impl TilingSupport for TilingWM {
     fn swap_windows(&mut self, dir: PrevOrNext) {
         self.tile_manager.swap_windows(dir, &self.focus_manager)
     }
+
+    fn swap_direction(&mut self, dir: Direction, wrap: bool) -> Result<(), Self::Error> {
+        self.tile_manager.swap_direction(dir, wrap, &self.focus_manager)
+    }
+}
+
+impl LayoutMessageSupport for TilingWM {
+    fn send_layout_message(&mut self, message: LayoutMessage) -> bool {
+        self.tile_manager.send_layout_message(message)
+    }
+}
+
+impl FocusPolicySupport for TilingWM {
+    fn get_focus_policy(&self) -> FocusPolicy {
+        self.focus_manager.focus_policy
+    }
+
+    fn set_focus_policy(&mut self, policy: FocusPolicy) {
+        self.focus_manager.focus_policy = policy
+    }
+
+    fn get_removal_focus(&self) -> RemovalFocus {
+        self.focus_manager.removal_focus
+    }
+
+    fn set_removal_focus(&mut self, removal_focus: RemovalFocus) {
+        self.focus_manager.removal_focus = removal_focus
+    }
+}
+
+impl TabSupport for TilingWM {
+    fn get_tabs(&self) -> Vec<Tab> {
+        self.tile_manager.get_tabs()
+    }
+}
+
+impl MasterTrait for TilingWM {
+    fn get_master_ratio(&self) -> u32 {
+        self.tile_manager.get_master_ratio()
+    }
+
+    fn get_master_count(&self) -> usize {
+        self.tile_manager.get_master_count()
+    }
+
+    fn shrink_master(&mut self) {
+        self.tile_manager.shrink_master()
+    }
+
+    fn expand_master(&mut self) {
+        self.tile_manager.expand_master()
+    }
+
+    fn inc_master(&mut self) {
+        self.tile_manager.inc_master()
+    }
+
+    fn dec_master(&mut self) {
+        self.tile_manager.dec_master()
+    }
 }
 
 impl GapSupport for TilingWM {
@@ -128,25 +211,123 @@ impl GapSupport for TilingWM {
     }
 }
 
-impl<T: GapTrait> TileManager<T> {
-    fn get_gap(&self) -> GapSize {
-        self.layout.get_gap()
+/// The outer gap at each side of the screen
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq)]
+pub struct OuterGaps {
+    /// gap at the top of the screen
+    pub top: GapSize,
+    /// gap at the right of the screen
+    pub right: GapSize,
+    /// gap at the bottom of the screen
+    pub bottom: GapSize,
+    /// gap at the left of the screen
+    pub left: GapSize,
+}
+
+impl OuterGaps {
+    /// the same outer gap at every side
+    pub fn uniform(gap: GapSize) -> OuterGaps {
+        OuterGaps {
+            top: gap,
+            right: gap,
+            bottom: gap,
+            left: gap,
+        }
     }
+}
 
-    fn set_gap(&mut self, gap: GapSize) {
-        self.layout.set_gap(gap)
+/// Whether the geometries show a single tile, i.e. there is at most one tile or all tiles are
+/// stacked on top of each other.
+fn shows_single_tile(geometries: &[(Window, Geometry)]) -> bool {
+    match geometries.split_first() {
+        None => true,
+        Some((&(_, first), rest)) => rest.iter().all(|&(_, geometry)| geometry == first),
     }
 }
 
 /// A TIlingLayout which wraps another layout and adds a gap
+///
+/// Every tile is shrunk by `gap` on all sides, as `GapSupport` requires. On top of that,
+/// neighbouring tiles are `inner_gap` apart and tiles at the side of the screen keep the
+/// `outer_gaps` of that side.
 #[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
 pub struct GapLayout<T: TilingLayout> {
-    /// size of the gap
+    /// size of the gap around every tile
     pub gap: GapSize,
+    /// extra space between two neighbouring tiles
+    pub inner_gap: GapSize,
+    /// extra space between the tiles and the sides of the screen
+    pub outer_gaps: OuterGaps,
+    /// drop all gaps when only one tile is visible
+    pub smart_gaps: bool,
     /// the underlying layout strategy
     pub tiling_layout: T,
 }
 
+impl<T: TilingLayout> GapLayout<T> {
+    /// wrap the given layout with a gap around every tile
+    pub fn new(tiling_layout: T, gap: GapSize) -> GapLayout<T> {
+        GapLayout {
+            gap,
+            inner_gap: 0,
+            outer_gaps: OuterGaps::uniform(0),
+            smart_gaps: false,
+            tiling_layout,
+        }
+    }
+
+    /// builder setting the space between neighbouring tiles
+    pub fn with_inner_gap(mut self, inner_gap: GapSize) -> GapLayout<T> {
+        self.inner_gap = inner_gap;
+        self
+    }
+
+    /// builder setting the space at the sides of the screen
+    pub fn with_outer_gaps(mut self, outer_gaps: OuterGaps) -> GapLayout<T> {
+        self.outer_gaps = outer_gaps;
+        self
+    }
+
+    /// builder enabling or disabling smart gaps
+    pub fn with_smart_gaps(mut self, smart_gaps: bool) -> GapLayout<T> {
+        self.smart_gaps = smart_gaps;
+        self
+    }
+
+    /// Whether smart gaps drop the gaps for the given tiles, i.e. the wrapped layout shows a single
+    /// tile.
+    fn drops_gaps(&self, screen: &Screen, tiles: &VecDeque<Window>) -> bool {
+        self.smart_gaps &&
+        shows_single_tile(&self.tiling_layout.get_window_geometries(screen, tiles))
+    }
+
+    /// Shrink the geometry of the wrapped layout. Sides at the border of the screen get the
+    /// outer gap, inner sides half of the inner gap: the tile at the right or bottom of an
+    /// inner edge gets the rounded up half, so the tiles at both sides add up to `inner_gap`.
+    fn add_gaps(&self, geometry: Geometry, screen: &Screen, single_tile: bool) -> Geometry {
+        if self.smart_gaps && single_tile {
+            return geometry;
+        }
+        let before_inner = self.inner_gap / 2;
+        let after_inner = self.inner_gap - before_inner;
+        let at_right = geometry.x + geometry.width as i32 >= screen.width as i32;
+        let at_bottom = geometry.y + geometry.height as i32 >= screen.height as i32;
+        let left = self.gap + if geometry.x <= 0 { self.outer_gaps.left } else { after_inner };
+        let top = self.gap + if geometry.y <= 0 { self.outer_gaps.top } else { after_inner };
+        let right = self.gap + if at_right { self.outer_gaps.right } else { before_inner };
+        let bottom = self.gap + if at_bottom { self.outer_gaps.bottom } else { before_inner };
+        // never move a tile past its own far side, so that shrunk tiles cannot overlap
+        let left = cmp::min(left, geometry.width);
+        let top = cmp::min(top, geometry.height);
+        Geometry {
+            x: geometry.x + left as i32,
+            y: geometry.y + top as i32,
+            width: geometry.width.saturating_sub(left + right),
+            height: geometry.height.saturating_sub(top + bottom),
+        }
+    }
+}
+
 impl<T: TilingLayout> GapTrait for GapLayout<T> {
     fn get_gap(&self) -> GapSize {
         self.gap
@@ -157,6 +338,50 @@ impl<T: TilingLayout> GapTrait for GapLayout<T> {
     }
 }
 
+impl<T: TilingLayout + LayoutSwitchTrait> LayoutSwitchTrait for GapLayout<T> {
+    fn get_layout_name(&self) -> &str {
+        self.tiling_layout.get_layout_name()
+    }
+
+    fn next_layout(&mut self) {
+        self.tiling_layout.next_layout()
+    }
+
+    fn prev_layout(&mut self) {
+        self.tiling_layout.prev_layout()
+    }
+
+    fn select_layout(&mut self, name: &str) -> bool {
+        self.tiling_layout.select_layout(name)
+    }
+}
+
+impl<T: TilingLayout + MasterTrait> MasterTrait for GapLayout<T> {
+    fn get_master_ratio(&self) -> u32 {
+        self.tiling_layout.get_master_ratio()
+    }
+
+    fn get_master_count(&self) -> usize {
+        self.tiling_layout.get_master_count()
+    }
+
+    fn shrink_master(&mut self) {
+        self.tiling_layout.shrink_master()
+    }
+
+    fn expand_master(&mut self) {
+        self.tiling_layout.expand_master()
+    }
+
+    fn inc_master(&mut self) {
+        self.tiling_layout.inc_master()
+    }
+
+    fn dec_master(&mut self) {
+        self.tiling_layout.dec_master()
+    }
+}
+
 impl<T: TilingLayout> TilingLayout for GapLayout<T> {
     // use the same type for Error as the wrapped layout
     type Error = T::Error;
@@ -164,35 +389,96 @@ impl<T: TilingLayout> TilingLayout for GapLayout<T> {
     fn get_master_window(&self, tiles: &VecDeque<Window>) -> Option<Window> {
         self.tiling_layout.get_master_window(tiles)
     }
-    fn swap_with_master(&self,
+    fn swap_with_master(&mut self,
                         window: Window,
                         tiles: &mut VecDeque<Window>)
                         -> Result<(), Self::Error> {
         self.tiling_layout.swap_with_master(window, tiles)
     }
-    fn swap_windows(&self, window: Window, dir: PrevOrNext, tiles: &mut VecDeque<Window>) {
+    fn swap_windows(&mut self, window: Window, dir: PrevOrNext, tiles: &mut VecDeque<Window>) {
         self.tiling_layout.swap_windows(window, dir, tiles)
     }
+    fn swap_tiles(&mut self, window: Window, other: Window, tiles: &mut VecDeque<Window>) {
+        self.tiling_layout.swap_tiles(window, other, tiles)
+    }
     fn get_window_geometry(&self,
                            window: Window,
                            screen: &Screen,
                            tiles: &VecDeque<Window>)
                            -> Result<Geometry, Self::Error> {
-        self.tiling_layout.get_window_geometry(window, screen, tiles).and_then(|geometry| {
-            Ok(Geometry {
-                x: geometry.x + self.gap as i32,
-                y: geometry.y + self.gap as i32,
-                width: cmp::max(0, geometry.width as i32 - 2 * self.gap as i32) as u32,
-                height: cmp::max(0, geometry.height as i32 - 2 * self.gap as i32) as u32,
-            })
+        self.tiling_layout.get_window_geometry(window, screen, tiles).map(|geometry| {
+            self.add_gaps(geometry, screen, self.drops_gaps(screen, tiles))
         })
     }
+
+    /// The wrapped layout lays out the tiles once, for smart gaps and for the geometries
+    fn get_window_geometries(&self,
+                             screen: &Screen,
+                             tiles: &VecDeque<Window>)
+                             -> Vec<(Window, Geometry)> {
+        let geometries = self.tiling_layout.get_window_geometries(screen, tiles);
+        let single_tile = self.smart_gaps && shows_single_tile(&geometries);
+        geometries.into_iter()
+            .map(|(window, geometry)| (window, self.add_gaps(geometry, screen, single_tile)))
+            .collect()
+    }
+
+    fn handle_message(&mut self, message: LayoutMessage) -> bool {
+        self.tiling_layout.handle_message(message)
+    }
+
+    fn focus_shifted(&mut self, window: Option<Window>) {
+        self.tiling_layout.focus_shifted(window)
+    }
+
+    fn tile_added(&mut self, window: Window) {
+        self.tiling_layout.tile_added(window)
+    }
+
+    fn tile_removed(&mut self, window: Window) {
+        self.tiling_layout.tile_removed(window)
+    }
+
+    fn screen_resized(&mut self, screen: &Screen) {
+        self.tiling_layout.screen_resized(screen)
+    }
+
+    fn get_stacking_order(&self, tiles: &VecDeque<Window>) -> Vec<Window> {
+        self.tiling_layout.get_stacking_order(tiles)
+    }
+
+    fn get_focus_neighbour(&self,
+                           window: Window,
+                           dir: PrevOrNext,
+                           tiles: &VecDeque<Window>)
+                           -> Option<Window> {
+        self.tiling_layout.get_focus_neighbour(window, dir, tiles)
+    }
+
+    /// Like the tiles, every tab gets the gap at its sides and at its top, the gap below the tab
+    /// bar is the gap at the top of the tiles.
+    fn get_tabs(&self, screen: &Screen, tiles: &VecDeque<Window>) -> Vec<Tab> {
+        let single_tile = self.drops_gaps(screen, tiles);
+        self.tiling_layout
+            .get_tabs(screen, tiles)
+            .into_iter()
+            .map(|tab| {
+                Tab {
+                    geometry: Geometry {
+                        height: tab.geometry.height,
+                        ..self.add_gaps(tab.geometry, screen, single_tile)
+                    },
+                    ..tab
+                }
+            })
+            .collect()
+    }
 }
 
 
 #[cfg(test)]
 mod vertical_layout_tests {
-    use super::GapLayout;
+    use super::{GapLayout, OuterGaps};
     use wm_common::TilingLayout;
     use b_tiling_wm::VerticalLayout;
     use std::collections::VecDeque;
@@ -211,10 +497,7 @@ mod vertical_layout_tests {
     #[test]
     fn test_vertical_layout_no_window() {
         // Initialize new GapLayout strategy
-        let layout = GapLayout {
-            tiling_layout: VerticalLayout {},
-            gap: 0,
-        };
+        let layout = GapLayout::new(VerticalLayout::new(), 0);
         // Initialize empty tile Deque
         let tiles = VecDeque::new();
 
@@ -225,10 +508,7 @@ mod vertical_layout_tests {
     #[test]
     fn test_vertical_layout_one_window() {
         // Initialize new GapLayout strategy
-        let layout = GapLayout {
-            tiling_layout: VerticalLayout {},
-            gap: 0,
-        };
+        let layout = GapLayout::new(VerticalLayout::new(), 0);
         // Initialize empty tile Deque
         let mut tiles = VecDeque::new();
         // Push one window on the Deque
@@ -247,10 +527,7 @@ mod vertical_layout_tests {
     #[test]
     fn test_vertical_layout_one_window_gapped() {
         // Initialize new GapLayout strategy
-        let layout = GapLayout {
-            tiling_layout: VerticalLayout {},
-            gap: 5,
-        };
+        let layout = GapLayout::new(VerticalLayout::new(), 5);
         // Initialize empty tile Deque
         let mut tiles = VecDeque::new();
         // Push one window on the Deque
@@ -269,10 +546,7 @@ mod vertical_layout_tests {
     #[test]
     fn test_vertical_layout_two_windows() {
         // Initialize new GapLayout strategy
-        let layout = GapLayout {
-            tiling_layout: VerticalLayout {},
-            gap: 0,
-        };
+        let layout = GapLayout::new(VerticalLayout::new(), 0);
         // Initialize empty tile Deque
         let mut tiles = VecDeque::new();
         // Push 2 tiles on the Deque, the first one will be the master in this layout.
@@ -303,10 +577,7 @@ mod vertical_layout_tests {
     #[test]
     fn test_vertical_layout_two_windows_gapped() {
         // Initialize new GapLayout strategy
-        let layout = GapLayout {
-            tiling_layout: VerticalLayout {},
-            gap: 5,
-        };
+        let layout = GapLayout::new(VerticalLayout::new(), 5);
         // Initialize empty tile Deque
         let mut tiles = VecDeque::new();
         // Push 2 tiles on the Deque, the first one will be the master in this layout.
@@ -337,10 +608,7 @@ mod vertical_layout_tests {
     #[test]
     fn test_vertical_layout_multiple_windows_regular_screen() {
         // Initialize new GapLayout strategy
-        let layout = GapLayout {
-            tiling_layout: VerticalLayout {},
-            gap: 0,
-        };
+        let layout = GapLayout::new(VerticalLayout::new(), 0);
         // Initialize empty tile Deque
         let mut tiles = VecDeque::new();
         // Push 4 tiles on the Deque, the first one will be the master in this layout.
@@ -386,10 +654,7 @@ mod vertical_layout_tests {
     #[test]
     fn test_vertical_layout_multiple_windows_regular_screen_gapped() {
         // Initialize new GapLayout strategy
-        let layout = GapLayout {
-            tiling_layout: VerticalLayout {},
-            gap: 5,
-        };
+        let layout = GapLayout::new(VerticalLayout::new(), 5);
         // Initialize empty tile Deque
         let mut tiles = VecDeque::new();
         // Push 4 tiles on the Deque, the first one will be the master in this layout.
@@ -436,10 +701,7 @@ mod vertical_layout_tests {
     #[test]
     fn test_vertical_layout_multiple_windows_irregular_screen() {
         // Initialize new GapLayout strategy
-        let layout = GapLayout {
-            tiling_layout: VerticalLayout {},
-            gap: 0,
-        };
+        let layout = GapLayout::new(VerticalLayout::new(), 0);
         // Initialize empty tile Deque
         let mut tiles = VecDeque::new();
         // Push 4 tiles on the Deque, the first one will be the master in this layout.
@@ -487,10 +749,7 @@ mod vertical_layout_tests {
     #[test]
     fn test_vertical_layout_multiple_windows_irregular_screen_gapped() {
         // Initialize new GapLayout strategy
-        let layout = GapLayout {
-            tiling_layout: VerticalLayout {},
-            gap: 5,
-        };
+        let layout = GapLayout::new(VerticalLayout::new(), 5);
         // Initialize empty tile Deque
         let mut tiles = VecDeque::new();
         // Push 4 tiles on the Deque, the first one will be the master in this layout.
@@ -533,13 +792,130 @@ mod vertical_layout_tests {
                    },
                    layout.get_window_geometry(4, &SCREEN2, &tiles).ok().unwrap());
     }
+
+    #[test]
+    fn test_vertical_layout_inner_and_outer_gaps() {
+        let layout = GapLayout::new(VerticalLayout::new(), 0)
+            .with_inner_gap(10)
+            .with_outer_gaps(OuterGaps::uniform(10));
+        let tiles: VecDeque<Window> = (1..4).collect();
+
+        // every gap, between tiles or at the side of the screen, is 10 pixels wide
+        assert_eq!(Geometry {
+                       x: 10,
+                       y: 10,
+                       width: 85,
+                       height: 280,
+                   },
+                   layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap());
+        assert_eq!(Geometry {
+                       x: 105,
+                       y: 10,
+                       width: 85,
+                       height: 135,
+                   },
+                   layout.get_window_geometry(2, &SCREEN1, &tiles).ok().unwrap());
+        assert_eq!(Geometry {
+                       x: 105,
+                       y: 155,
+                       width: 85,
+                       height: 135,
+                   },
+                   layout.get_window_geometry(3, &SCREEN1, &tiles).ok().unwrap());
+    }
+
+    #[test]
+    fn test_vertical_layout_odd_inner_gap() {
+        let layout = GapLayout::new(VerticalLayout::new(), 0).with_inner_gap(5);
+        let tiles: VecDeque<Window> = (1..3).collect();
+
+        let master = layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap();
+        let other = layout.get_window_geometry(2, &SCREEN1, &tiles).ok().unwrap();
+        assert_eq!(98, master.width);
+        assert_eq!(103, other.x);
+        assert_eq!(SCREEN1.width, other.x as u32 + other.width);
+    }
+
+    #[test]
+    fn test_vertical_layout_per_side_outer_gaps() {
+        let layout = GapLayout::new(VerticalLayout::new(), 2).with_outer_gaps(OuterGaps {
+            top: 20,
+            right: 0,
+            bottom: 3,
+            left: 8,
+        });
+        let mut tiles = VecDeque::new();
+        tiles.push_back(1);
+
+        // the outer gaps come on top of the gap around every tile
+        assert_eq!(Geometry {
+                       x: 10,
+                       y: 22,
+                       width: SCREEN1.width - 12,
+                       height: SCREEN1.height - 27,
+                   },
+                   layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap());
+    }
+
+    #[test]
+    fn test_vertical_layout_smart_gaps() {
+        let layout = GapLayout::new(VerticalLayout::new(), 5)
+            .with_inner_gap(10)
+            .with_outer_gaps(OuterGaps::uniform(10))
+            .with_smart_gaps(true);
+        let mut tiles = VecDeque::new();
+        tiles.push_back(1);
+
+        // a single tile gets no gaps at all
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: SCREEN1.width,
+                       height: SCREEN1.height,
+                   },
+                   layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap());
+        assert_eq!(vec![(1, SCREEN1.to_geometry())],
+                   layout.get_window_geometries(&SCREEN1, &tiles));
+
+        // as soon as there is a second tile, the gaps are back
+        tiles.push_back(2);
+        assert_eq!(Geometry {
+                       x: 15,
+                       y: 15,
+                       width: 75,
+                       height: 270,
+                   },
+                   layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap());
+        let geometries: Vec<(Window, Geometry)> = tiles.iter()
+            .map(|w| (*w, layout.get_window_geometry(*w, &SCREEN1, &tiles).ok().unwrap()))
+            .collect();
+        assert_eq!(geometries, layout.get_window_geometries(&SCREEN1, &tiles));
+    }
+
+    #[test]
+    fn test_vertical_layout_huge_gap() {
+        let layout = GapLayout::new(VerticalLayout::new(), 150);
+        let tiles: VecDeque<Window> = (1..3).collect();
+
+        // tiles shrink to nothing, but never past their own geometry
+        let master = layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap();
+        let other = layout.get_window_geometry(2, &SCREEN1, &tiles).ok().unwrap();
+        assert_eq!((0, 0), (master.width, master.height));
+        assert_eq!((0, 0), (other.width, other.height));
+        assert!(master.x <= other.x);
+        assert_eq!(SCREEN1.width as i32, other.x);
+    }
 }
 
 
 #[cfg(test)]
 mod tests {
     use wm_common::tests::window_manager;
+    use wm_common::tests::layout_message_support;
+    use wm_common::tests::tab_support;
+    use wm_common::tests::master_support;
     use wm_common::tests::tiling_support;
+    use wm_common::tests::size_hint_support;
     use wm_common::tests::gap_support;
     use super::TilingWM;
     use super::GapLayout;
@@ -593,28 +969,44 @@ mod tests {
 
     #[test]
     fn test_swap_windows() {
-        let layout: GapLayout<VerticalLayout> = GapLayout {
-            tiling_layout: VerticalLayout {},
-            gap: 0,
-        };
+        let layout: GapLayout<VerticalLayout> = GapLayout::new(VerticalLayout::new(), 0);
         tiling_support::test_swap_windows::<TilingWM, GapLayout<VerticalLayout>>(layout);
     }
 
     #[test]
     fn test_tiling_layout() {
-        let layout: GapLayout<VerticalLayout> = GapLayout {
-            tiling_layout: VerticalLayout {},
-            gap: 0,
-        };
+        let layout: GapLayout<VerticalLayout> = GapLayout::new(VerticalLayout::new(), 0);
         tiling_support::test_get_window_info::<TilingWM, GapLayout<VerticalLayout>>(layout);
     }
 
+    #[test]
+    fn test_tiled_size_hints() {
+        size_hint_support::test_tiled_size_hints::<TilingWM>();
+    }
+
     #[test]
     fn test_set_gap() {
-        let layout: GapLayout<VerticalLayout> = GapLayout {
-            tiling_layout: VerticalLayout {},
-            gap: 0,
-        };
+        let layout: GapLayout<VerticalLayout> = GapLayout::new(VerticalLayout::new(), 0);
         gap_support::test_set_gap::<TilingWM, GapLayout<VerticalLayout>>(layout);
     }
+
+    #[test]
+    fn test_shrink_and_expand_master() {
+        master_support::test_shrink_and_expand_master::<TilingWM>();
+    }
+
+    #[test]
+    fn test_inc_and_dec_master() {
+        master_support::test_inc_and_dec_master::<TilingWM>();
+    }
+
+    #[test]
+    fn test_master_messages() {
+        layout_message_support::test_master_messages::<TilingWM>();
+    }
+
+    #[test]
+    fn test_no_tabs_without_tab_bar() {
+        tab_support::test_no_tabs_without_tab_bar::<TilingWM>();
+    }
 }
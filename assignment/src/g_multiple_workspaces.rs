@@ -17,18 +17,46 @@
 //!
 //! # Status
 //!
-//! COMPLETED: PARTIAL
+//! COMPLETED: YES
 //!
 //! COMMENTS:
-//! This manager only delegates everything on the the currently selected Wordspace. So certain
-//! invariants do not hold at the moment.
+//! Most actions are delegated to the current workspace. The workspace managing a window is found
+//! by asking every workspace, so windows added directly through `get_workspace_mut` are known
+//! too. The choices made:
+//!
+//! * `get_windows` returns the windows of all workspaces, `is_managed`, `get_window_info` and
+//!   `remove_window` work for windows on any workspace.
+//! * `add_window` adds to the current workspace, or the one the window rules send the window to,
+//!   and fails when any workspace manages the window.
+//! * `focus_window`, `swap_with_master` and unminimising a window on another workspace make
+//!   that workspace the current one, as the window has to be visible or focused afterwards.
+//! * `toggle_floating`, `set_window_geometry` and minimising a window are executed on the
+//!   workspace managing the window, without switching to it.
+//! * The getters of the other traits, e.g. `get_floating_windows`, only concern the current
+//!   workspace.
+//! * A window rule sending a window to another workspace does not switch to that workspace. The
+//!   other rules are applied by the window manager of the workspace, so they only apply when it
+//!   supports window rules. Windows moved to another workspace do not get the rules again.
+//! * The struts reserve their areas along the edges of every output. Every workspace is shown in
+//!   the usable area of its output, so its window manager does not need to support struts.
+//!   Floating windows can still be placed over the reserved areas, but move along with the
+//!   usable area when the struts change.
+//! * A new workspace is a copy of an empty template workspace, which keeps the settings shared
+//!   by all workspaces. It is sized to the usable part of the output it is created for, the
+//!   focused output unless it is created for a new output.
 //!
 
 // Add imports here
 use cplwm_api::types::*;
 use cplwm_api::wm::*;
+use wm_common::{FocusPolicySupport, LayoutMessage, LayoutMessageSupport, MasterTrait,
+                WindowRuleSupport, WindowSwitcherSupport};
 use wm_common::error::MultiWorkspaceError;
+use a_fullscreen_wm::{FocusPolicy, RemovalFocus};
 use d_minimising_windows::MinimiseWM;
+use layouts::border::BorderManager;
+use rules::WindowRules;
+use struts::StrutManager;
 
 /// public type
 pub type WMName = MultiWorkspaces<MinimiseWM>;
@@ -38,67 +66,320 @@ pub type WMName = MultiWorkspaces<MinimiseWM>;
 pub struct MultiWorkspaces<WM: WindowManager> {
     /// all the workspaces
     pub workspaces: Vec<WM>,
-    /// index of the current workspace in the workspaces vector
-    pub current_workspace: WorkspaceIndex,
-    /// the current screen size
-    pub screen: Screen,
+    /// an empty workspace with the settings the workspaces created later start with
+    pub template: WM,
+    /// all the outputs and the workspace each of them shows, there is always at least one
+    pub outputs: Vec<OutputWorkspace>,
+    /// id of the focused output, the workspace it shows is the current workspace
+    pub focused_output: OutputId,
+    /// the window rules, the workspace the added windows go to is picked with them
+    pub window_rules: WindowRules,
+    /// the areas reserved by panels and docks, along the edges of every output
+    pub strut_manager: StrutManager,
+}
+
+/// An output together with the index of the workspace it shows
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct OutputWorkspace {
+    /// the output
+    pub output: Output,
+    /// index of the workspace shown on the output
+    pub workspace: WorkspaceIndex,
 }
 
 impl<WM: WindowManager> MultiWorkspaces<WM> {
-    fn get_current_workspace(&self) -> Result<&WM, MultiWorkspaceError> {
+    fn get_current_workspace(&self) -> Result<&WM, MultiWorkspaceError<WM::Error>> {
         self.get_workspace(self.get_current_workspace_index())
     }
 
-    fn get_current_workspace_mut(&mut self) -> Result<&mut WM, MultiWorkspaceError> {
+    fn get_current_workspace_mut(&mut self) -> Result<&mut WM, MultiWorkspaceError<WM::Error>> {
         let index = self.get_current_workspace_index();
         self.get_workspace_mut(index)
     }
+
+    /// The window info with the geometry relative to the workspace at the given index. Like in
+    /// `get_window_info`, the geometry is only translated when the workspace is shown.
+    fn to_workspace_local(&self,
+                          index: WorkspaceIndex,
+                          window_with_info: WindowWithInfo)
+                          -> WindowWithInfo {
+        match self.get_workspace_output(index) {
+            None => window_with_info,
+            Some(output) => {
+                let output = self.get_usable_output(output);
+                WindowWithInfo {
+                    geometry: to_local(&output, window_with_info.geometry),
+                    ..window_with_info
+                }
+            }
+        }
+    }
+
+    fn get_output_workspace(&self,
+                            id: OutputId)
+                            -> Result<&OutputWorkspace, MultiWorkspaceError<WM::Error>> {
+        self.outputs
+            .iter()
+            .find(|o| o.output.id == id)
+            .ok_or(MultiWorkspaceError::UnknownOutput(id))
+    }
+
+    fn get_focused_output_workspace(&self)
+                                    -> Result<&OutputWorkspace, MultiWorkspaceError<WM::Error>> {
+        self.get_output_workspace(self.focused_output)
+    }
+
+    /// The focused output, or the first output when the focused output is unknown.
+    fn get_current_output(&self) -> Output {
+        self.get_focused_output_workspace()
+            .map(|o| o.output)
+            .unwrap_or(self.outputs[0].output)
+    }
+
+    /// A new workspace with the settings of the template, sized to the usable part of the given
+    /// output.
+    fn new_workspace(&self, output: Output) -> WM {
+        let mut wm = self.template.clone();
+        wm.resize_screen(self.get_usable_output(output).screen);
+        wm
+    }
+
+    /// Return the index of the workspace shown on the output with the given id.
+    pub fn get_output_workspace_index(&self,
+                                      id: OutputId)
+                                      -> Result<WorkspaceIndex, MultiWorkspaceError<WM::Error>> {
+        self.get_output_workspace(id).map(|o| o.workspace)
+    }
+
+    /// Return the index of the workspace managing the given window, if any.
+    pub fn get_window_workspace_index(&self, window: Window) -> Option<WorkspaceIndex> {
+        self.workspaces.iter().position(|wm| wm.is_managed(window))
+    }
+
+    fn get_window_workspace(&self, window: Window) -> Result<&WM, MultiWorkspaceError<WM::Error>> {
+        self.get_window_workspace_index(window)
+            .ok_or(MultiWorkspaceError::UnknownWindow(window))
+            .and_then(|index| self.get_workspace(index))
+    }
+
+    fn get_window_workspace_mut(&mut self,
+                                window: Window)
+                                -> Result<&mut WM, MultiWorkspaceError<WM::Error>> {
+        self.get_window_workspace_index(window)
+            .ok_or(MultiWorkspaceError::UnknownWindow(window))
+            .and_then(move |index| self.get_workspace_mut(index))
+    }
+
+    /// Make the workspace managing the window the current workspace, by focusing the output
+    /// showing it or by switching to it.
+    fn show_window_workspace(&mut self,
+                             window: Window)
+                             -> Result<(), MultiWorkspaceError<WM::Error>> {
+        match self.get_window_workspace_index(window) {
+            None => Err(MultiWorkspaceError::UnknownWindow(window)),
+            Some(index) => {
+                match self.get_workspace_output(index) {
+                    None => self.switch_workspace(index),
+                    Some(output) => self.focus_output(output.id),
+                }
+            }
+        }
+    }
+
+    /// The output on which the workspace at the given index is shown, if any.
+    fn get_workspace_output(&self, index: WorkspaceIndex) -> Option<Output> {
+        self.outputs.iter().find(|o| o.workspace == index).map(|o| o.output)
+    }
+
+    /// The part of the output not reserved by the struts, in which its workspace is shown.
+    fn get_usable_output(&self, output: Output) -> Output {
+        let area = self.strut_manager.get_strut().get_usable_area(&output.screen);
+        Output::new(output.id,
+                    output.x + area.x,
+                    output.y + area.y,
+                    Screen {
+                        width: area.width,
+                        height: area.height,
+                    })
+    }
+
+    /// Show the workspace at the given index on the given output, resizing it to the usable
+    /// part of the output.
+    fn show_workspace(&mut self, position: usize, index: WorkspaceIndex) {
+        let output = self.get_usable_output(self.outputs[position].output);
+        self.outputs[position].workspace = index;
+        match self.workspaces.get_mut(index) {
+            None => {}
+            Some(wm) => wm.resize_screen(output.screen),
+        }
+    }
+
+    /// Resize the workspaces shown on the outputs after the struts changed.
+    fn apply_struts(&mut self) {
+        for position in 0..self.outputs.len() {
+            let index = self.outputs[position].workspace;
+            self.show_workspace(position, index);
+        }
+    }
+
+    /// The window layout of the output, with the geometries relative to the origin.
+    fn get_output_workspace_layout(&self,
+                                   output_workspace: &OutputWorkspace)
+                                   -> Result<WindowLayout, MultiWorkspaceError<WM::Error>> {
+        let output = self.get_usable_output(output_workspace.output);
+        self.get_workspace(output_workspace.workspace).map(|wm| {
+            let layout = wm.get_window_layout();
+            WindowLayout {
+                focused_window: if output.id == self.focused_output {
+                    layout.focused_window
+                } else {
+                    None
+                },
+                windows: layout.windows
+                    .into_iter()
+                    .map(|(w, geometry)| (w, to_global(&output, geometry)))
+                    .collect(),
+            }
+        })
+    }
+}
+
+/// Add a window moved from another workspace, urgent and minimised when it was so there, without
+/// applying the window rules again. A minimised window does not take the focus. When the window
+/// can not keep its state it is removed again.
+fn add_moved_window<WM>(wm: &mut WM,
+                        info: WindowWithInfo,
+                        minimised: bool,
+                        urgent: bool)
+                        -> Result<(), WM::Error>
+    where WM: MinimiseSupport + BorderSupport + WindowRuleSupport
+{
+    let window = info.window;
+    let focused_window = wm.get_focused_window();
+    wm.add_window_without_rules(info).and_then(|_| {
+        wm.set_urgent(window, urgent)
+            .and_then(|_| if minimised {
+                wm.toggle_minimised(window).and_then(|_| wm.focus_window(focused_window))
+            } else {
+                Ok(())
+            })
+            .or_else(|error| wm.remove_window(window).and(Err(error)))
+    })
+}
+
+/// Translate a geometry relative to the output to a geometry relative to the origin.
+fn to_global(output: &Output, geometry: Geometry) -> Geometry {
+    Geometry {
+        x: geometry.x + output.x,
+        y: geometry.y + output.y,
+        ..geometry
+    }
+}
+
+/// Translate a geometry relative to the origin to a geometry relative to the output.
+fn to_local(output: &Output, geometry: Geometry) -> Geometry {
+    Geometry {
+        x: geometry.x - output.x,
+        y: geometry.y - output.y,
+        ..geometry
+    }
 }
 
 impl<WM: WindowManager> WindowManager for MultiWorkspaces<WM> {
-    type Error = MultiWorkspaceError;
+    type Error = MultiWorkspaceError<WM::Error>;
 
     fn new(screen: Screen) -> Self {
         MultiWorkspaces {
             workspaces: vec![WM::new(screen)],
-            current_workspace: 0,
-            screen: screen,
+            template: WM::new(screen),
+            outputs: vec![OutputWorkspace {
+                              output: Output::new(0, 0, 0, screen),
+                              workspace: 0,
+                          }],
+            focused_output: 0,
+            window_rules: WindowRules::new(),
+            strut_manager: StrutManager::new(),
         }
     }
 
+    /// The layouts of all outputs, the focused output comes last.
     fn get_window_layout(&self) -> WindowLayout {
-        self.get_current_workspace()
-            .and_then(|wm| Ok(wm.get_window_layout()))
-            .unwrap_or(WindowLayout::new())
+        let mut windows = Vec::new();
+        let (focused, others): (Vec<&OutputWorkspace>, Vec<&OutputWorkspace>) =
+            self.outputs.iter().partition(|o| o.output.id == self.focused_output);
+        for output_workspace in others.into_iter().chain(focused) {
+            match self.get_output_workspace_layout(output_workspace) {
+                Err(_) => {}
+                Ok(layout) => windows.extend(layout.windows),
+            }
+        }
+        WindowLayout {
+            focused_window: self.get_focused_window(),
+            windows,
+        }
     }
 
+    /// The windows of all workspaces.
     fn get_windows(&self) -> Vec<Window> {
-        self.get_current_workspace()
-            .and_then(|wm| Ok(wm.get_windows()))
-            .unwrap_or(Vec::new())
+        self.workspaces.iter().flat_map(|wm| wm.get_windows()).collect()
     }
 
+    fn is_managed(&self, window: Window) -> bool {
+        self.get_window_workspace_index(window).is_some()
+    }
+
+    fn get_focused_window(&self) -> Option<Window> {
+        match self.get_current_workspace() {
+            Err(_) => None,
+            Ok(wm) => wm.get_focused_window(),
+        }
+    }
+
+    /// The window is added to the workspace the window rules send it to, or else to the current
+    /// workspace. Nothing is added when that workspace does not exist and is not the next new
+    /// one. The workspace applies the other rules itself.
     fn add_window(&mut self, window_with_info: WindowWithInfo) -> Result<(), Self::Error> {
-        self.get_current_workspace_mut()
-            .and_then(|wm| {
+        if self.is_managed(window_with_info.window) {
+            return Err(MultiWorkspaceError::AlReadyManagedWindow(window_with_info.window));
+        }
+        let index = self.window_rules
+            .get_workspace(&window_with_info.properties)
+            .unwrap_or(self.get_current_workspace_index());
+        let window_with_info = self.to_workspace_local(index, window_with_info);
+        if index == self.workspaces.len() {
+            let mut wm = self.new_workspace(self.get_current_output());
+            wm.add_window(window_with_info)
+                .map_err(MultiWorkspaceError::WrappedError)
+                .map(|_| self.workspaces.push(wm))
+        } else {
+            self.get_workspace_mut(index).and_then(|wm| {
                 wm.add_window(window_with_info)
-                    .map_err(|_| MultiWorkspaceError::WrappedError)
+                    .map_err(MultiWorkspaceError::WrappedError)
             })
+        }
     }
 
+    /// The window is removed from the workspace managing it.
     fn remove_window(&mut self, window: Window) -> Result<(), Self::Error> {
-        self.get_current_workspace_mut()
+        self.get_window_workspace_mut(window)
             .and_then(|wm| {
                 wm.remove_window(window)
-                    .map_err(|_| MultiWorkspaceError::WrappedError)
+                    .map_err(MultiWorkspaceError::WrappedError)
             })
+            .map(|_| self.remove_window_strut(window))
     }
 
+    /// Focusing a window on another workspace makes that workspace the current one, see
+    /// `show_window_workspace`.
     fn focus_window(&mut self, window: Option<Window>) -> Result<(), Self::Error> {
-        self.get_current_workspace_mut()
+        match window {
+                None => Ok(()),
+                Some(w) => self.show_window_workspace(w),
+            }
+            .and_then(|_| self.get_current_workspace_mut())
             .and_then(|wm| {
                 wm.focus_window(window)
-                    .map_err(|_| MultiWorkspaceError::WrappedError)
+                    .map_err(MultiWorkspaceError::WrappedError)
             })
     }
 
@@ -109,34 +390,51 @@ impl<WM: WindowManager> WindowManager for MultiWorkspaces<WM> {
         }
     }
 
+    /// The geometry of a window on a hidden workspace is relative to that workspace.
     fn get_window_info(&self, window: Window) -> Result<WindowWithInfo, Self::Error> {
-        self.get_current_workspace()
-            .and_then(|wm| {
-                wm.get_window_info(window)
-                    .map_err(|_| MultiWorkspaceError::WrappedError)
-            })
+        let output = self.get_window_workspace_index(window)
+            .and_then(|index| self.get_workspace_output(index))
+            .map(|output| self.get_usable_output(output));
+        self.get_window_workspace(window).and_then(|wm| {
+            wm.get_window_info(window)
+                .map(|info| match output {
+                    None => info,
+                    Some(output) => {
+                        WindowWithInfo { geometry: to_global(&output, info.geometry), ..info }
+                    }
+                })
+                .map_err(MultiWorkspaceError::WrappedError)
+        })
     }
 
+    /// The screen of the focused output
     fn get_screen(&self) -> Screen {
-        self.screen
+        self.get_current_output().screen
     }
 
+    /// Resize the focused output, the hidden workspaces are resized along.
     fn resize_screen(&mut self, screen: Screen) {
-        self.screen = screen;
+        let focused_output = self.focused_output;
+        match self.outputs.iter().position(|o| o.output.id == focused_output) {
+            None => {}
+            Some(position) => {
+                self.outputs[position].output.screen = screen;
+                let workspace = self.outputs[position].workspace;
+                self.show_workspace(position, workspace);
+            }
+        }
+        let usable_screen = self.get_usable_output(Output::new(0, 0, 0, screen)).screen;
         for index in 0..self.workspaces.len() {
-            self.workspaces
-                .get_mut(index)
-                .and_then(|w| {
-                    w.resize_screen(screen);
-                    Some(())
-                });
+            if self.get_workspace_output(index).is_none() {
+                self.workspaces[index].resize_screen(usable_screen);
+            }
         }
     }
 }
 
 impl<WM: WindowManager> MultiWorkspaceSupport<WM> for MultiWorkspaces<WM> {
     fn get_current_workspace_index(&self) -> WorkspaceIndex {
-        self.current_workspace
+        self.get_focused_output_workspace().map(|o| o.workspace).unwrap_or(0)
     }
 
     fn get_workspace(&self, index: WorkspaceIndex) -> Result<&WM, Self::Error> {
@@ -147,17 +445,171 @@ impl<WM: WindowManager> MultiWorkspaceSupport<WM> for MultiWorkspaces<WM> {
         self.workspaces.get_mut(index).ok_or(MultiWorkspaceError::WorkspaceIndexOutOfBound(index))
     }
 
+    /// Show the workspace on the focused output. When the workspace is already shown on another
+    /// output, that output shows the current workspace instead.
     fn switch_workspace(&mut self, index: WorkspaceIndex) -> Result<(), Self::Error> {
-        if index < self.workspaces.len() {
-            self.current_workspace = index;
-            Ok(())
+        let current = self.get_current_workspace_index();
+        if index == current {
+            return Ok(());
         } else if index == self.workspaces.len() {
-            self.workspaces.push(WM::new(self.screen));
-            self.current_workspace = index;
-            Ok(())
-        } else {
-            Err(MultiWorkspaceError::WorkspaceIndexOutOfBound(index))
+            let wm = self.new_workspace(self.get_current_output());
+            self.workspaces.push(wm);
+        } else if index > self.workspaces.len() {
+            return Err(MultiWorkspaceError::WorkspaceIndexOutOfBound(index));
+        }
+        let focused_output = self.focused_output;
+        match self.outputs.iter().position(|o| o.workspace == index) {
+            None => {}
+            Some(other) => self.show_workspace(other, current),
+        }
+        self.outputs
+            .iter()
+            .position(|o| o.output.id == focused_output)
+            .ok_or(MultiWorkspaceError::UnknownOutput(focused_output))
+            .map(|position| self.show_workspace(position, index))
+    }
+}
+
+impl<WM: WindowManager> MultiOutputSupport for MultiWorkspaces<WM> {
+    fn get_outputs(&self) -> Vec<Output> {
+        self.outputs.iter().map(|o| o.output).collect()
+    }
+
+    fn get_focused_output(&self) -> OutputId {
+        self.focused_output
+    }
+
+    /// The new output shows the first workspace which is not shown on another output, a new
+    /// workspace is created if there is no such workspace.
+    fn add_output(&mut self, output: Output) -> Result<(), Self::Error> {
+        if self.get_output_workspace(output.id).is_ok() {
+            return Err(MultiWorkspaceError::AlreadyKnownOutput(output.id));
+        }
+        let index = (0..self.workspaces.len())
+            .find(|&index| self.get_workspace_output(index).is_none())
+            .unwrap_or(self.workspaces.len());
+        if index == self.workspaces.len() {
+            let wm = self.new_workspace(output);
+            self.workspaces.push(wm);
+        }
+        self.outputs.push(OutputWorkspace {
+            output,
+            workspace: index,
+        });
+        let position = self.outputs.len() - 1;
+        self.show_workspace(position, index);
+        Ok(())
+    }
+
+    /// The workspace shown on the removed output becomes hidden. When the focused output is
+    /// removed, the first output gets focused.
+    fn remove_output(&mut self, id: OutputId) -> Result<(), Self::Error> {
+        match self.outputs.iter().position(|o| o.output.id == id) {
+            None => Err(MultiWorkspaceError::UnknownOutput(id)),
+            Some(_) if self.outputs.len() == 1 => Err(MultiWorkspaceError::LastOutput),
+            Some(position) => {
+                self.outputs.remove(position);
+                if self.focused_output == id {
+                    self.focused_output = self.outputs[0].output.id;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn resize_output(&mut self, output: Output) -> Result<(), Self::Error> {
+        match self.outputs.iter().position(|o| o.output.id == output.id) {
+            None => Err(MultiWorkspaceError::UnknownOutput(output.id)),
+            Some(position) => {
+                self.outputs[position].output = output;
+                let workspace = self.outputs[position].workspace;
+                self.show_workspace(position, workspace);
+                Ok(())
+            }
+        }
+    }
+
+    fn focus_output(&mut self, id: OutputId) -> Result<(), Self::Error> {
+        match self.get_output_workspace(id) {
+            Err(error) => Err(error),
+            Ok(_) => {
+                self.focused_output = id;
+                Ok(())
+            }
+        }
+    }
+
+    fn get_output_layout(&self, id: OutputId) -> Result<WindowLayout, Self::Error> {
+        self.get_output_workspace(id).and_then(|o| self.get_output_workspace_layout(o))
+    }
+}
+
+impl<WM> MultiWorkspaces<WM>
+    where WM: MinimiseSupport + BorderSupport + WindowRuleSupport
+{
+    /// Move the window to the workspace at the given index. A new workspace is created when the
+    /// index equals the number of workspaces.
+    ///
+    /// The window keeps its float or tile state, its geometry, whether it is minimised and
+    /// whether it is urgent, the window rules are not applied to it again. On the target
+    /// workspace it gets the focus, unless it is minimised, then the focus is kept.
+    /// The window is added to the target before it is removed from the source, so when the move
+    /// fails the window stays where it was and no workspace is created.
+    pub fn move_window_to_workspace(&mut self,
+                                    window: Window,
+                                    index: WorkspaceIndex)
+                                    -> Result<(), MultiWorkspaceError<WM::Error>> {
+        let source = match self.get_window_workspace_index(window) {
+            None => return Err(MultiWorkspaceError::UnknownWindow(window)),
+            Some(source) => source,
+        };
+        if index == source {
+            return Ok(());
+        } else if index > self.workspaces.len() {
+            return Err(MultiWorkspaceError::WorkspaceIndexOutOfBound(index));
         }
+        let (info, minimised, urgent) = self.get_workspace(source).and_then(|wm| {
+            wm.get_window_info(window)
+                .map(|info| (info, wm.is_minimised(window), wm.is_urgent(window)))
+                .map_err(MultiWorkspaceError::WrappedError)
+        })?;
+        // a new workspace is only pushed once the window moved to it, so a failed move leaves no
+        // empty workspace behind
+        let mut new_workspace = None;
+        let added = if index == self.workspaces.len() {
+            let mut wm = self.new_workspace(self.get_current_output());
+            let added = add_moved_window(&mut wm, info, minimised, urgent);
+            new_workspace = Some(wm);
+            added
+        } else {
+            add_moved_window(&mut self.workspaces[index], info, minimised, urgent)
+        };
+        added.and_then(|_| match self.workspaces[source].remove_window(window) {
+                Ok(_) => Ok(()),
+                Err(error) => {
+                    // take the window back from the target, it stays on the source
+                    match new_workspace {
+                        Some(_) => Err(error),
+                        None => self.workspaces[index].remove_window(window).and(Err(error)),
+                    }
+                }
+            })
+            .map_err(MultiWorkspaceError::WrappedError)
+            .map(|_| self.workspaces.extend(new_workspace))
+    }
+
+    /// Move the window to the workspace at the given index and show that workspace.
+    ///
+    /// When the workspace is already shown on another output, that output gets focused instead.
+    pub fn move_window_to_workspace_and_follow(&mut self,
+                                               window: Window,
+                                               index: WorkspaceIndex)
+                                               -> Result<(), MultiWorkspaceError<WM::Error>> {
+        self.move_window_to_workspace(window, index)
+            .and_then(|_| match self.get_workspace_output(index) {
+                None => self.switch_workspace(index),
+                Some(output) => self.focus_output(output.id),
+            })
     }
 }
 
@@ -169,21 +621,28 @@ impl<WM: FloatSupport> FloatSupport for MultiWorkspaces<WM> {
     }
 
     fn toggle_floating(&mut self, window: Window) -> Result<(), Self::Error> {
-        self.get_current_workspace_mut()
+        self.get_window_workspace_mut(window)
             .and_then(|wm| {
                 wm.toggle_floating(window)
-                    .map_err(|_| MultiWorkspaceError::WrappedError)
+                    .map_err(MultiWorkspaceError::WrappedError)
             })
     }
 
+    /// Like in `get_window_info`, the geometry of a window on a hidden workspace is relative to
+    /// that workspace.
     fn set_window_geometry(&mut self,
                            window: Window,
                            new_geometry: Geometry)
                            -> Result<(), Self::Error> {
-        self.get_current_workspace_mut()
+        let new_geometry = match self.get_window_workspace_index(window)
+            .and_then(|index| self.get_workspace_output(index)) {
+            None => new_geometry,
+            Some(output) => to_local(&self.get_usable_output(output), new_geometry),
+        };
+        self.get_window_workspace_mut(window)
             .and_then(|wm| {
                 wm.set_window_geometry(window, new_geometry)
-                    .map_err(|_| MultiWorkspaceError::WrappedError)
+                    .map_err(MultiWorkspaceError::WrappedError)
             })
     }
 }
@@ -195,15 +654,60 @@ impl<WM: MinimiseSupport> MinimiseSupport for MultiWorkspaces<WM> {
             .unwrap_or(Vec::new())
     }
 
+    /// Unminimising a window on another workspace makes that workspace the current one, as the
+    /// window has to become visible.
     fn toggle_minimised(&mut self, window: Window) -> Result<(), Self::Error> {
-        self.get_current_workspace_mut()
+        if self.get_window_workspace(window).map(|wm| wm.is_minimised(window)).unwrap_or(false) {
+                self.show_window_workspace(window)
+            } else {
+                Ok(())
+            }
+            .and_then(|_| self.get_window_workspace_mut(window))
             .and_then(|wm| {
                 wm.toggle_minimised(window)
-                    .map_err(|_| MultiWorkspaceError::WrappedError)
+                    .map_err(MultiWorkspaceError::WrappedError)
             })
     }
 }
 
+/// The session is the one of the current workspace, it switches between the windows on it.
+impl<WM: WindowSwitcherSupport> WindowSwitcherSupport for MultiWorkspaces<WM> {
+    fn begin_switch(&mut self) {
+        match self.get_current_workspace_mut() {
+            Err(_) => {}
+            Ok(wm) => wm.begin_switch(),
+        }
+    }
+
+    fn step_switch(&mut self, dir: PrevOrNext) {
+        match self.get_current_workspace_mut() {
+            Err(_) => {}
+            Ok(wm) => wm.step_switch(dir),
+        }
+    }
+
+    fn get_switch_candidate(&self) -> Option<Window> {
+        match self.get_current_workspace() {
+            Err(_) => None,
+            Ok(wm) => wm.get_switch_candidate(),
+        }
+    }
+
+    fn commit_switch(&mut self) -> Result<(), Self::Error> {
+        self.get_current_workspace_mut().and_then(|wm| {
+            wm.commit_switch()
+                .map_err(MultiWorkspaceError::WrappedError)
+        })
+    }
+
+    fn cancel_switch(&mut self) {
+        match self.get_current_workspace_mut() {
+            Err(_) => {}
+            Ok(wm) => wm.cancel_switch(),
+        }
+    }
+}
+
 impl<WM: TilingSupport> TilingSupport for MultiWorkspaces<WM> {
     fn get_master_window(&self) -> Option<Window> {
         match self.get_current_workspace() {
@@ -212,11 +716,13 @@ impl<WM: TilingSupport> TilingSupport for MultiWorkspaces<WM> {
         }
     }
 
+    /// The workspace managing the window becomes the current one, as the master gets focused.
     fn swap_with_master(&mut self, window: Window) -> Result<(), Self::Error> {
-        self.get_current_workspace_mut()
+        self.show_window_workspace(window)
+            .and_then(|_| self.get_current_workspace_mut())
             .and_then(|wm| {
                 wm.swap_with_master(window)
-                    .map_err(|_| MultiWorkspaceError::WrappedError)
+                    .map_err(MultiWorkspaceError::WrappedError)
             })
     }
 
@@ -226,19 +732,308 @@ impl<WM: TilingSupport> TilingSupport for MultiWorkspaces<WM> {
             Ok(wm) => wm.swap_windows(dir),
         }
     }
+
+    /// Swap within the current workspace, windows only swap with windows on the same workspace.
+    fn swap_direction(&mut self, dir: Direction, wrap: bool) -> Result<(), Self::Error> {
+        self.get_current_workspace_mut().and_then(|wm| {
+            wm.swap_direction(dir, wrap)
+                .map_err(MultiWorkspaceError::WrappedError)
+        })
+    }
+}
+
+/// The message is sent to the layout of the current workspace.
+impl<WM: LayoutMessageSupport> LayoutMessageSupport for MultiWorkspaces<WM> {
+    fn send_layout_message(&mut self, message: LayoutMessage) -> bool {
+        self.get_current_workspace_mut().map(|wm| wm.send_layout_message(message)).unwrap_or(false)
+    }
+}
+
+/// The tabs of the workspaces shown on every output, relative to the origin like the windows in
+/// the window layout.
+impl<WM: TabSupport> TabSupport for MultiWorkspaces<WM> {
+    fn get_tabs(&self) -> Vec<Tab> {
+        let mut tabs = Vec::new();
+        for output_workspace in &self.outputs {
+            match self.get_workspace(output_workspace.workspace) {
+                Err(_) => {}
+                Ok(wm) => {
+                    tabs.extend(wm.get_tabs().into_iter().map(|tab| {
+                        let output = self.get_usable_output(output_workspace.output);
+                        Tab { geometry: to_global(&output, tab.geometry), ..tab }
+                    }))
+                }
+            }
+        }
+        tabs
+    }
+}
+
+/// The master area of the current workspace is adjusted.
+impl<WM: WindowManager + MasterTrait> MasterTrait for MultiWorkspaces<WM> {
+    fn get_master_ratio(&self) -> u32 {
+        self.get_current_workspace().map(|wm| wm.get_master_ratio()).unwrap_or(0)
+    }
+
+    fn get_master_count(&self) -> usize {
+        self.get_current_workspace().map(|wm| wm.get_master_count()).unwrap_or(0)
+    }
+
+    fn shrink_master(&mut self) {
+        match self.get_current_workspace_mut() {
+            Err(_) => {}
+            Ok(wm) => wm.shrink_master(),
+        }
+    }
+
+    fn expand_master(&mut self) {
+        match self.get_current_workspace_mut() {
+            Err(_) => {}
+            Ok(wm) => wm.expand_master(),
+        }
+    }
+
+    fn inc_master(&mut self) {
+        match self.get_current_workspace_mut() {
+            Err(_) => {}
+            Ok(wm) => wm.inc_master(),
+        }
+    }
+
+    fn dec_master(&mut self) {
+        match self.get_current_workspace_mut() {
+            Err(_) => {}
+            Ok(wm) => wm.dec_master(),
+        }
+    }
+}
+
+/// Every workspace has its own gap, only the gap of the current workspace is changed.
+impl<WM: GapSupport> GapSupport for MultiWorkspaces<WM> {
+    fn get_gap(&self) -> GapSize {
+        self.get_current_workspace().map(|wm| wm.get_gap()).unwrap_or(0)
+    }
+
+    fn set_gap(&mut self, gap: GapSize) {
+        match self.get_current_workspace_mut() {
+            Err(_) => {}
+            Ok(wm) => wm.set_gap(gap),
+        }
+    }
+}
+
+/// Every workspace follows the same policies, so they are set on all of them. The template keeps
+/// them for the workspaces created later.
+impl<WM: FocusPolicySupport> FocusPolicySupport for MultiWorkspaces<WM> {
+    fn get_focus_policy(&self) -> FocusPolicy {
+        self.template.get_focus_policy()
+    }
+
+    fn set_focus_policy(&mut self, policy: FocusPolicy) {
+        self.template.set_focus_policy(policy);
+        for wm in self.workspaces.iter_mut() {
+            wm.set_focus_policy(policy);
+        }
+    }
+
+    fn get_removal_focus(&self) -> RemovalFocus {
+        self.template.get_removal_focus()
+    }
+
+    fn set_removal_focus(&mut self, removal_focus: RemovalFocus) {
+        self.template.set_removal_focus(removal_focus);
+        for wm in self.workspaces.iter_mut() {
+            wm.set_removal_focus(removal_focus);
+        }
+    }
+}
+
+/// The border width and colours are those of the current workspace, like the gap. The windows
+/// keep their urgency when moving to another workspace.
+impl<WM: BorderSupport> BorderSupport for MultiWorkspaces<WM> {
+    fn get_border_width(&self) -> BorderWidth {
+        self.get_current_workspace().map(|wm| wm.get_border_width()).unwrap_or(0)
+    }
+
+    fn set_border_width(&mut self, width: BorderWidth) {
+        match self.get_current_workspace_mut() {
+            Err(_) => {}
+            Ok(wm) => wm.set_border_width(width),
+        }
+    }
+
+    fn get_border_colours(&self) -> BorderColours {
+        self.get_current_workspace()
+            .map(|wm| wm.get_border_colours())
+            .unwrap_or_else(|_| BorderManager::new().colours)
+    }
+
+    fn set_border_colours(&mut self, colours: BorderColours) {
+        match self.get_current_workspace_mut() {
+            Err(_) => {}
+            Ok(wm) => wm.set_border_colours(colours),
+        }
+    }
+
+    fn is_urgent(&self, window: Window) -> bool {
+        self.get_window_workspace(window).map(|wm| wm.is_urgent(window)).unwrap_or(false)
+    }
+
+    fn set_urgent(&mut self, window: Window, urgent: bool) -> Result<(), Self::Error> {
+        self.get_window_workspace_mut(window).and_then(|wm| {
+            wm.set_urgent(window, urgent)
+                .map_err(MultiWorkspaceError::WrappedError)
+        })
+    }
+
+    /// The bordered windows of every output, like `get_window_layout`. Only the focused output
+    /// shows a focused window.
+    fn get_bordered_window_layout(&self) -> BorderedWindowLayout {
+        let mut windows = Vec::new();
+        let (focused, others): (Vec<&OutputWorkspace>, Vec<&OutputWorkspace>) =
+            self.outputs.iter().partition(|o| o.output.id == self.focused_output);
+        for output_workspace in others.into_iter().chain(focused) {
+            let output = self.get_usable_output(output_workspace.output);
+            match self.get_workspace(output_workspace.workspace) {
+                Err(_) => {}
+                Ok(wm) => {
+                    let colours = wm.get_border_colours();
+                    windows.extend(wm.get_bordered_window_layout().windows.into_iter().map(|b| {
+                        let state = match b.state {
+                            BorderState::Focused if output.id != self.focused_output => {
+                                if wm.is_urgent(b.window) {
+                                    BorderState::Urgent
+                                } else {
+                                    BorderState::Unfocused
+                                }
+                            }
+                            state => state,
+                        };
+                        BorderedWindow {
+                            geometry: to_global(&output, b.geometry),
+                            state,
+                            colour: colours.get_colour(state),
+                            ..b
+                        }
+                    }))
+                }
+            }
+        }
+        BorderedWindowLayout {
+            focused_window: self.get_focused_window(),
+            windows,
+        }
+    }
+}
+
+/// The usable area is the one of the focused output, relative to the origin like the windows.
+impl<WM: WindowManager> StrutSupport for MultiWorkspaces<WM> {
+    fn get_usable_area(&self) -> Geometry {
+        self.get_focused_output_workspace()
+            .map(|o| self.get_usable_output(o.output).to_geometry())
+            .unwrap_or(self.get_screen().to_geometry())
+    }
+
+    fn get_static_strut(&self) -> Strut {
+        self.strut_manager.static_strut
+    }
+
+    fn set_static_strut(&mut self, strut: Strut) {
+        self.strut_manager.static_strut = strut;
+        self.apply_struts()
+    }
+
+    fn get_window_strut(&self, window: Window) -> Option<Strut> {
+        self.strut_manager.get_window_strut(window)
+    }
+
+    fn set_window_strut(&mut self, window: Window, strut: Strut) {
+        self.strut_manager.set_window_strut(window, strut);
+        self.apply_struts()
+    }
+
+    fn remove_window_strut(&mut self, window: Window) {
+        if self.strut_manager.get_window_strut(window).is_some() {
+            self.strut_manager.remove_window_strut(window);
+            self.apply_struts()
+        }
+    }
 }
 
+/// The rules are shared by all workspaces, so they are set on all of them and on the template. A
+/// window sent to another workspace by the rules does not make it the current one, and the focus
+/// stays on the current workspace.
+impl<WM: WindowRuleSupport> WindowRuleSupport for MultiWorkspaces<WM> {
+    fn get_window_rules(&self) -> &WindowRules {
+        &self.window_rules
+    }
+
+    fn set_window_rules(&mut self, rules: WindowRules) {
+        self.template.set_window_rules(rules.clone());
+        for wm in self.workspaces.iter_mut() {
+            wm.set_window_rules(rules.clone());
+        }
+        self.window_rules = rules;
+    }
+
+    /// The window is added to the current workspace.
+    fn add_window_without_rules(&mut self,
+                                window_with_info: WindowWithInfo)
+                                -> Result<(), Self::Error> {
+        if self.is_managed(window_with_info.window) {
+            return Err(MultiWorkspaceError::AlReadyManagedWindow(window_with_info.window));
+        }
+        let index = self.get_current_workspace_index();
+        let window_with_info = self.to_workspace_local(index, window_with_info);
+        self.get_workspace_mut(index).and_then(|wm| {
+            wm.add_window_without_rules(window_with_info)
+                .map_err(MultiWorkspaceError::WrappedError)
+        })
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use wm_common::tests::window_manager;
+    use wm_common::tests::layout_message_support;
+    use wm_common::tests::tab_support;
     use wm_common::tests::tiling_support;
+    use wm_common::tests::size_hint_support;
+    use wm_common::tests::direction_support;
+    use wm_common::tests::focus_policy_support;
+    use wm_common::tests::strut_support;
+    use wm_common::tests::window_switcher_support;
     use wm_common::tests::float_support;
     use wm_common::tests::float_and_tile_support;
     use wm_common::tests::minimise_support;
+    use wm_common::tests::multi_output_support;
+    use wm_common::tests::master_support;
+    use wm_common::tests::gap_support;
+    use wm_common::tests::border_support;
     use super::MultiWorkspaces;
     use d_minimising_windows::MinimiseWM;
-    use b_tiling_wm::VerticalLayout;
+    use rules::{PropertyPattern, RuleAction, WindowMatcher, WindowRules};
+    use wm_common::WindowRuleSupport;
+    use b_tiling_wm::{TilingWM, VerticalLayout};
+    use f_gaps::GapLayout;
+    use cplwm_api::wm::*;
+    use cplwm_api::types::*;
+    use wm_common::error::{FloatWMError, MultiWorkspaceError};
+    use std::error::Error;
+    use a_fullscreen_wm::{FocusPolicy, RemovalFocus};
+    use wm_common::FocusPolicySupport;
+
+    static SCREEN: Screen = Screen {
+        width: 800,
+        height: 600,
+    };
+
+    static SOME_GEOM: Geometry = Geometry {
+        x: 10,
+        y: 10,
+        width: 100,
+        height: 100,
+    };
 
     #[test]
     fn test_empty_tiling_wm() {
@@ -289,13 +1084,95 @@ mod tests {
     #[test]
     fn test_swap_windows() {
         tiling_support::test_swap_windows::<MultiWorkspaces<MinimiseWM>,
-                                            VerticalLayout>(VerticalLayout {});
+                                            VerticalLayout>(VerticalLayout::new());
     }
 
     #[test]
     fn test_tiling_layout() {
         tiling_support::test_get_window_info::<MultiWorkspaces<MinimiseWM>,
-                                               VerticalLayout>(VerticalLayout {});
+                                               VerticalLayout>(VerticalLayout::new());
+    }
+
+    #[test]
+    fn test_tiled_size_hints() {
+        size_hint_support::test_tiled_size_hints::<MultiWorkspaces<MinimiseWM>>();
+    }
+
+    #[test]
+    fn test_tiles_in_usable_area() {
+        strut_support::test_tiles_in_usable_area::<MultiWorkspaces<MinimiseWM>>();
+    }
+
+    #[test]
+    fn test_remove_window_with_strut() {
+        strut_support::test_remove_window_with_strut::<MultiWorkspaces<MinimiseWM>>();
+    }
+
+    #[test]
+    fn test_floating_window_ignores_struts() {
+        strut_support::test_floating_window_ignores_struts::<MultiWorkspaces<MinimiseWM>>();
+    }
+
+    #[test]
+    fn test_focus_direction() {
+        direction_support::test_focus_direction::<MultiWorkspaces<MinimiseWM>>();
+    }
+
+    #[test]
+    fn test_swap_direction() {
+        direction_support::test_swap_direction::<MultiWorkspaces<MinimiseWM>>();
+    }
+
+    #[test]
+    fn test_direction_with_floats() {
+        direction_support::test_direction_with_floats::<MultiWorkspaces<MinimiseWM>>();
+    }
+
+    #[test]
+    fn test_stack_order() {
+        focus_policy_support::test_stack_order::<MultiWorkspaces<MinimiseWM>>();
+    }
+
+    #[test]
+    fn test_most_recently_used() {
+        focus_policy_support::test_most_recently_used::<MultiWorkspaces<MinimiseWM>>();
+    }
+
+    #[test]
+    fn test_reading_order() {
+        focus_policy_support::test_reading_order::<MultiWorkspaces<MinimiseWM>>();
+    }
+
+    #[test]
+    fn test_new_workspaces_follow_the_policies() {
+        let mut wm = MultiWorkspaces::<MinimiseWM>::new(SCREEN);
+        wm.set_focus_policy(FocusPolicy::ReadingOrder);
+        wm.set_removal_focus(RemovalFocus::Master);
+        assert!(wm.add_output(Output::new(1, 800, 0, SCREEN)).is_ok());
+        assert!(wm.switch_workspace(2).is_ok());
+
+        for index in 0..3 {
+            let workspace = wm.get_workspace(index).unwrap();
+            assert_eq!(FocusPolicy::ReadingOrder, workspace.get_focus_policy());
+            assert_eq!(RemovalFocus::Master, workspace.get_removal_focus());
+        }
+        assert_eq!(FocusPolicy::ReadingOrder, wm.get_focus_policy());
+        assert_eq!(RemovalFocus::Master, wm.get_removal_focus());
+    }
+
+    #[test]
+    fn test_removal_focus() {
+        focus_policy_support::test_removal_focus::<MultiWorkspaces<MinimiseWM>>();
+    }
+
+    #[test]
+    fn test_switch_commit() {
+        window_switcher_support::test_switch_commit::<MultiWorkspaces<MinimiseWM>>();
+    }
+
+    #[test]
+    fn test_switch_cancel() {
+        window_switcher_support::test_switch_cancel::<MultiWorkspaces<MinimiseWM>>();
     }
 
     #[test]
@@ -313,6 +1190,11 @@ mod tests {
         float_support::test_set_window_geometry::<MultiWorkspaces<MinimiseWM>>();
     }
 
+    #[test]
+    fn test_floating_size_hints() {
+        size_hint_support::test_floating_size_hints::<MultiWorkspaces<MinimiseWM>>();
+    }
+
     #[test]
     fn test_window_layout_order() {
         float_support::test_window_layout_order::<MultiWorkspaces<MinimiseWM>>();
@@ -378,5 +1260,564 @@ mod tests {
         minimise_support::test_minimise_state_after_cycle_focus::<MultiWorkspaces<MinimiseWM>>();
     }
 
+    #[test]
+    fn test_set_border_width() {
+        border_support::test_set_border_width::<MultiWorkspaces<MinimiseWM>>();
+    }
+
+    #[test]
+    fn test_floating_window_border() {
+        border_support::test_floating_window_border::<MultiWorkspaces<MinimiseWM>>();
+    }
+
+    #[test]
+    fn test_border_states() {
+        border_support::test_border_states::<MultiWorkspaces<MinimiseWM>>();
+    }
+
+    #[test]
+    fn test_set_gap() {
+        let layout = GapLayout::new(VerticalLayout::new(), 0);
+        gap_support::test_set_gap::<MultiWorkspaces<MinimiseWM>, GapLayout<VerticalLayout>>(layout);
+    }
+
+    #[test]
+    fn test_gap_ignores_floating_windows() {
+        gap_support::test_gap_ignores_floating_windows::<MultiWorkspaces<MinimiseWM>>();
+    }
+
+    #[test]
+    fn test_shrink_and_expand_master() {
+        master_support::test_shrink_and_expand_master::<MultiWorkspaces<MinimiseWM>>();
+    }
+
+    #[test]
+    fn test_inc_and_dec_master() {
+        master_support::test_inc_and_dec_master::<MultiWorkspaces<MinimiseWM>>();
+    }
+
+    #[test]
+    fn test_add_and_remove_outputs() {
+        multi_output_support::test_add_and_remove_outputs::<MultiWorkspaces<MinimiseWM>>();
+    }
+
+    #[test]
+    fn test_output_layout() {
+        multi_output_support::test_output_layout::<MultiWorkspaces<MinimiseWM>>();
+    }
+
+    #[test]
+    fn test_focus_moves_between_outputs() {
+        multi_output_support::test_focus_moves_between_outputs::<MultiWorkspaces<MinimiseWM>>();
+    }
+
+    #[test]
+    fn test_workspace_per_output() {
+        multi_output_support::test_workspace_per_output::<MinimiseWM,
+                                                          MultiWorkspaces<MinimiseWM>>();
+    }
+
+    #[test]
+    fn test_struts_on_every_output() {
+        let mut wm = MultiWorkspaces::<MinimiseWM>::new(SCREEN);
+        assert!(wm.add_output(Output::new(1, 800, 0, SCREEN)).is_ok());
+        wm.set_static_strut(Strut::new(20, 0, 0, 0));
+        assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+        assert!(wm.focus_output(1).is_ok());
+        assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+
+        let usable_area = Geometry {
+            x: 800,
+            y: 20,
+            width: 800,
+            height: 580,
+        };
+        assert_eq!(usable_area, wm.get_usable_area());
+        assert_eq!(usable_area, wm.get_window_info(2).unwrap().geometry);
+        assert_eq!(Geometry { x: 0, ..usable_area },
+                   wm.get_window_info(1).unwrap().geometry);
+
+        // a hidden workspace is shown in the usable area as well
+        assert!(wm.move_window_to_workspace_and_follow(2, 2).is_ok());
+        assert_eq!(usable_area, wm.get_window_info(2).unwrap().geometry);
+    }
+
+    #[test]
+    fn test_move_window_to_workspace() {
+        let mut wm = MultiWorkspaces::<MinimiseWM>::new(SCREEN);
+        assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+        assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+        assert_eq!(Some(2), wm.get_focused_window());
+
+        assert!(wm.move_window_to_workspace(2, 1).is_ok());
+        assert_eq!(0, wm.get_current_workspace_index());
+        assert_eq!(vec![1], wm.get_workspace(0).unwrap().get_windows());
+        assert_eq!(vec![1, 2], wm.get_windows());
+        assert_eq!(Some(1), wm.get_focused_window());
+        assert_eq!(Some(1), wm.get_window_workspace_index(2));
+
+        let target = wm.get_workspace(1).unwrap();
+        assert_eq!(vec![2], target.get_windows());
+        assert_eq!(Some(2), target.get_focused_window());
+        assert_eq!(SCREEN.to_geometry(), target.get_window_info(2).unwrap().geometry);
+
+        // moving to the workspace the window is on does nothing
+        assert!(wm.move_window_to_workspace(1, 0).is_ok());
+        assert_eq!(vec![1], wm.get_workspace(0).unwrap().get_windows());
+
+        assert!(wm.move_window_to_workspace(3, 1).is_err());
+        assert!(wm.move_window_to_workspace(1, 5).is_err());
+        assert_eq!(Some(0), wm.get_window_workspace_index(1));
+    }
 
+    // a failed move leaves the window where it was and creates no workspace
+    #[test]
+    fn test_move_window_failures() {
+        let mut wm = MultiWorkspaces::<MinimiseWM>::new(SCREEN);
+        assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+
+        assert!(wm.move_window_to_workspace(1, 2).is_err());
+        assert!(wm.move_window_to_workspace(2, 1).is_err());
+        assert_eq!(1, wm.workspaces.len());
+
+        // the target workspace can not add the window, as it already has a window with that id
+        assert!(wm.switch_workspace(1).is_ok());
+        assert!(wm.get_workspace_mut(1)
+            .unwrap()
+            .add_window(WindowWithInfo::new_tiled(1, SOME_GEOM))
+            .is_ok());
+        assert!(wm.switch_workspace(0).is_ok());
+        assert!(wm.move_window_to_workspace(1, 1).is_err());
+        assert_eq!(2, wm.workspaces.len());
+        assert_eq!(vec![1], wm.get_workspace(0).unwrap().get_windows());
+        assert_eq!(Some(0), wm.get_window_workspace_index(1));
+        assert_eq!(SCREEN.to_geometry(), wm.get_window_info(1).unwrap().geometry);
+    }
+
+    #[test]
+    fn test_move_window_keeps_state() {
+        let mut wm = MultiWorkspaces::<MinimiseWM>::new(SCREEN);
+        assert!(wm.add_window(WindowWithInfo::new_float(1, SOME_GEOM)).is_ok());
+        assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+        assert!(wm.add_window(WindowWithInfo::new_float(3, SOME_GEOM)).is_ok());
+        assert!(wm.toggle_minimised(3).is_ok());
+        assert!(wm.set_urgent(1, true).is_ok());
+
+        assert!(wm.move_window_to_workspace(1, 1).is_ok());
+        assert!(wm.move_window_to_workspace(3, 1).is_ok());
+        assert!(wm.is_urgent(1));
+        assert!(!wm.is_urgent(3));
+
+        let target = wm.get_workspace(1).unwrap();
+        assert!(target.is_floating(1));
+        assert!(target.is_urgent(1));
+        assert_eq!(SOME_GEOM, target.get_window_info(1).unwrap().geometry);
+        assert!(target.is_minimised(3));
+        assert_eq!(FloatOrTile::Float,
+                   target.get_window_info(3).unwrap().float_or_tile);
+        assert_eq!(Some(1), target.get_focused_window());
+        assert_eq!(Vec::<Window>::new(), wm.get_minimised_windows());
+    }
+
+    #[test]
+    fn test_move_window_to_new_workspace_on_other_output() {
+        let mut wm = MultiWorkspaces::<MinimiseWM>::new(SCREEN);
+        let small = Screen {
+            width: 400,
+            height: 300,
+        };
+        assert!(wm.add_output(Output::new(1, 800, 0, small)).is_ok());
+        wm.set_static_strut(Strut::new(20, 0, 0, 0));
+        assert!(wm.focus_output(1).is_ok());
+        assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+
+        // the new workspace gets the usable part of the focused output
+        assert!(wm.move_window_to_workspace(1, 2).is_ok());
+        let usable_screen = Screen {
+            width: 400,
+            height: 280,
+        };
+        assert_eq!(usable_screen, wm.get_workspace(2).unwrap().get_screen());
+        assert_eq!(usable_screen.to_geometry(),
+                   wm.get_workspace(2).unwrap().get_window_info(1).unwrap().geometry);
+    }
+
+    #[test]
+    fn test_move_window_to_workspace_and_follow() {
+        let mut wm = MultiWorkspaces::<MinimiseWM>::new(SCREEN);
+        assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+        assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+
+        assert!(wm.move_window_to_workspace_and_follow(1, 1).is_ok());
+        assert_eq!(1, wm.get_current_workspace_index());
+        assert_eq!(Some(1), wm.get_focused_window());
+        assert_eq!(Some(2), wm.get_workspace(0).unwrap().get_focused_window());
+
+        // the target workspace is shown on another output
+        assert!(wm.add_output(Output::new(1, 800, 0, SCREEN)).is_ok());
+        assert_eq!(0, wm.get_output_workspace_index(1).unwrap());
+        assert!(wm.move_window_to_workspace_and_follow(1, 0).is_ok());
+        assert_eq!(1, wm.get_focused_output());
+        assert_eq!(0, wm.get_current_workspace_index());
+        assert_eq!(Some(1), wm.get_focused_window());
+    }
+
+    #[test]
+    fn test_windows_of_all_workspaces() {
+        let mut wm = MultiWorkspaces::<MinimiseWM>::new(SCREEN);
+        assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+        assert!(wm.switch_workspace(1).is_ok());
+        assert!(wm.add_window(WindowWithInfo::new_float(2, SOME_GEOM)).is_ok());
+
+        assert_eq!(vec![1, 2], wm.get_windows());
+        assert!(wm.is_managed(1));
+        assert_eq!(SCREEN.to_geometry(), wm.get_window_info(1).unwrap().geometry);
+        assert_eq!(None, wm.get_window_layout().windows.iter().find(|&&(w, _)| w == 1));
+
+        // a window on another workspace can not be added again
+        assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_err());
+        assert_eq!(vec![2], wm.get_workspace(1).unwrap().get_windows());
+
+        assert!(wm.remove_window(1).is_ok());
+        assert!(!wm.is_managed(1));
+        assert!(wm.get_window_info(1).is_err());
+        assert!(wm.remove_window(1).is_err());
+        assert_eq!(vec![2], wm.get_windows());
+        assert_eq!(1, wm.get_current_workspace_index());
+    }
+
+    #[test]
+    fn test_focus_window_switches_workspace() {
+        let mut wm = MultiWorkspaces::<MinimiseWM>::new(SCREEN);
+        assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+        assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+        assert!(wm.switch_workspace(1).is_ok());
+        assert!(wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).is_ok());
+
+        assert!(wm.focus_window(Some(1)).is_ok());
+        assert_eq!(0, wm.get_current_workspace_index());
+        assert_eq!(Some(1), wm.get_focused_window());
+
+        assert!(wm.swap_with_master(3).is_ok());
+        assert_eq!(1, wm.get_current_workspace_index());
+        assert_eq!(Some(3), wm.get_master_window());
+        assert_eq!(Some(3), wm.get_focused_window());
+
+        assert!(wm.focus_window(Some(4)).is_err());
+        assert_eq!(1, wm.get_current_workspace_index());
+    }
+
+    #[test]
+    fn test_gap_per_workspace() {
+        let mut wm = MultiWorkspaces::<MinimiseWM>::new(SCREEN);
+        assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+        wm.set_gap(5);
+
+        // a new workspace starts without gap and changing it leaves the other workspace alone
+        assert!(wm.switch_workspace(1).is_ok());
+        assert_eq!(0, wm.get_gap());
+        assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 800,
+                       height: 600,
+                   },
+                   wm.get_window_info(2).unwrap().geometry);
+        wm.set_gap(10);
+        assert_eq!(5, wm.get_workspace(0).unwrap().get_gap());
+
+        assert!(wm.switch_workspace(0).is_ok());
+        assert_eq!(5, wm.get_gap());
+        assert_eq!(Geometry {
+                       x: 5,
+                       y: 5,
+                       width: 790,
+                       height: 590,
+                   },
+                   wm.get_window_info(1).unwrap().geometry);
+    }
+
+    #[test]
+    fn test_window_actions_on_other_workspace() {
+        let mut wm = MultiWorkspaces::<MinimiseWM>::new(SCREEN);
+        assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+        assert!(wm.switch_workspace(1).is_ok());
+
+        assert!(wm.toggle_floating(1).is_ok());
+        assert!(wm.set_window_geometry(1, SOME_GEOM).is_ok());
+        assert!(wm.toggle_minimised(1).is_ok());
+        assert_eq!(1, wm.get_current_workspace_index());
+        assert!(wm.get_workspace(0).unwrap().is_minimised(1));
+
+        // unminimising makes the window visible, so its workspace is shown
+        assert!(wm.toggle_minimised(1).is_ok());
+        assert_eq!(0, wm.get_current_workspace_index());
+        assert!(wm.is_floating(1));
+        assert_eq!(SOME_GEOM, wm.get_window_info(1).unwrap().geometry);
+    }
+
+    #[test]
+    fn test_wrapped_error_display_and_source() {
+        let mut wm = MultiWorkspaces::<MinimiseWM>::new(SCREEN);
+        assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+        let error = wm.set_window_geometry(1, SOME_GEOM).unwrap_err();
+        match error {
+            MultiWorkspaceError::WrappedError(FloatWMError::NotFloatingWindow(1)) => {}
+            _ => panic!("expected a wrapped NotFloatingWindow error"),
+        }
+        assert_eq!("Error in workspace: Not floating window: 1", format!("{}", error));
+        assert_eq!("Not floating window: 1",
+                   format!("{}", error.source().unwrap()));
+
+        let error = wm.remove_window(2).unwrap_err();
+        assert_eq!("Unknown window: 2", format!("{}", error));
+        assert!(error.source().is_none());
+    }
+
+    #[test]
+    fn test_workspace_index_out_of_bound() {
+        let mut wm = MultiWorkspaces::<MinimiseWM>::new(SCREEN);
+        assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+        // the index one past the last workspace is a new workspace, beyond that is out of bound
+        let results: Vec<(&str, Result<(), MultiWorkspaceError<FloatWMError>>)> =
+            vec![("get_workspace", wm.get_workspace(5).map(|_| ())),
+                 ("get_workspace_mut", wm.get_workspace_mut(5).map(|_| ())),
+                 ("switch_workspace", wm.switch_workspace(5)),
+                 ("move_window_to_workspace", wm.move_window_to_workspace(1, 5))];
+        for (name, result) in results {
+            match result {
+                Err(MultiWorkspaceError::WorkspaceIndexOutOfBound(5)) => {}
+                _ => panic!("expected a WorkspaceIndexOutOfBound error from {}", name),
+            }
+        }
+        let error = wm.switch_workspace(5).unwrap_err();
+        assert_eq!("Index is out of bound 5", format!("{}", error));
+        assert!(error.source().is_none());
+        // nothing changed
+        assert_eq!(0, wm.get_current_workspace_index());
+        assert_eq!(vec![1], wm.get_windows());
+        assert!(wm.get_workspace(1).is_err());
+    }
+
+    #[test]
+    fn test_output_errors() {
+        let mut wm = MultiWorkspaces::<MinimiseWM>::new(SCREEN);
+        assert!(wm.add_output(Output::new(1, 800, 0, SCREEN)).is_ok());
+        let results: Vec<(&str, Result<(), MultiWorkspaceError<FloatWMError>>)> =
+            vec![("focus_output", wm.focus_output(7)),
+                 ("remove_output", wm.remove_output(7)),
+                 ("resize_output", wm.resize_output(Output::new(7, 0, 0, SCREEN))),
+                 ("get_output_layout", wm.get_output_layout(7).map(|_| ()))];
+        for (name, result) in results {
+            match result {
+                Err(MultiWorkspaceError::UnknownOutput(7)) => {}
+                _ => panic!("expected an UnknownOutput error from {}", name),
+            }
+        }
+        let error = wm.remove_output(7).unwrap_err();
+        assert_eq!("Unknown output: 7", format!("{}", error));
+        assert!(error.source().is_none());
+
+        let error = wm.add_output(Output::new(1, 0, 600, SCREEN)).unwrap_err();
+        match error {
+            MultiWorkspaceError::AlreadyKnownOutput(1) => {}
+            _ => panic!("expected an AlreadyKnownOutput error"),
+        }
+        assert_eq!("Already known output: 1", format!("{}", error));
+        assert!(error.source().is_none());
+        // the known output is not moved
+        assert_eq!(2, wm.get_outputs().len());
+        assert_eq!(800, wm.get_outputs()[1].x);
+
+        assert!(wm.remove_output(0).is_ok());
+        let error = wm.remove_output(1).unwrap_err();
+        match error {
+            MultiWorkspaceError::LastOutput => {}
+            _ => panic!("expected a LastOutput error"),
+        }
+        assert_eq!("Can not remove the last output!", format!("{}", error));
+        assert!(error.source().is_none());
+    }
+
+    #[test]
+    fn test_wrapped_error_of_hidden_workspace() {
+        let mut wm = MultiWorkspaces::<MinimiseWM>::new(SCREEN);
+        assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+        assert!(wm.switch_workspace(1).is_ok());
+        // the window is on the hidden workspace, its error is wrapped as is
+        let inner = wm.get_workspace_mut(0).unwrap().set_window_geometry(1, SOME_GEOM).unwrap_err();
+        let error = wm.set_window_geometry(1, SOME_GEOM).unwrap_err();
+        match error {
+            MultiWorkspaceError::WrappedError(FloatWMError::NotFloatingWindow(1)) => {}
+            _ => panic!("expected a wrapped NotFloatingWindow error"),
+        }
+        assert_eq!(format!("{}", inner), format!("{}", error.source().unwrap()));
+        assert_eq!(format!("Error in workspace: {}", inner), format!("{}", error));
+        assert_eq!(1, wm.get_current_workspace_index());
+    }
+
+    #[test]
+    fn test_errors_for_unknown_window() {
+        let mut wm = MultiWorkspaces::<MinimiseWM>::new(SCREEN);
+        assert!(wm.add_window(WindowWithInfo::new_float(1, SOME_GEOM)).is_ok());
+        let results: Vec<(&str, Result<(), MultiWorkspaceError<FloatWMError>>)> =
+            vec![("remove_window", wm.remove_window(2)),
+                 ("focus_window", wm.focus_window(Some(2))),
+                 ("get_window_info", wm.get_window_info(2).map(|_| ())),
+                 ("toggle_floating", wm.toggle_floating(2)),
+                 ("set_window_geometry", wm.set_window_geometry(2, SOME_GEOM)),
+                 ("toggle_minimised", wm.toggle_minimised(2)),
+                 ("swap_with_master", wm.swap_with_master(2)),
+                 ("move_window_to_workspace", wm.move_window_to_workspace(2, 1))];
+        for (name, result) in results {
+            match result {
+                Err(MultiWorkspaceError::UnknownWindow(2)) => {}
+                _ => panic!("expected an UnknownWindow error from {}", name),
+            }
+        }
+        assert_eq!(vec![1], wm.get_windows());
+        assert_eq!(Some(1), wm.get_focused_window());
+    }
+
+    #[test]
+    fn test_window_added_to_workspace_is_managed() {
+        let mut wm = MultiWorkspaces::<MinimiseWM>::new(SCREEN);
+        assert!(wm.get_workspace_mut(0)
+            .unwrap()
+            .add_window(WindowWithInfo::new_tiled(1, SOME_GEOM))
+            .is_ok());
+        assert!(wm.is_managed(1));
+        assert_eq!(vec![1], wm.get_windows());
+        assert_eq!(Some(0), wm.get_window_workspace_index(1));
+        assert!(wm.get_window_info(1).is_ok());
+        match wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)) {
+            Err(MultiWorkspaceError::AlReadyManagedWindow(1)) => {}
+            _ => panic!("expected an AlReadyManagedWindow error"),
+        }
+
+        assert!(wm.get_workspace_mut(0).unwrap().remove_window(1).is_ok());
+        assert!(!wm.is_managed(1));
+        assert_eq!(None, wm.get_window_workspace_index(1));
+        assert!(wm.get_window_info(1).is_err());
+    }
+
+    #[test]
+    fn test_master_messages() {
+        layout_message_support::test_master_messages::<MultiWorkspaces<MinimiseWM>>();
+    }
+
+    #[test]
+    fn test_no_tabs_without_tab_bar() {
+        tab_support::test_no_tabs_without_tab_bar::<MultiWorkspaces<MinimiseWM>>();
+    }
+
+    fn wm_with_rules() -> MultiWorkspaces<MinimiseWM> {
+        let mut wm = MultiWorkspaces::<MinimiseWM>::new(SCREEN);
+        wm.set_window_rules(WindowRules::new()
+            .with_rule(WindowMatcher::any()
+                           .with_class(PropertyPattern::Equals("Pavucontrol".to_string())),
+                       vec![RuleAction::Geometry(SOME_GEOM), RuleAction::NoFocus])
+            .with_rule(WindowMatcher::any().with_role(PropertyPattern::Equals("browser"
+                           .to_string())),
+                       vec![RuleAction::Workspace(1)])
+            .with_rule(WindowMatcher::any()
+                           .with_title(PropertyPattern::Contains("Downloads".to_string())),
+                       vec![RuleAction::Minimise]));
+        assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+        wm
+    }
+
+    #[test]
+    fn test_window_rules_float_without_focus() {
+        let mut wm = wm_with_rules();
+        let pavucontrol = WindowProperties::new().with_class("Pavucontrol", "pavucontrol");
+
+        assert!(wm.add_window(WindowWithInfo::new_tiled(2, SCREEN.to_geometry())
+                .with_properties(pavucontrol))
+            .is_ok());
+        assert!(wm.is_floating(2));
+        assert_eq!(SOME_GEOM, wm.get_window_info(2).unwrap().geometry);
+        assert_eq!(Some(1), wm.get_focused_window());
+
+        // without properties, no rule matches
+        assert!(wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).is_ok());
+        assert!(!wm.is_floating(3));
+        assert_eq!(Some(3), wm.get_focused_window());
+    }
+
+    #[test]
+    fn test_window_rules_workspace_and_minimise() {
+        let mut wm = wm_with_rules();
+        let browser = WindowProperties::new().with_title("Downloads").with_role("browser");
+
+        assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM).with_properties(browser))
+            .is_ok());
+        assert_eq!(0, wm.get_current_workspace_index());
+        assert_eq!(Some(1), wm.get_focused_window());
+        assert_eq!(Some(1), wm.get_window_workspace_index(2));
+        assert!(wm.get_workspace(1).unwrap().is_minimised(2));
+
+        assert!(wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)
+                .with_properties(WindowProperties::new().with_title("Downloads")))
+            .is_ok());
+        assert_eq!(vec![3], wm.get_minimised_windows());
+        assert_eq!(Some(1), wm.get_focused_window());
+    }
+
+    #[test]
+    fn test_window_rules_without_properties() {
+        let mut wm = MultiWorkspaces::<MinimiseWM>::new(SCREEN);
+        assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+        wm.set_window_rules(WindowRules::new()
+            .with_rule(WindowMatcher::any(), vec![RuleAction::Workspace(1), RuleAction::Float]));
+
+        // add_window applies the rules matching every window
+        assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+        assert_eq!(Some(1), wm.get_window_workspace_index(2));
+        assert!(wm.get_workspace(1).unwrap().is_floating(2));
+        assert_eq!(Some(1), wm.get_focused_window());
+    }
+
+    #[test]
+    fn test_window_rules_workspace_out_of_bound() {
+        let mut wm = wm_with_rules();
+        wm.set_window_rules(WindowRules::new()
+            .with_rule(WindowMatcher::any(), vec![RuleAction::Workspace(5)]));
+
+        match wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)) {
+            Err(MultiWorkspaceError::WorkspaceIndexOutOfBound(5)) => {}
+            _ => panic!("expected a WorkspaceIndexOutOfBound error"),
+        }
+        assert!(!wm.is_managed(2));
+        assert_eq!(1, wm.get_window_rules().rules.len());
+    }
+
+    #[test]
+    fn test_moved_window_keeps_its_state() {
+        let mut wm = wm_with_rules();
+        let pavucontrol = WindowProperties::new().with_class("Pavucontrol", "pavucontrol");
+        assert!(wm.add_window(WindowWithInfo::new_tiled(2, SCREEN.to_geometry())
+                .with_properties(pavucontrol))
+            .is_ok());
+        assert!(wm.toggle_floating(2).is_ok());
+
+        // the rules are not applied again, so the window stays tiled on the other workspace
+        assert!(wm.move_window_to_workspace_and_follow(2, 1).is_ok());
+        assert!(!wm.is_floating(2));
+        assert_eq!(Some(2), wm.get_focused_window());
+    }
+
+    #[test]
+    fn test_without_minimise_support() {
+        let mut wm = MultiWorkspaces::<TilingWM>::new(SCREEN);
+        assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+        assert!(wm.switch_workspace(1).is_ok());
+        assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+        assert_eq!(Some(2), wm.get_master_window());
+        assert!(wm.swap_with_master(1).is_ok());
+        assert_eq!(0, wm.get_current_workspace_index());
+        assert!(wm.remove_window(2).is_ok());
+        assert_eq!(vec![1], wm.get_windows());
+    }
 }
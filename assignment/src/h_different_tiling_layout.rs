@@ -34,12 +34,14 @@
 //! I have no source for this layout, but I do believe this is a fairly known scheme.
 
 // Add imports here
-use cplwm_api::types::{Geometry, PrevOrNext, Screen, Window, WindowLayout, WindowWithInfo};
-use cplwm_api::wm::{TilingSupport, WindowManager};
+use cplwm_api::types::{Direction, Geometry, PrevOrNext, Screen, Tab, Window, WindowLayout,
+                       WindowWithInfo};
+use cplwm_api::wm::{TabSupport, TilingSupport, WindowManager};
 
-use wm_common::{LayoutManager, Manager, TilingLayout, TilingTrait};
+use wm_common::{FocusPolicySupport, LayoutManager, LayoutMessage, LayoutMessageSupport, Manager,
+                TilingLayout, TilingTrait};
 use wm_common::error::StandardError;
-use a_fullscreen_wm::FocusManager;
+use a_fullscreen_wm::{FocusManager, FocusPolicy, RemovalFocus};
 use b_tiling_wm::TileManager;
 use std::collections::VecDeque;
 
@@ -80,14 +82,23 @@ impl WindowManager for TilingWM {
     }
     fn add_window(&mut self, window_with_info: WindowWithInfo) -> Result<(), Self::Error> {
         self.focus_manager
-            .add_window(window_with_info)
+            .add_window(window_with_info.clone())
             .and_then(|_| self.tile_manager.add_window(window_with_info))
     }
 
     fn remove_window(&mut self, window: Window) -> Result<(), Self::Error> {
+        let was_focused = self.get_focused_window() == Some(window);
+        let next_tile = self.tile_manager.get_next_tile(window);
         self.focus_manager
             .remove_window(window)
             .and_then(|_| self.tile_manager.remove_window(window))
+            .and_then(|_| if was_focused {
+                let master = self.tile_manager.get_master_window();
+                self.focus_manager.focus_after_removal(next_tile, master)
+            } else {
+                Ok(())
+            })
+            .and_then(|_| self.tile_manager.focus_shifted(self.focus_manager.get_focused_window()))
     }
 
     fn get_window_layout(&self) -> WindowLayout {
@@ -98,11 +109,27 @@ impl WindowManager for TilingWM {
     }
 
     fn focus_window(&mut self, window: Option<Window>) -> Result<(), Self::Error> {
-        self.focus_manager.focus_window(window)
+        self.focus_manager
+            .focus_window(window)
+            .and_then(|_| self.tile_manager.focus_shifted(window))
     }
 
     fn cycle_focus(&mut self, dir: PrevOrNext) {
-        self.focus_manager.cycle_focus(dir)
+        // the layout may decide which tile comes next, e.g. to respect its containers
+        let neighbour = self.focus_manager
+            .get_focused_window()
+            .and_then(|window| self.tile_manager.get_focus_neighbour(window, dir));
+        let windows = self.tile_manager.get_window_layout();
+        match self.focus_manager
+            .cycle_focus_in(dir, &windows, neighbour)
+            .and_then(|_| {
+                let focused = self.focus_manager.get_focused_window();
+                self.tile_manager.focus_shifted(focused)
+            }) {
+            Ok(_) => {}
+            // rather no focused window than one the layout does not know is focused
+            Err(_) => self.focus_manager.unfocus(),
+        }
     }
 
     fn get_window_info(&self, window: Window) -> Result<WindowWithInfo, Self::Error> {
@@ -130,6 +157,40 @@ impl TilingSupport for TilingWM {
     fn swap_windows(&mut self, dir: PrevOrNext) {
         self.tile_manager.swap_windows(dir, &self.focus_manager)
     }
+
+    fn swap_direction(&mut self, dir: Direction, wrap: bool) -> Result<(), Self::Error> {
+        self.tile_manager.swap_direction(dir, wrap, &self.focus_manager)
+    }
+}
+
+impl LayoutMessageSupport for TilingWM {
+    fn send_layout_message(&mut self, message: LayoutMessage) -> bool {
+        self.tile_manager.send_layout_message(message)
+    }
+}
+
+impl FocusPolicySupport for TilingWM {
+    fn get_focus_policy(&self) -> FocusPolicy {
+        self.focus_manager.focus_policy
+    }
+
+    fn set_focus_policy(&mut self, policy: FocusPolicy) {
+        self.focus_manager.focus_policy = policy
+    }
+
+    fn get_removal_focus(&self) -> RemovalFocus {
+        self.focus_manager.removal_focus
+    }
+
+    fn set_removal_focus(&mut self, removal_focus: RemovalFocus) {
+        self.focus_manager.removal_focus = removal_focus
+    }
+}
+
+impl TabSupport for TilingWM {
+    fn get_tabs(&self) -> Vec<Tab> {
+        self.tile_manager.get_tabs()
+    }
 }
 
 
@@ -145,7 +206,7 @@ impl TilingLayout for BasicDockLayout {
         return tiles.front().map(|w| *w);
     }
 
-    fn swap_with_master(&self,
+    fn swap_with_master(&mut self,
                         window: Window,
                         tiles: &mut VecDeque<Window>)
                         -> Result<(), Self::Error> {
@@ -168,7 +229,7 @@ impl TilingLayout for BasicDockLayout {
         }
     }
 
-    fn swap_windows(&self, window: Window, dir: PrevOrNext, tiles: &mut VecDeque<Window>) {
+    fn swap_windows(&mut self, window: Window, dir: PrevOrNext, tiles: &mut VecDeque<Window>) {
         tiles.iter().position(|w| *w == window).and_then(|index| {
             let n = tiles.len() as i32;
             let neighbour = (neighbour_of(&(index as i32), dir) + n) % n;
@@ -227,6 +288,11 @@ impl TilingLayout for BasicDockLayout {
             }
         }
     }
+
+    /// This layout has nothing to adjust, so it ignores every message
+    fn handle_message(&mut self, _: LayoutMessage) -> bool {
+        false
+    }
 }
 
 
@@ -500,6 +566,8 @@ mod vertical_layout_tests {
 #[cfg(test)]
 mod tests {
     use wm_common::tests::window_manager;
+    use wm_common::tests::layout_message_support;
+    use wm_common::tests::tab_support;
     use wm_common::tests::tiling_support;
     use super::TilingWM;
     use super::BasicDockLayout;
@@ -559,4 +627,14 @@ mod tests {
     fn test_tiling_layout() {
         tiling_support::test_get_window_info::<TilingWM, BasicDockLayout>(BasicDockLayout {});
     }
+
+    #[test]
+    fn test_ignored_messages() {
+        layout_message_support::test_ignored_messages::<TilingWM>();
+    }
+
+    #[test]
+    fn test_no_tabs_without_tab_bar() {
+        tab_support::test_no_tabs_without_tab_bar::<TilingWM>();
+    }
 }
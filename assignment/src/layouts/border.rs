@@ -0,0 +1,382 @@
+//! Window borders.
+//!
+//! BorderLayout wraps another layout and shrinks every tile by the border width, so the border
+//! drawn around a tile stays within the tile and never overlaps its neighbours. BorderManager
+//! keeps the border colours and the windows demanding attention, and extends the window layout
+//! of a window manager with the border of every window, see BorderSupport.
+//!
+//! ```text
+//! +-----------+-----------+
+//! |+---------+|+---------+|
+//! ||         |||         ||
+//! ||    1    |||    2    ||
+//! ||         |||         ||
+//! |+---------+|+---------+|
+//! +-----------+-----------+
+//! ```
+
+use cplwm_api::types::{BorderColours, BorderState, BorderWidth, BorderedWindow,
+                       BorderedWindowLayout, GapSize, Geometry, PrevOrNext, Screen, Tab, Window};
+use cplwm_api::wm::WindowManager;
+use wm_common::{BorderTrait, GapTrait, LayoutMessage, LayoutSwitchTrait, MasterTrait,
+                TilingLayout};
+use std::collections::VecDeque;
+
+/// Shrink the geometry by the border width at every side, without shrinking past zero
+pub fn remove_border(geometry: Geometry, border_width: BorderWidth) -> Geometry {
+    Geometry {
+        x: geometry.x + border_width as i32,
+        y: geometry.y + border_width as i32,
+        width: geometry.width.saturating_sub(2 * border_width),
+        height: geometry.height.saturating_sub(2 * border_width),
+    }
+}
+
+/// Grow the geometry by the border width at every side
+pub fn add_border(geometry: Geometry, border_width: BorderWidth) -> Geometry {
+    Geometry {
+        x: geometry.x - border_width as i32,
+        y: geometry.y - border_width as i32,
+        width: geometry.width + 2 * border_width,
+        height: geometry.height + 2 * border_width,
+    }
+}
+
+/// A TilingLayout which wraps another layout and makes room for the border of every tile
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct BorderLayout<T: TilingLayout> {
+    /// width of the border around every tile
+    pub border_width: BorderWidth,
+    /// the underlying layout strategy
+    pub tiling_layout: T,
+}
+
+impl<T: TilingLayout> BorderLayout<T> {
+    /// wrap the given layout with a border around every tile
+    pub fn new(tiling_layout: T, border_width: BorderWidth) -> BorderLayout<T> {
+        BorderLayout {
+            border_width,
+            tiling_layout,
+        }
+    }
+}
+
+impl<T: TilingLayout> BorderTrait for BorderLayout<T> {
+    fn get_border_width(&self) -> BorderWidth {
+        self.border_width
+    }
+
+    fn set_border_width(&mut self, border_width: BorderWidth) {
+        self.border_width = border_width;
+    }
+}
+
+impl<T: TilingLayout + GapTrait> GapTrait for BorderLayout<T> {
+    fn get_gap(&self) -> GapSize {
+        self.tiling_layout.get_gap()
+    }
+
+    fn set_gap(&mut self, gap: GapSize) {
+        self.tiling_layout.set_gap(gap)
+    }
+}
+
+impl<T: TilingLayout + LayoutSwitchTrait> LayoutSwitchTrait for BorderLayout<T> {
+    fn get_layout_name(&self) -> &str {
+        self.tiling_layout.get_layout_name()
+    }
+
+    fn next_layout(&mut self) {
+        self.tiling_layout.next_layout()
+    }
+
+    fn prev_layout(&mut self) {
+        self.tiling_layout.prev_layout()
+    }
+
+    fn select_layout(&mut self, name: &str) -> bool {
+        self.tiling_layout.select_layout(name)
+    }
+}
+
+impl<T: TilingLayout + MasterTrait> MasterTrait for BorderLayout<T> {
+    fn get_master_ratio(&self) -> u32 {
+        self.tiling_layout.get_master_ratio()
+    }
+
+    fn get_master_count(&self) -> usize {
+        self.tiling_layout.get_master_count()
+    }
+
+    fn shrink_master(&mut self) {
+        self.tiling_layout.shrink_master()
+    }
+
+    fn expand_master(&mut self) {
+        self.tiling_layout.expand_master()
+    }
+
+    fn inc_master(&mut self) {
+        self.tiling_layout.inc_master()
+    }
+
+    fn dec_master(&mut self) {
+        self.tiling_layout.dec_master()
+    }
+}
+
+impl<T: TilingLayout> TilingLayout for BorderLayout<T> {
+    // use the same type for Error as the wrapped layout
+    type Error = T::Error;
+
+    fn get_master_window(&self, tiles: &VecDeque<Window>) -> Option<Window> {
+        self.tiling_layout.get_master_window(tiles)
+    }
+
+    fn swap_with_master(&mut self,
+                        window: Window,
+                        tiles: &mut VecDeque<Window>)
+                        -> Result<(), Self::Error> {
+        self.tiling_layout.swap_with_master(window, tiles)
+    }
+
+    fn swap_windows(&mut self, window: Window, dir: PrevOrNext, tiles: &mut VecDeque<Window>) {
+        self.tiling_layout.swap_windows(window, dir, tiles)
+    }
+
+    fn swap_tiles(&mut self, window: Window, other: Window, tiles: &mut VecDeque<Window>) {
+        self.tiling_layout.swap_tiles(window, other, tiles)
+    }
+
+    fn get_window_geometry(&self,
+                           window: Window,
+                           screen: &Screen,
+                           tiles: &VecDeque<Window>)
+                           -> Result<Geometry, Self::Error> {
+        self.tiling_layout
+            .get_window_geometry(window, screen, tiles)
+            .map(|geometry| remove_border(geometry, self.border_width))
+    }
+
+    fn get_window_geometries(&self,
+                             screen: &Screen,
+                             tiles: &VecDeque<Window>)
+                             -> Vec<(Window, Geometry)> {
+        self.tiling_layout
+            .get_window_geometries(screen, tiles)
+            .into_iter()
+            .map(|(window, geometry)| (window, remove_border(geometry, self.border_width)))
+            .collect()
+    }
+
+    fn handle_message(&mut self, message: LayoutMessage) -> bool {
+        self.tiling_layout.handle_message(message)
+    }
+
+    fn focus_shifted(&mut self, window: Option<Window>) {
+        self.tiling_layout.focus_shifted(window)
+    }
+
+    fn tile_added(&mut self, window: Window) {
+        self.tiling_layout.tile_added(window)
+    }
+
+    fn tile_removed(&mut self, window: Window) {
+        self.tiling_layout.tile_removed(window)
+    }
+
+    fn screen_resized(&mut self, screen: &Screen) {
+        self.tiling_layout.screen_resized(screen)
+    }
+
+    fn get_stacking_order(&self, tiles: &VecDeque<Window>) -> Vec<Window> {
+        self.tiling_layout.get_stacking_order(tiles)
+    }
+
+    fn get_focus_neighbour(&self,
+                           window: Window,
+                           dir: PrevOrNext,
+                           tiles: &VecDeque<Window>)
+                           -> Option<Window> {
+        self.tiling_layout.get_focus_neighbour(window, dir, tiles)
+    }
+
+    /// The tab bar has no border, so the tabs are left alone.
+    fn get_tabs(&self, screen: &Screen, tiles: &VecDeque<Window>) -> Vec<Tab> {
+        self.tiling_layout.get_tabs(screen, tiles)
+    }
+}
+
+/// Manager keeping the border colours and the windows demanding attention
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct BorderManager {
+    /// the colour of the borders in every state
+    pub colours: BorderColours,
+    /// the windows demanding attention
+    pub urgent_windows: Vec<Window>,
+}
+
+impl BorderManager {
+    /// creates a BorderManager without urgent windows and with the default colours
+    pub fn new() -> BorderManager {
+        BorderManager {
+            colours: BorderColours {
+                focused: 0x4c7899,
+                unfocused: 0x333333,
+                urgent: 0x900000,
+            },
+            urgent_windows: Vec::new(),
+        }
+    }
+
+    /// whether the window demands attention
+    pub fn is_urgent(&self, window: Window) -> bool {
+        self.urgent_windows.contains(&window)
+    }
+
+    /// mark the window as demanding attention or not
+    pub fn set_urgent(&mut self, window: Window, urgent: bool) {
+        self.urgent_windows.retain(|w| *w != window);
+        if urgent {
+            self.urgent_windows.push(window);
+        }
+    }
+
+    /// forget the window, e.g. when it is removed from the window manager
+    pub fn remove_window(&mut self, window: Window) {
+        self.set_urgent(window, false)
+    }
+
+    /// The state of the window: the focused window is never shown as urgent.
+    pub fn get_state(&self, window: Window, focused_window: Option<Window>) -> BorderState {
+        if focused_window == Some(window) {
+            BorderState::Focused
+        } else if self.is_urgent(window) {
+            BorderState::Urgent
+        } else {
+            BorderState::Unfocused
+        }
+    }
+
+    /// The window layout of the window manager with the given border around every window but
+    /// the fullscreen one.
+    pub fn get_bordered_window_layout<WM: WindowManager>(&self,
+                                                         wm: &WM,
+                                                         border_width: BorderWidth)
+                                                         -> BorderedWindowLayout {
+        let layout = wm.get_window_layout();
+        let focused_window = layout.focused_window;
+        BorderedWindowLayout {
+            focused_window,
+            windows: layout.windows
+                .into_iter()
+                .map(|(window, geometry)| {
+                    let border_width = match wm.get_window_info(window) {
+                        Ok(ref info) if info.fullscreen => 0,
+                        _ => border_width,
+                    };
+                    let state = self.get_state(window, focused_window);
+                    BorderedWindow {
+                        window,
+                        geometry: add_border(geometry, border_width),
+                        border_width,
+                        state,
+                        colour: self.colours.get_colour(state),
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Default for BorderManager {
+    fn default() -> BorderManager {
+        BorderManager::new()
+    }
+}
+
+#[cfg(test)]
+mod border_layout_tests {
+    use super::{BorderLayout, BorderManager, add_border, remove_border};
+    use wm_common::{BorderTrait, GapTrait, TilingLayout};
+    use b_tiling_wm::VerticalLayout;
+    use f_gaps::GapLayout;
+    use std::collections::VecDeque;
+    use cplwm_api::types::*;
+
+    static SCREEN1: Screen = Screen {
+        width: 200,
+        height: 300,
+    };
+
+    fn tiles(count: Window) -> VecDeque<Window> {
+        (1..count + 1).collect()
+    }
+
+    #[test]
+    fn test_border_layout_geometry() {
+        let layout = BorderLayout::new(VerticalLayout::new(), 2);
+        let tiles = tiles(2);
+
+        assert_eq!(Geometry {
+                       x: 2,
+                       y: 2,
+                       width: 96,
+                       height: 296,
+                   },
+                   layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap());
+        assert_eq!(Geometry {
+                       x: 102,
+                       y: 2,
+                       width: 96,
+                       height: 296,
+                   },
+                   layout.get_window_geometry(2, &SCREEN1, &tiles).ok().unwrap());
+    }
+
+    // with its border included, every tile gets back the geometry of the wrapped layout
+    #[test]
+    fn test_border_layout_borders_do_not_overlap() {
+        let mut layout = BorderLayout::new(GapLayout::new(VerticalLayout::new(), 0), 0);
+        layout.set_border_width(3);
+        layout.set_gap(4);
+        assert_eq!(3, layout.get_border_width());
+        assert_eq!(4, layout.get_gap());
+        let tiles = tiles(3);
+
+        for tile in &tiles {
+            let geometry = layout.get_window_geometry(*tile, &SCREEN1, &tiles).ok().unwrap();
+            assert_eq!(layout.tiling_layout.get_window_geometry(*tile, &SCREEN1, &tiles).ok(),
+                       Some(add_border(geometry, 3)));
+        }
+    }
+
+    #[test]
+    fn test_border_layout_huge_border() {
+        let geometry = remove_border(Geometry {
+                                         x: 0,
+                                         y: 0,
+                                         width: 10,
+                                         height: 30,
+                                     },
+                                     8);
+        assert_eq!((0, 14), (geometry.width, geometry.height));
+    }
+
+    #[test]
+    fn test_border_manager_states() {
+        let mut manager = BorderManager::new();
+        manager.set_urgent(1, true);
+        manager.set_urgent(2, true);
+        manager.set_urgent(2, false);
+
+        assert!(manager.is_urgent(1));
+        assert!(!manager.is_urgent(2));
+        assert_eq!(BorderState::Urgent, manager.get_state(1, Some(2)));
+        assert_eq!(BorderState::Focused, manager.get_state(1, Some(1)));
+        assert_eq!(BorderState::Unfocused, manager.get_state(2, None));
+
+        manager.remove_window(1);
+        assert!(!manager.is_urgent(1));
+    }
+}
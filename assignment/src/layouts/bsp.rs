@@ -0,0 +1,742 @@
+//! A binary space partitioning layout, like the layout of bspwm.
+//!
+//! Unlike the other layouts, the geometry of a tile does not follow from its position in the
+//! tiles, but from a tree of splits kept by the layout. Every split divides its area in two parts
+//! with its own orientation and ratio, every leaf of the tree is a tile. A new tile splits the
+//! leaf of the focused tile, the new split gets the other orientation than the split above it.
+//! When a tile is removed, the other part of its split takes the place of that split.
+//!
+//! ```text
+//! +--------+--------+
+//! |   1    |        |
+//! +--------+   2    |
+//! |   4    +--------+
+//! |        |   3    |
+//! +--------+--------+
+//! ```
+//!
+//! The first leaf is the master. The order of the tiles in the TileManager does not matter for
+//! this layout, swapping tiles swaps their leaves in the tree.
+
+use cplwm_api::types::{Geometry, PrevOrNext, Screen, Window};
+use wm_common::{LayoutMessage, TilingLayout};
+use wm_common::error::StandardError;
+use std::collections::VecDeque;
+use std::mem;
+
+/// The orientation of a split
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq)]
+pub enum Orientation {
+    /// the parts are side by side, the first part on the left
+    Horizontal,
+    /// the parts are stacked, the first part on top
+    Vertical,
+}
+
+impl Orientation {
+    /// The other orientation
+    pub fn opposite(&self) -> Orientation {
+        match *self {
+            Orientation::Horizontal => Orientation::Vertical,
+            Orientation::Vertical => Orientation::Horizontal,
+        }
+    }
+}
+
+/// An area split in two parts
+#[derive(Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq)]
+pub struct Split {
+    /// how the area is split
+    pub orientation: Orientation,
+    /// percentage of the area taken by the first part
+    pub ratio: u32,
+    /// the first part, on the left or on top
+    pub first: Box<SplitTree>,
+    /// the second part, on the right or at the bottom
+    pub second: Box<SplitTree>,
+}
+
+impl Split {
+    /// The areas of the first and the second part of the given area
+    pub fn split_area(&self, area: Geometry) -> (Geometry, Geometry) {
+        match self.orientation {
+            Orientation::Horizontal => {
+                let width = area.width * self.ratio / 100;
+                (Geometry { width, ..area },
+                 Geometry {
+                    x: area.x + width as i32,
+                    width: area.width - width,
+                    ..area
+                })
+            }
+            Orientation::Vertical => {
+                let height = area.height * self.ratio / 100;
+                (Geometry { height, ..area },
+                 Geometry {
+                    y: area.y + height as i32,
+                    height: area.height - height,
+                    ..area
+                })
+            }
+        }
+    }
+
+    /// Swap the two parts, they keep their size
+    fn swap_parts(&mut self) {
+        mem::swap(&mut self.first, &mut self.second);
+        self.ratio = 100 - self.ratio;
+    }
+
+    /// Rotate this split and all splits in it clockwise by 90 degrees
+    fn rotate(&mut self) {
+        // the top part moves to the right, the left part moves to the top
+        if self.orientation == Orientation::Vertical {
+            self.swap_parts();
+        }
+        self.orientation = self.orientation.opposite();
+        self.first.rotate();
+        self.second.rotate();
+    }
+
+    /// Mirror this split and all splits in it, only the splits with the given orientation swap
+    /// their parts.
+    fn flip(&mut self, orientation: Orientation) {
+        if self.orientation == orientation {
+            self.swap_parts();
+        }
+        self.first.flip(orientation);
+        self.second.flip(orientation);
+    }
+}
+
+/// The tree of splits of a BspLayout
+#[derive(Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq)]
+pub enum SplitTree {
+    /// a tile
+    Leaf(Window),
+    /// an area split in two parts
+    Split(Split),
+}
+
+impl SplitTree {
+    /// The windows of the leaves, from the first to the last leaf
+    pub fn get_windows(&self) -> Vec<Window> {
+        match *self {
+            SplitTree::Leaf(window) => vec![window],
+            SplitTree::Split(ref split) => {
+                let mut windows = split.first.get_windows();
+                windows.extend(split.second.get_windows());
+                windows
+            }
+        }
+    }
+
+    /// True when one of the leaves is the given window
+    pub fn contains(&self, window: Window) -> bool {
+        self.get_windows().contains(&window)
+    }
+
+    /// The geometry of the leaf of the given window when this tree takes the given area
+    pub fn get_geometry(&self, window: Window, area: Geometry) -> Option<Geometry> {
+        match *self {
+            SplitTree::Leaf(w) if w == window => Some(area),
+            SplitTree::Leaf(_) => None,
+            SplitTree::Split(ref split) => {
+                let (first, second) = split.split_area(area);
+                split.first
+                    .get_geometry(window, first)
+                    .or_else(|| split.second.get_geometry(window, second))
+            }
+        }
+    }
+
+    /// Split the leaf of target in two, the new window gets the second part. The orientation is
+    /// used when this tree is the leaf, the splits below get the opposite orientation of their
+    /// parent split. Returns false when target is not in the tree.
+    fn split_leaf(&mut self, target: Window, window: Window, orientation: Orientation) -> bool {
+        match *self {
+            SplitTree::Leaf(w) if w == target => {
+                *self = SplitTree::Split(Split {
+                    orientation,
+                    ratio: 50,
+                    first: Box::new(SplitTree::Leaf(target)),
+                    second: Box::new(SplitTree::Leaf(window)),
+                });
+                true
+            }
+            SplitTree::Leaf(_) => false,
+            SplitTree::Split(ref mut split) => {
+                let orientation = split.orientation.opposite();
+                split.first.split_leaf(target, window, orientation) ||
+                split.second.split_leaf(target, window, orientation)
+            }
+        }
+    }
+
+    /// The tree without the leaf of the given window, the other part of its split takes the
+    /// place of that split. Returns None when the tree was just that leaf.
+    fn remove(self, window: Window) -> Option<SplitTree> {
+        match self {
+            SplitTree::Leaf(w) if w == window => None,
+            SplitTree::Leaf(w) => Some(SplitTree::Leaf(w)),
+            SplitTree::Split(Split { orientation, ratio, first, second }) => {
+                match (first.remove(window), second.remove(window)) {
+                    (None, rest) | (rest, None) => rest,
+                    (Some(first), Some(second)) => {
+                        Some(SplitTree::Split(Split {
+                            orientation,
+                            ratio,
+                            first: Box::new(first),
+                            second: Box::new(second),
+                        }))
+                    }
+                }
+            }
+        }
+    }
+
+    /// The split of which the leaf of the given window is one of the parts
+    fn get_parent_mut(&mut self, window: Window) -> Option<&mut Split> {
+        match *self {
+            SplitTree::Leaf(_) => None,
+            SplitTree::Split(ref mut split) => {
+                if *split.first == SplitTree::Leaf(window) ||
+                   *split.second == SplitTree::Leaf(window) {
+                    Some(split)
+                } else if split.first.contains(window) {
+                    split.first.get_parent_mut(window)
+                } else {
+                    split.second.get_parent_mut(window)
+                }
+            }
+        }
+    }
+
+    /// Swap the leaves of the two windows
+    fn swap_leaves(&mut self, a: Window, b: Window) {
+        match *self {
+            SplitTree::Leaf(ref mut window) => {
+                if *window == a {
+                    *window = b;
+                } else if *window == b {
+                    *window = a;
+                }
+            }
+            SplitTree::Split(ref mut split) => {
+                split.first.swap_leaves(a, b);
+                split.second.swap_leaves(a, b);
+            }
+        }
+    }
+
+    fn rotate(&mut self) {
+        match *self {
+            SplitTree::Leaf(_) => {}
+            SplitTree::Split(ref mut split) => split.rotate(),
+        }
+    }
+
+    fn flip(&mut self, orientation: Orientation) {
+        match *self {
+            SplitTree::Leaf(_) => {}
+            SplitTree::Split(ref mut split) => split.flip(orientation),
+        }
+    }
+}
+
+/// Layout which keeps a tree of splits, the first leaf is the master
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct BspLayout {
+    /// the tree of splits, None when there are no tiles
+    pub tree: Option<SplitTree>,
+    /// the focused tile, new tiles split its leaf
+    pub focused_window: Option<Window>,
+    /// percentage by which the part of a tile changes with ShrinkMaster and ExpandMaster messages
+    pub ratio_step: u32,
+}
+
+impl BspLayout {
+    /// A BspLayout without tiles, resizable in steps of 5 percent
+    pub fn new() -> BspLayout {
+        BspLayout {
+            tree: None,
+            focused_window: None,
+            ratio_step: 5,
+        }
+    }
+
+    /// The windows of the leaves, from the first to the last leaf
+    pub fn get_windows(&self) -> Vec<Window> {
+        self.tree.as_ref().map(|tree| tree.get_windows()).unwrap_or_default()
+    }
+
+    /// Call f with the split of which the leaf of the window is a part and whether it is the
+    /// first part. Does nothing when the window is the only tile.
+    fn with_parent<F>(&mut self, window: Window, f: F) -> Result<(), StandardError>
+        where F: FnOnce(&mut Split, bool)
+    {
+        match self.tree {
+            Some(ref mut tree) if tree.contains(window) => {
+                match tree.get_parent_mut(window) {
+                    None => {}
+                    Some(split) => {
+                        let first = *split.first == SplitTree::Leaf(window);
+                        f(split, first)
+                    }
+                }
+                Ok(())
+            }
+            _ => Err(StandardError::UnknownWindow(window)),
+        }
+    }
+
+    /// Rotate the split of the window clockwise by 90 degrees
+    pub fn rotate(&mut self, window: Window) -> Result<(), StandardError> {
+        self.with_parent(window, |split, _| split.rotate())
+    }
+
+    /// Mirror the split of the window, the splits in it with the same orientation swap their
+    /// parts too.
+    pub fn flip(&mut self, window: Window) -> Result<(), StandardError> {
+        self.with_parent(window, |split, _| {
+            let orientation = split.orientation;
+            split.flip(orientation)
+        })
+    }
+
+    /// Set the ratio of the split of the window, the percentage of the area taken by its first
+    /// part. The ratio is kept between 1 and 99 percent so no part disappears.
+    pub fn set_split_ratio(&mut self, window: Window, ratio: u32) -> Result<(), StandardError> {
+        self.with_parent(window, |split, _| split.ratio = ratio.clamp(1, 99))
+    }
+
+    /// Shrink the part of the window in its split by ratio_step percent
+    pub fn shrink(&mut self, window: Window) -> Result<(), StandardError> {
+        let step = self.ratio_step;
+        self.with_parent(window, |split, first| {
+            if first && split.ratio > step {
+                split.ratio -= step;
+            } else if !first && split.ratio + step < 100 {
+                split.ratio += step;
+            }
+        })
+    }
+
+    /// Expand the part of the window in its split by ratio_step percent
+    pub fn expand(&mut self, window: Window) -> Result<(), StandardError> {
+        let step = self.ratio_step;
+        self.with_parent(window, |split, first| {
+            if first && split.ratio + step < 100 {
+                split.ratio += step;
+            } else if !first && split.ratio > step {
+                split.ratio -= step;
+            }
+        })
+    }
+}
+
+impl Default for BspLayout {
+    fn default() -> BspLayout {
+        BspLayout::new()
+    }
+}
+
+impl TilingLayout for BspLayout {
+    type Error = StandardError;
+
+    fn get_master_window(&self, _: &VecDeque<Window>) -> Option<Window> {
+        self.get_windows().first().copied()
+    }
+
+    fn swap_with_master(&mut self,
+                        window: Window,
+                        tiles: &mut VecDeque<Window>)
+                        -> Result<(), Self::Error> {
+        match (self.get_master_window(tiles), self.tree.as_mut()) {
+            (Some(master), Some(tree)) if tree.contains(window) => {
+                tree.swap_leaves(window, master);
+                Ok(())
+            }
+            _ => Err(StandardError::UnknownWindow(window)),
+        }
+    }
+
+    /// Swap with the previous or next leaf, the first and the last leaf are neighbours.
+    fn swap_windows(&mut self, window: Window, dir: PrevOrNext, tiles: &mut VecDeque<Window>) {
+        let windows = self.get_windows();
+        let index = match windows.iter().position(|w| *w == window) {
+            Some(index) => index,
+            None => return,
+        };
+        let neighbour = match dir {
+            PrevOrNext::Prev => (index + windows.len() - 1) % windows.len(),
+            PrevOrNext::Next => (index + 1) % windows.len(),
+        };
+        self.swap_tiles(window, windows[neighbour], tiles);
+    }
+
+    fn swap_tiles(&mut self, window: Window, other: Window, _: &mut VecDeque<Window>) {
+        match self.tree.as_mut() {
+            Some(tree) if tree.contains(window) && tree.contains(other) => {
+                tree.swap_leaves(window, other)
+            }
+            _ => {}
+        }
+    }
+
+    fn get_window_geometry(&self,
+                           window: Window,
+                           screen: &Screen,
+                           _: &VecDeque<Window>)
+                           -> Result<Geometry, Self::Error> {
+        self.tree
+            .as_ref()
+            .and_then(|tree| tree.get_geometry(window, screen.to_geometry()))
+            .ok_or(StandardError::UnknownWindow(window))
+    }
+
+    /// Handles ShrinkMaster and ExpandMaster by resizing the part of the focused tile, Rotate and
+    /// Flip act on the split of the focused tile.
+    fn handle_message(&mut self, message: LayoutMessage) -> bool {
+        let action: fn(&mut BspLayout, Window) -> Result<(), StandardError> = match message {
+            LayoutMessage::ShrinkMaster => BspLayout::shrink,
+            LayoutMessage::ExpandMaster => BspLayout::expand,
+            LayoutMessage::Rotate => BspLayout::rotate,
+            LayoutMessage::Flip => BspLayout::flip,
+            _ => return false,
+        };
+        let window = match self.focused_window {
+            Some(window) => window,
+            // without focused tile there is nothing to act on
+            None => return true,
+        };
+        // an error means the focused window is not a tile, e.g. a floating window, so it has no
+        // split to act on
+        action(self, window).unwrap_or(());
+        true
+    }
+
+    fn focus_shifted(&mut self, window: Option<Window>) {
+        self.focused_window = window;
+    }
+
+    /// The new tile splits the leaf of the focused tile, or the last leaf when the focused tile
+    /// is unknown.
+    fn tile_added(&mut self, window: Window) {
+        match self.tree {
+            None => self.tree = Some(SplitTree::Leaf(window)),
+            Some(ref mut tree) => {
+                let target = match self.focused_window {
+                    Some(w) if tree.contains(w) => w,
+                    // a tree always has at least one leaf
+                    _ => *tree.get_windows().last().unwrap(),
+                };
+                tree.split_leaf(target, window, Orientation::Horizontal);
+            }
+        }
+    }
+
+    fn tile_removed(&mut self, window: Window) {
+        self.tree = self.tree.take().and_then(|tree| tree.remove(window));
+    }
+}
+
+#[cfg(test)]
+mod bsp_layout_tests {
+    use super::{BspLayout, Orientation, Split, SplitTree};
+    use wm_common::{LayoutManager, LayoutMessage, Manager, TilingLayout};
+    use b_tiling_wm::TileManager;
+    use f_gaps::GapLayout;
+    use std::collections::VecDeque;
+    use cplwm_api::types::*;
+
+    static SCREEN1: Screen = Screen {
+        width: 800,
+        height: 600,
+    };
+
+    static SCREEN2: Screen = Screen {
+        width: 301,
+        height: 401,
+    };
+
+    static SOME_GEOM: Geometry = Geometry {
+        x: 10,
+        y: 10,
+        width: 100,
+        height: 100,
+    };
+
+    fn leaf(window: Window) -> SplitTree {
+        SplitTree::Leaf(window)
+    }
+
+    fn split(orientation: Orientation,
+             ratio: u32,
+             first: SplitTree,
+             second: SplitTree)
+             -> SplitTree {
+        SplitTree::Split(Split {
+            orientation,
+            ratio,
+            first: Box::new(first),
+            second: Box::new(second),
+        })
+    }
+
+    /// A layout to which the tiles are added like a TileManager does, every new tile gets the
+    /// focus.
+    fn layout_with(amount: Window) -> (BspLayout, VecDeque<Window>) {
+        let mut layout = BspLayout::new();
+        let mut tiles = VecDeque::new();
+        for window in 1..amount + 1 {
+            tiles.push_back(window);
+            layout.tile_added(window);
+            layout.focus_shifted(Some(window));
+        }
+        (layout, tiles)
+    }
+
+    #[test]
+    fn test_bsp_layout_no_window() {
+        let (mut layout, mut tiles) = layout_with(0);
+
+        // make sure there is no geometry.
+        assert!(layout.get_window_geometry(1, &SCREEN1, &tiles).is_err());
+        assert_eq!(None, layout.get_master_window(&tiles));
+        assert!(layout.swap_with_master(1, &mut tiles).is_err());
+        assert!(layout.rotate(1).is_err());
+    }
+
+    #[test]
+    fn test_bsp_layout_one_window() {
+        let (mut layout, tiles) = layout_with(1);
+
+        assert_eq!(Some(leaf(1)), layout.tree);
+        assert_eq!(SCREEN1.to_geometry(),
+                   layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap());
+        // there is no split to rotate
+        assert!(layout.rotate(1).is_ok());
+        assert_eq!(Some(leaf(1)), layout.tree);
+    }
+
+    #[test]
+    fn test_bsp_layout_splits_focused_leaf() {
+        let (mut layout, mut tiles) = layout_with(3);
+
+        assert_eq!(Some(split(Orientation::Horizontal,
+                              50,
+                              leaf(1),
+                              split(Orientation::Vertical, 50, leaf(2), leaf(3)))),
+                   layout.tree);
+
+        // the new tile splits the leaf of the focused tile
+        layout.focus_shifted(Some(1));
+        tiles.push_back(4);
+        layout.tile_added(4);
+
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 400,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 300,
+                       width: 400,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(4, &SCREEN1, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 400,
+                       y: 0,
+                       width: 400,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(2, &SCREEN1, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 400,
+                       y: 300,
+                       width: 400,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(3, &SCREEN1, &tiles).ok().unwrap());
+
+        assert_eq!(vec![1, 4, 2, 3], layout.get_windows());
+        assert!(layout.get_window_geometry(5, &SCREEN1, &tiles).is_err());
+    }
+
+    #[test]
+    fn test_bsp_layout_remove_collapses_split() {
+        let (mut layout, _) = layout_with(3);
+
+        layout.tile_removed(2);
+        assert_eq!(Some(split(Orientation::Horizontal, 50, leaf(1), leaf(3))), layout.tree);
+
+        layout.tile_removed(1);
+        assert_eq!(Some(leaf(3)), layout.tree);
+
+        layout.tile_removed(3);
+        assert_eq!(None, layout.tree);
+
+        // the focused tile is gone, so the new tile becomes the root
+        layout.tile_added(4);
+        assert_eq!(Some(leaf(4)), layout.tree);
+    }
+
+    // test to see the second part of a split gets the remaining pixels of irregular screens
+    #[test]
+    fn test_bsp_layout_multiple_windows_irregular_screen() {
+        let (layout, tiles) = layout_with(3);
+
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 150,
+                       height: 401,
+                   },
+                   layout.get_window_geometry(1, &SCREEN2, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 150,
+                       y: 0,
+                       width: 151,
+                       height: 200,
+                   },
+                   layout.get_window_geometry(2, &SCREEN2, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 150,
+                       y: 200,
+                       width: 151,
+                       height: 201,
+                   },
+                   layout.get_window_geometry(3, &SCREEN2, &tiles).ok().unwrap());
+    }
+
+    #[test]
+    fn test_bsp_layout_rotate() {
+        let (mut layout, _) = layout_with(3);
+
+        // the split of window 1 is the root, the whole tree rotates
+        assert!(layout.rotate(1).is_ok());
+        assert_eq!(Some(split(Orientation::Vertical,
+                              50,
+                              leaf(1),
+                              split(Orientation::Horizontal, 50, leaf(3), leaf(2)))),
+                   layout.tree);
+
+        // only the split of window 2 rotates
+        assert!(layout.rotate(2).is_ok());
+        assert_eq!(Some(split(Orientation::Vertical,
+                              50,
+                              leaf(1),
+                              split(Orientation::Vertical, 50, leaf(3), leaf(2)))),
+                   layout.tree);
+
+        assert!(layout.rotate(4).is_err());
+    }
+
+    #[test]
+    fn test_bsp_layout_flip() {
+        let (mut layout, _) = layout_with(3);
+        assert!(layout.set_split_ratio(1, 30).is_ok());
+
+        // only the splits with the orientation of the root split swap their parts
+        assert!(layout.flip(1).is_ok());
+        assert_eq!(Some(split(Orientation::Horizontal,
+                              70,
+                              split(Orientation::Vertical, 50, leaf(2), leaf(3)),
+                              leaf(1))),
+                   layout.tree);
+
+        assert!(layout.flip(3).is_ok());
+        assert_eq!(vec![3, 2, 1], layout.get_windows());
+        assert_eq!(Some(3), layout.get_master_window(&VecDeque::new()));
+    }
+
+    #[test]
+    fn test_bsp_layout_ratio() {
+        let (mut layout, tiles) = layout_with(2);
+
+        assert!(layout.set_split_ratio(2, 25).is_ok());
+        assert_eq!(Geometry {
+                       x: 200,
+                       y: 0,
+                       width: 600,
+                       height: 600,
+                   },
+                   layout.get_window_geometry(2, &SCREEN1, &tiles).ok().unwrap());
+
+        // no part can disappear
+        assert!(layout.set_split_ratio(2, 100).is_ok());
+        assert_eq!(Some(split(Orientation::Horizontal, 99, leaf(1), leaf(2))), layout.tree);
+        assert!(layout.set_split_ratio(3, 50).is_err());
+
+        // the focused tile is window 2, the second part of the split
+        assert!(layout.set_split_ratio(2, 50).is_ok());
+        assert!(layout.handle_message(LayoutMessage::ExpandMaster));
+        assert_eq!(Some(split(Orientation::Horizontal, 45, leaf(1), leaf(2))), layout.tree);
+        assert!(layout.handle_message(LayoutMessage::ShrinkMaster));
+        assert!(layout.handle_message(LayoutMessage::ShrinkMaster));
+        assert_eq!(Some(split(Orientation::Horizontal, 55, leaf(1), leaf(2))), layout.tree);
+
+        assert!(layout.handle_message(LayoutMessage::Rotate));
+        assert!(layout.handle_message(LayoutMessage::Flip));
+        assert_eq!(Some(split(Orientation::Vertical, 45, leaf(2), leaf(1))), layout.tree);
+        assert!(!layout.handle_message(LayoutMessage::IncMaster));
+    }
+
+    #[test]
+    fn test_bsp_layout_swap() {
+        let (mut layout, mut tiles) = layout_with(3);
+
+        assert!(layout.swap_with_master(3, &mut tiles).is_ok());
+        assert_eq!(vec![3, 2, 1], layout.get_windows());
+        assert_eq!(SCREEN1.to_geometry().width / 2,
+                   layout.get_window_geometry(3, &SCREEN1, &tiles).ok().unwrap().width);
+        assert!(layout.swap_with_master(4, &mut tiles).is_err());
+
+        layout.swap_windows(3, PrevOrNext::Prev, &mut tiles);
+        assert_eq!(vec![1, 2, 3], layout.get_windows());
+        layout.swap_windows(2, PrevOrNext::Next, &mut tiles);
+        assert_eq!(vec![1, 3, 2], layout.get_windows());
+        layout.swap_tiles(1, 2, &mut tiles);
+        assert_eq!(vec![2, 3, 1], layout.get_windows());
+        layout.swap_tiles(2, 4, &mut tiles);
+        layout.swap_tiles(1, 2, &mut tiles);
+        assert_eq!(vec![1, 3, 2], layout.get_windows());
+
+        // the tree keeps its shape
+        assert_eq!(Some(split(Orientation::Horizontal,
+                              50,
+                              leaf(1),
+                              split(Orientation::Vertical, 50, leaf(3), leaf(2)))),
+                   layout.tree);
+    }
+
+    #[test]
+    fn test_bsp_layout_in_tile_manager() {
+        let mut manager = TileManager::new(SCREEN1, GapLayout::new(BspLayout::new(), 0));
+        assert!(manager.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+        assert!(manager.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+        assert!(manager.focus_shifted(Some(1)).is_ok());
+        assert!(manager.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).is_ok());
+
+        assert_eq!(Some(split(Orientation::Horizontal,
+                              50,
+                              split(Orientation::Vertical, 50, leaf(1), leaf(3)),
+                              leaf(2))),
+                   manager.layout.tiling_layout.tree);
+        assert_eq!(3, manager.get_window_layout().len());
+
+        assert!(manager.remove_window(1).is_ok());
+        assert_eq!(Some(split(Orientation::Horizontal, 50, leaf(3), leaf(2))),
+                   manager.layout.tiling_layout.tree);
+    }
+}
@@ -0,0 +1,464 @@
+//! A three column layout with the master area in the middle of the screen, like the ThreeColMid
+//! layout of xmonad, which makes better use of the width of ultrawide screens.
+//!
+//! The stack tiles alternate between the right and the left column, starting with the right
+//! one. As long as the left column is empty, the master area starts at the left edge of the
+//! screen, so with two tiles this layout looks like the VerticalLayout.
+//!
+//! ```text
+//! +----+----------+----+
+//! | 3  |          | 2  |
+//! +----+    1     +----+
+//! | 5  |          | 4  |
+//! +----+----------+----+
+//! ```
+
+use cplwm_api::types::{Geometry, PrevOrNext, Screen, Window};
+use wm_common::{LayoutMessage, MasterArea, MasterTrait, TilingLayout};
+use wm_common::error::StandardError;
+use layouts::{split, swap_with_front, swap_with_neighbour};
+use std::cmp;
+use std::collections::VecDeque;
+
+/// Layout with the master area centered between two stack columns
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct CenteredMasterLayout {
+    /// the master area, see MasterTrait
+    pub master: MasterArea,
+    /// percentage of the width left by the master area taken by the left column
+    pub left_ratio: u32,
+}
+
+impl CenteredMasterLayout {
+    /// A CenteredMasterLayout with one master window taking half of the screen and two stack
+    /// columns of the same width
+    pub fn new() -> CenteredMasterLayout {
+        CenteredMasterLayout {
+            master: MasterArea::new(),
+            left_ratio: 50,
+        }
+    }
+
+    /// The x coordinate and the width of the left, the master and the right column.
+    fn get_columns(&self, screen: &Screen, masters: usize, tiles: usize) -> [(i32, u32); 3] {
+        if tiles <= masters {
+            return [(0, 0), (0, screen.width), (screen.width as i32, 0)];
+        }
+        let master_width = screen.width * self.master.ratio / 100;
+        let stack_width = screen.width - master_width;
+        // the left column only gets space when there are at least two stack tiles
+        let left_width = if tiles - masters < 2 {
+            0
+        } else {
+            stack_width * self.left_ratio / 100
+        };
+        [(0, left_width),
+         (left_width as i32, master_width),
+         ((left_width + master_width) as i32, stack_width - left_width)]
+    }
+}
+
+impl Default for CenteredMasterLayout {
+    fn default() -> CenteredMasterLayout {
+        CenteredMasterLayout::new()
+    }
+}
+
+impl MasterTrait for CenteredMasterLayout {
+    fn get_master_ratio(&self) -> u32 {
+        self.master.get_master_ratio()
+    }
+
+    fn get_master_count(&self) -> usize {
+        self.master.get_master_count()
+    }
+
+    fn shrink_master(&mut self) {
+        self.master.shrink_master()
+    }
+
+    fn expand_master(&mut self) {
+        self.master.expand_master()
+    }
+
+    fn inc_master(&mut self) {
+        self.master.inc_master()
+    }
+
+    fn dec_master(&mut self) {
+        self.master.dec_master()
+    }
+}
+
+impl TilingLayout for CenteredMasterLayout {
+    type Error = StandardError;
+
+    fn get_master_window(&self, tiles: &VecDeque<Window>) -> Option<Window> {
+        tiles.front().copied()
+    }
+
+    fn swap_with_master(&mut self,
+                        window: Window,
+                        tiles: &mut VecDeque<Window>)
+                        -> Result<(), Self::Error> {
+        swap_with_front(window, tiles)
+    }
+
+    fn swap_windows(&mut self, window: Window, dir: PrevOrNext, tiles: &mut VecDeque<Window>) {
+        swap_with_neighbour(window, dir, tiles)
+    }
+
+    fn get_window_geometry(&self,
+                           window: Window,
+                           screen: &Screen,
+                           tiles: &VecDeque<Window>)
+                           -> Result<Geometry, Self::Error> {
+        let masters = cmp::min(self.master.count, tiles.len());
+        let columns = self.get_columns(screen, masters, tiles.len());
+        match tiles.iter().position(|w| *w == window) {
+            None => Err(StandardError::UnknownWindow(window)),
+            Some(index) => {
+                // the column of the tile, its index in that column and the size of that column
+                let (column, row, rows) = if index < masters {
+                    (1, index, masters)
+                } else {
+                    let stack_index = index - masters;
+                    let stack = tiles.len() - masters;
+                    // the stack tiles alternate between the right and the left column
+                    match stack_index % 2 {
+                        0 => (2, stack_index / 2, stack.div_ceil(2)),
+                        _ => (0, stack_index / 2, stack / 2),
+                    }
+                };
+                let (x, width) = columns[column];
+                let (y, height) = split(screen.height, rows, row);
+                Ok(Geometry {
+                    x,
+                    y: y as i32,
+                    width,
+                    height,
+                })
+            }
+        }
+    }
+
+    /// Handles the messages to adjust the master area
+    fn handle_message(&mut self, message: LayoutMessage) -> bool {
+        match message {
+            LayoutMessage::ShrinkMaster => self.shrink_master(),
+            LayoutMessage::ExpandMaster => self.expand_master(),
+            LayoutMessage::IncMaster => self.inc_master(),
+            LayoutMessage::DecMaster => self.dec_master(),
+            _ => return false,
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod centered_master_layout_tests {
+    use super::CenteredMasterLayout;
+    use wm_common::{LayoutManager, LayoutMessage, Manager, MinimiseTrait,
+                    TilingLayout};
+    use a_fullscreen_wm::FocusManager;
+    use c_floating_windows::FloatOrTileManager;
+    use d_minimising_windows::MinimiseManager;
+    use std::collections::VecDeque;
+    use cplwm_api::types::*;
+
+    static SCREEN1: Screen = Screen {
+        width: 800,
+        height: 600,
+    };
+
+    static SCREEN2: Screen = Screen {
+        width: 301,
+        height: 401,
+    };
+
+    static SOME_GEOM: Geometry = Geometry {
+        x: 10,
+        y: 10,
+        width: 100,
+        height: 100,
+    };
+
+    fn tiles(amount: Window) -> VecDeque<Window> {
+        (1..amount + 1).collect()
+    }
+
+    #[test]
+    fn test_centered_master_layout_no_window() {
+        let layout = CenteredMasterLayout::new();
+        let tiles = VecDeque::new();
+
+        // make sure there is no geometry.
+        assert!(layout.get_window_geometry(1, &SCREEN1, &tiles).is_err());
+        assert_eq!(None, layout.get_master_window(&tiles));
+    }
+
+    #[test]
+    fn test_centered_master_layout_one_window() {
+        let layout = CenteredMasterLayout::new();
+        let tiles = tiles(1);
+
+        assert_eq!(SCREEN1.to_geometry(),
+                   layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap());
+    }
+
+    #[test]
+    fn test_centered_master_layout_two_windows() {
+        let layout = CenteredMasterLayout::new();
+        let tiles = tiles(2);
+
+        // without tiles in the left column, the master starts at the left edge
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 400,
+                       height: 600,
+                   },
+                   layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 400,
+                       y: 0,
+                       width: 400,
+                       height: 600,
+                   },
+                   layout.get_window_geometry(2, &SCREEN1, &tiles).ok().unwrap());
+    }
+
+    #[test]
+    fn test_centered_master_layout_multiple_windows() {
+        let layout = CenteredMasterLayout::new();
+        let tiles = tiles(5);
+
+        assert_eq!(Geometry {
+                       x: 200,
+                       y: 0,
+                       width: 400,
+                       height: 600,
+                   },
+                   layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap());
+
+        // the stack tiles alternate between the right and the left column
+        assert_eq!(Geometry {
+                       x: 600,
+                       y: 0,
+                       width: 200,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(2, &SCREEN1, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 200,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(3, &SCREEN1, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 600,
+                       y: 300,
+                       width: 200,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(4, &SCREEN1, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 300,
+                       width: 200,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(5, &SCREEN1, &tiles).ok().unwrap());
+
+        // any other window should return an error
+        assert!(layout.get_window_geometry(6, &SCREEN1, &tiles).is_err());
+    }
+
+    // test to see this layout gives the remaining pixels of irregular screens to the right column
+    #[test]
+    fn test_centered_master_layout_multiple_windows_irregular_screen() {
+        let layout = CenteredMasterLayout::new();
+        let tiles = tiles(4);
+
+        assert_eq!(Geometry {
+                       x: 75,
+                       y: 0,
+                       width: 150,
+                       height: 401,
+                   },
+                   layout.get_window_geometry(1, &SCREEN2, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 225,
+                       y: 0,
+                       width: 76,
+                       height: 201,
+                   },
+                   layout.get_window_geometry(2, &SCREEN2, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 75,
+                       height: 401,
+                   },
+                   layout.get_window_geometry(3, &SCREEN2, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 225,
+                       y: 201,
+                       width: 76,
+                       height: 200,
+                   },
+                   layout.get_window_geometry(4, &SCREEN2, &tiles).ok().unwrap());
+    }
+
+    #[test]
+    fn test_centered_master_layout_column_widths() {
+        let mut layout = CenteredMasterLayout::new();
+        layout.master.ratio = 60;
+        layout.left_ratio = 25;
+        let tiles = tiles(3);
+
+        assert_eq!(Geometry {
+                       x: 80,
+                       y: 0,
+                       width: 480,
+                       height: 600,
+                   },
+                   layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 560,
+                       y: 0,
+                       width: 240,
+                       height: 600,
+                   },
+                   layout.get_window_geometry(2, &SCREEN1, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 80,
+                       height: 600,
+                   },
+                   layout.get_window_geometry(3, &SCREEN1, &tiles).ok().unwrap());
+
+        assert!(layout.handle_message(LayoutMessage::ShrinkMaster));
+        assert_eq!(55, layout.master.ratio);
+        assert!(layout.handle_message(LayoutMessage::ExpandMaster));
+        assert_eq!(60, layout.master.ratio);
+    }
+
+    #[test]
+    fn test_centered_master_layout_multiple_masters() {
+        let mut layout = CenteredMasterLayout::new();
+        assert!(layout.handle_message(LayoutMessage::IncMaster));
+        let tiles = tiles(3);
+
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 400,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 300,
+                       width: 400,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(2, &SCREEN1, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 400,
+                       y: 0,
+                       width: 400,
+                       height: 600,
+                   },
+                   layout.get_window_geometry(3, &SCREEN1, &tiles).ok().unwrap());
+
+        assert!(layout.handle_message(LayoutMessage::DecMaster));
+        assert!(layout.handle_message(LayoutMessage::DecMaster));
+        assert_eq!(1, layout.master.count);
+    }
+
+    #[test]
+    fn test_centered_master_layout_in_float_manager() {
+        let mut manager = FloatOrTileManager::new(SCREEN1, CenteredMasterLayout::new());
+        assert!(manager.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+        assert!(manager.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+        assert!(manager.add_window(WindowWithInfo::new_float(3, SOME_GEOM)).is_ok());
+        assert!(manager.add_window(WindowWithInfo::new_tiled(4, SOME_GEOM)).is_ok());
+
+        let layout = manager.get_window_layout();
+        assert_eq!(4, layout.len());
+        assert!(layout.contains(&(1,
+                                  Geometry {
+                                      x: 200,
+                                      y: 0,
+                                      width: 400,
+                                      height: 600,
+                                  })));
+        assert!(layout.contains(&(4,
+                                  Geometry {
+                                      x: 0,
+                                      y: 0,
+                                      width: 200,
+                                      height: 600,
+                                  })));
+        assert!(layout.contains(&(3, SOME_GEOM)));
+
+        // removing the only tile of the left column gives its space to the right column
+        assert!(manager.remove_window(4).is_ok());
+        assert!(manager.get_window_layout().contains(&(2,
+                                                       Geometry {
+                                                           x: 400,
+                                                           y: 0,
+                                                           width: 400,
+                                                           height: 600,
+                                                       })));
+    }
+
+    #[test]
+    fn test_centered_master_layout_in_minimise_manager() {
+        let mut focus_manager = FocusManager::new();
+        let mut manager =
+            MinimiseManager::new(FloatOrTileManager::new(SCREEN1, CenteredMasterLayout::new()));
+        assert!(manager.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+        assert!(manager.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+        assert!(manager.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).is_ok());
+
+        assert!(manager.get_window_layout().contains(&(1,
+                                                       Geometry {
+                                                           x: 200,
+                                                           y: 0,
+                                                           width: 400,
+                                                           height: 600,
+                                                       })));
+
+        assert!(manager.toggle_minimised(1, &mut focus_manager).is_ok());
+        assert_eq!(vec![(2,
+                         Geometry {
+                            x: 0,
+                            y: 0,
+                            width: 400,
+                            height: 600,
+                        }),
+                        (3,
+                         Geometry {
+                            x: 400,
+                            y: 0,
+                            width: 400,
+                            height: 600,
+                        })],
+                   manager.get_window_layout());
+    }
+}
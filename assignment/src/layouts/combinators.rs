@@ -0,0 +1,877 @@
+//! Layouts which are built from other layouts, like the GapLayout.
+//!
+//! MirrorLayout swaps the axes of a layout, ReflectLayout flips a layout horizontally or
+//! vertically and CombineLayout splits the screen between two layouts. This way the VerticalLayout
+//! gives "master on top" when mirrored and "master on the right" when reflected horizontally,
+//! without a TilingLayout of its own.
+//!
+//! ```text
+//!   Mirror        Reflect       Combine
+//! +--------+    +---+----+    +--+--+--+
+//! |   1    |    | 2 |    |    |  |  |  |
+//! +---+----+    +---+ 1  |    |1 |2 |3 |
+//! | 2 | 3  |    | 3 |    |    |  |  |  |
+//! +---+----+    +---+----+    +--+--+--+
+//! ```
+
+use cplwm_api::types::{Geometry, PrevOrNext, Screen, Tab, Window};
+use wm_common::{LayoutMessage, MasterTrait, TilingLayout};
+use wm_common::error::StandardError;
+use layouts::{swap_with_front, swap_with_neighbour, swap_with_tile};
+use layouts::bsp::Orientation;
+use std::collections::VecDeque;
+
+/// Swap the horizontal and the vertical axis of the geometry
+fn transpose(geometry: Geometry) -> Geometry {
+    Geometry {
+        x: geometry.y,
+        y: geometry.x,
+        width: geometry.height,
+        height: geometry.width,
+    }
+}
+
+/// The screen with its width and height swapped
+fn transpose_screen(screen: &Screen) -> Screen {
+    Screen {
+        width: screen.height,
+        height: screen.width,
+    }
+}
+
+/// A layout which swaps the axes of the wrapped layout, columns become rows and rows become
+/// columns. The tab bar at the top of a layout becomes a column on the left.
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct MirrorLayout<T: TilingLayout> {
+    /// the mirrored layout
+    pub tiling_layout: T,
+}
+
+impl<T: TilingLayout> MirrorLayout<T> {
+    /// Mirror the given layout
+    pub fn new(tiling_layout: T) -> MirrorLayout<T> {
+        MirrorLayout { tiling_layout }
+    }
+}
+
+impl<T: TilingLayout + MasterTrait> MasterTrait for MirrorLayout<T> {
+    fn get_master_ratio(&self) -> u32 {
+        self.tiling_layout.get_master_ratio()
+    }
+
+    fn get_master_count(&self) -> usize {
+        self.tiling_layout.get_master_count()
+    }
+
+    fn shrink_master(&mut self) {
+        self.tiling_layout.shrink_master()
+    }
+
+    fn expand_master(&mut self) {
+        self.tiling_layout.expand_master()
+    }
+
+    fn inc_master(&mut self) {
+        self.tiling_layout.inc_master()
+    }
+
+    fn dec_master(&mut self) {
+        self.tiling_layout.dec_master()
+    }
+}
+
+impl<T: TilingLayout> TilingLayout for MirrorLayout<T> {
+    // use the same type for Error as the wrapped layout
+    type Error = T::Error;
+
+    fn get_master_window(&self, tiles: &VecDeque<Window>) -> Option<Window> {
+        self.tiling_layout.get_master_window(tiles)
+    }
+
+    fn swap_with_master(&mut self,
+                        window: Window,
+                        tiles: &mut VecDeque<Window>)
+                        -> Result<(), Self::Error> {
+        self.tiling_layout.swap_with_master(window, tiles)
+    }
+
+    fn swap_windows(&mut self, window: Window, dir: PrevOrNext, tiles: &mut VecDeque<Window>) {
+        self.tiling_layout.swap_windows(window, dir, tiles)
+    }
+
+    fn swap_tiles(&mut self, window: Window, other: Window, tiles: &mut VecDeque<Window>) {
+        self.tiling_layout.swap_tiles(window, other, tiles)
+    }
+
+    /// The wrapped layout lays out the tiles on the transposed screen
+    fn get_window_geometry(&self,
+                           window: Window,
+                           screen: &Screen,
+                           tiles: &VecDeque<Window>)
+                           -> Result<Geometry, Self::Error> {
+        self.tiling_layout
+            .get_window_geometry(window, &transpose_screen(screen), tiles)
+            .map(transpose)
+    }
+
+    fn get_window_geometries(&self,
+                             screen: &Screen,
+                             tiles: &VecDeque<Window>)
+                             -> Vec<(Window, Geometry)> {
+        self.tiling_layout
+            .get_window_geometries(&transpose_screen(screen), tiles)
+            .into_iter()
+            .map(|(window, geometry)| (window, transpose(geometry)))
+            .collect()
+    }
+
+    fn handle_message(&mut self, message: LayoutMessage) -> bool {
+        self.tiling_layout.handle_message(message)
+    }
+
+    fn focus_shifted(&mut self, window: Option<Window>) {
+        self.tiling_layout.focus_shifted(window)
+    }
+
+    fn tile_added(&mut self, window: Window) {
+        self.tiling_layout.tile_added(window)
+    }
+
+    fn tile_removed(&mut self, window: Window) {
+        self.tiling_layout.tile_removed(window)
+    }
+
+    /// The wrapped layout lays out the tiles on the transposed screen
+    fn screen_resized(&mut self, screen: &Screen) {
+        self.tiling_layout.screen_resized(&transpose_screen(screen))
+    }
+
+    fn get_stacking_order(&self, tiles: &VecDeque<Window>) -> Vec<Window> {
+        self.tiling_layout.get_stacking_order(tiles)
+    }
+
+    fn get_tabs(&self, screen: &Screen, tiles: &VecDeque<Window>) -> Vec<Tab> {
+        self.tiling_layout
+            .get_tabs(&transpose_screen(screen), tiles)
+            .into_iter()
+            .map(|tab| Tab { geometry: transpose(tab.geometry), ..tab })
+            .collect()
+    }
+
+    fn get_focus_neighbour(&self,
+                           window: Window,
+                           dir: PrevOrNext,
+                           tiles: &VecDeque<Window>)
+                           -> Option<Window> {
+        self.tiling_layout.get_focus_neighbour(window, dir, tiles)
+    }
+}
+
+/// The way a ReflectLayout flips its layout
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq)]
+pub enum Reflection {
+    /// left and right swap
+    Horizontal,
+    /// top and bottom swap
+    Vertical,
+}
+
+/// A layout which flips the wrapped layout horizontally or vertically
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct ReflectLayout<T: TilingLayout> {
+    /// the reflected layout
+    pub tiling_layout: T,
+    /// how the layout is flipped
+    pub reflection: Reflection,
+}
+
+impl<T: TilingLayout> ReflectLayout<T> {
+    /// Flip the given layout in the given way
+    pub fn new(tiling_layout: T, reflection: Reflection) -> ReflectLayout<T> {
+        ReflectLayout {
+            tiling_layout,
+            reflection,
+        }
+    }
+
+    /// The geometry flipped within the given screen
+    fn reflect(&self, geometry: Geometry, screen: &Screen) -> Geometry {
+        match self.reflection {
+            Reflection::Horizontal => {
+                Geometry {
+                    x: screen.width as i32 - geometry.x - geometry.width as i32,
+                    ..geometry
+                }
+            }
+            Reflection::Vertical => {
+                Geometry {
+                    y: screen.height as i32 - geometry.y - geometry.height as i32,
+                    ..geometry
+                }
+            }
+        }
+    }
+}
+
+impl<T: TilingLayout + MasterTrait> MasterTrait for ReflectLayout<T> {
+    fn get_master_ratio(&self) -> u32 {
+        self.tiling_layout.get_master_ratio()
+    }
+
+    fn get_master_count(&self) -> usize {
+        self.tiling_layout.get_master_count()
+    }
+
+    fn shrink_master(&mut self) {
+        self.tiling_layout.shrink_master()
+    }
+
+    fn expand_master(&mut self) {
+        self.tiling_layout.expand_master()
+    }
+
+    fn inc_master(&mut self) {
+        self.tiling_layout.inc_master()
+    }
+
+    fn dec_master(&mut self) {
+        self.tiling_layout.dec_master()
+    }
+}
+
+impl<T: TilingLayout> TilingLayout for ReflectLayout<T> {
+    // use the same type for Error as the wrapped layout
+    type Error = T::Error;
+
+    fn get_master_window(&self, tiles: &VecDeque<Window>) -> Option<Window> {
+        self.tiling_layout.get_master_window(tiles)
+    }
+
+    fn swap_with_master(&mut self,
+                        window: Window,
+                        tiles: &mut VecDeque<Window>)
+                        -> Result<(), Self::Error> {
+        self.tiling_layout.swap_with_master(window, tiles)
+    }
+
+    fn swap_windows(&mut self, window: Window, dir: PrevOrNext, tiles: &mut VecDeque<Window>) {
+        self.tiling_layout.swap_windows(window, dir, tiles)
+    }
+
+    fn swap_tiles(&mut self, window: Window, other: Window, tiles: &mut VecDeque<Window>) {
+        self.tiling_layout.swap_tiles(window, other, tiles)
+    }
+
+    fn get_window_geometry(&self,
+                           window: Window,
+                           screen: &Screen,
+                           tiles: &VecDeque<Window>)
+                           -> Result<Geometry, Self::Error> {
+        self.tiling_layout
+            .get_window_geometry(window, screen, tiles)
+            .map(|geometry| self.reflect(geometry, screen))
+    }
+
+    fn get_window_geometries(&self,
+                             screen: &Screen,
+                             tiles: &VecDeque<Window>)
+                             -> Vec<(Window, Geometry)> {
+        self.tiling_layout
+            .get_window_geometries(screen, tiles)
+            .into_iter()
+            .map(|(window, geometry)| (window, self.reflect(geometry, screen)))
+            .collect()
+    }
+
+    fn handle_message(&mut self, message: LayoutMessage) -> bool {
+        self.tiling_layout.handle_message(message)
+    }
+
+    fn focus_shifted(&mut self, window: Option<Window>) {
+        self.tiling_layout.focus_shifted(window)
+    }
+
+    fn tile_added(&mut self, window: Window) {
+        self.tiling_layout.tile_added(window)
+    }
+
+    fn tile_removed(&mut self, window: Window) {
+        self.tiling_layout.tile_removed(window)
+    }
+
+    fn screen_resized(&mut self, screen: &Screen) {
+        self.tiling_layout.screen_resized(screen)
+    }
+
+    fn get_stacking_order(&self, tiles: &VecDeque<Window>) -> Vec<Window> {
+        self.tiling_layout.get_stacking_order(tiles)
+    }
+
+    /// The tabs are flipped with the tiles, a vertical reflection puts the tab bar at the bottom
+    fn get_tabs(&self, screen: &Screen, tiles: &VecDeque<Window>) -> Vec<Tab> {
+        self.tiling_layout
+            .get_tabs(screen, tiles)
+            .into_iter()
+            .map(|tab| Tab { geometry: self.reflect(tab.geometry, screen), ..tab })
+            .collect()
+    }
+
+    fn get_focus_neighbour(&self,
+                           window: Window,
+                           dir: PrevOrNext,
+                           tiles: &VecDeque<Window>)
+                           -> Option<Window> {
+        self.tiling_layout.get_focus_neighbour(window, dir, tiles)
+    }
+}
+
+/// A layout which splits the screen in two parts with their own layout. The first tiles, up to
+/// first_count, go to the first part, the other tiles go to the second part. When one of the parts
+/// has no tiles, the other part takes the whole screen.
+///
+/// The layout of a part is only told about the tiles of its part and about the area of its part,
+/// so layouts which keep their own state about the tiles, like the BspLayout, can lay out a part.
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct CombineLayout<A: TilingLayout, B: TilingLayout> {
+    /// the layout of the first part, on the left or on top
+    pub first_layout: A,
+    /// the layout of the second part, on the right or at the bottom
+    pub second_layout: B,
+    /// how the screen is split
+    pub orientation: Orientation,
+    /// percentage of the screen taken by the first part
+    pub ratio: u32,
+    /// maximum number of tiles in the first part
+    pub first_count: usize,
+    /// the last screen the layout was told about, for the methods which get no screen
+    pub screen: Screen,
+    /// the tiles the layout was told about, for the methods which get no tiles
+    pub tiles: VecDeque<Window>,
+}
+
+impl<A, B> CombineLayout<A, B>
+    where A: TilingLayout<Error = StandardError>,
+          B: TilingLayout<Error = StandardError>
+{
+    /// Split the screen between the given layouts, the first part takes ratio percent of the
+    /// screen and at most first_count tiles. The screen is unknown until the layout is given to a
+    /// TileManager.
+    pub fn new(first_layout: A,
+               second_layout: B,
+               orientation: Orientation,
+               ratio: u32,
+               first_count: usize)
+               -> CombineLayout<A, B> {
+        CombineLayout {
+            first_layout,
+            second_layout,
+            orientation,
+            ratio,
+            first_count,
+            screen: Screen {
+                width: 0,
+                height: 0,
+            },
+            tiles: VecDeque::new(),
+        }
+    }
+
+    /// Tell the layouts of the parts about the change from the tiles the layout knows to the
+    /// given tiles and about the new areas of the parts.
+    fn update_tiles(&mut self, tiles: VecDeque<Window>) {
+        let (old_first, old_second) = self.split_tiles(&self.tiles);
+        let (first, second) = self.split_tiles(&tiles);
+        update_part(&mut self.first_layout, &old_first, &first);
+        update_part(&mut self.second_layout, &old_second, &second);
+        let old_areas = self.split_screen(&self.screen, &old_first, &old_second);
+        let areas = self.split_screen(&self.screen, &first, &second);
+        self.tiles = tiles;
+        if areas != old_areas {
+            self.resize_parts();
+        }
+    }
+
+    /// Tell the layouts of the parts about the areas of their parts
+    fn resize_parts(&mut self) {
+        let (first_tiles, second_tiles) = self.split_tiles(&self.tiles);
+        let (first_area, second_area) = self.split_screen(&self.screen,
+                                                          &first_tiles,
+                                                          &second_tiles);
+        self.first_layout.screen_resized(&area_screen(first_area));
+        self.second_layout.screen_resized(&area_screen(second_area));
+    }
+
+    /// The tiles of the first and of the second part
+    fn split_tiles(&self, tiles: &VecDeque<Window>) -> (VecDeque<Window>, VecDeque<Window>) {
+        (tiles.iter().take(self.first_count).copied().collect(),
+         tiles.iter().skip(self.first_count).copied().collect())
+    }
+
+    /// The areas of the first and the second part for the given tiles of both parts
+    fn split_screen(&self,
+                    screen: &Screen,
+                    first_tiles: &VecDeque<Window>,
+                    second_tiles: &VecDeque<Window>)
+                    -> (Geometry, Geometry) {
+        let area = screen.to_geometry();
+        if first_tiles.is_empty() || second_tiles.is_empty() {
+            return (area, area);
+        }
+        match self.orientation {
+            Orientation::Horizontal => {
+                let width = area.width * self.ratio / 100;
+                (Geometry { width, ..area },
+                 Geometry {
+                    x: width as i32,
+                    width: area.width - width,
+                    ..area
+                })
+            }
+            Orientation::Vertical => {
+                let height = area.height * self.ratio / 100;
+                (Geometry { height, ..area },
+                 Geometry {
+                    y: height as i32,
+                    height: area.height - height,
+                    ..area
+                })
+            }
+        }
+    }
+}
+
+/// Tell the layout of a part about the change from the old to the new tiles of the part. A tile
+/// which enters the part takes the place of a tile which leaves it, if there is one, the other
+/// tiles which enter are added and the other tiles which leave are removed.
+fn update_part<T>(layout: &mut T, old_tiles: &VecDeque<Window>, new_tiles: &VecDeque<Window>)
+    where T: TilingLayout
+{
+    let left: Vec<Window> = old_tiles.iter()
+        .filter(|w| !new_tiles.contains(w)).copied()
+        .collect();
+    let entered = new_tiles.iter().filter(|w| !old_tiles.contains(w));
+    let mut tiles = old_tiles.clone();
+    for (index, &window) in entered.enumerate() {
+        tiles.push_back(window);
+        layout.tile_added(window);
+        match left.get(index) {
+            None => {}
+            Some(&other) => layout.swap_tiles(window, other, &mut tiles),
+        }
+    }
+    for window in left {
+        layout.tile_removed(window);
+    }
+}
+
+/// The screen of a layout which lays out its tiles in the given area
+fn area_screen(area: Geometry) -> Screen {
+    Screen {
+        width: area.width,
+        height: area.height,
+    }
+}
+
+/// The geometry moved from the screen of a layout to the area of that layout
+fn move_to_area(geometry: Geometry, area: Geometry) -> Geometry {
+    Geometry {
+        x: geometry.x + area.x,
+        y: geometry.y + area.y,
+        ..geometry
+    }
+}
+
+impl<A, B> TilingLayout for CombineLayout<A, B>
+    where A: TilingLayout<Error = StandardError>,
+          B: TilingLayout<Error = StandardError>
+{
+    type Error = StandardError;
+
+    /// The master of the first part, or the master of the second part when the first part has no
+    /// tiles.
+    fn get_master_window(&self, tiles: &VecDeque<Window>) -> Option<Window> {
+        let (first_tiles, second_tiles) = self.split_tiles(tiles);
+        self.first_layout
+            .get_master_window(&first_tiles)
+            .or_else(|| self.second_layout.get_master_window(&second_tiles))
+    }
+
+    /// The window becomes the first tile
+    fn swap_with_master(&mut self,
+                        window: Window,
+                        tiles: &mut VecDeque<Window>)
+                        -> Result<(), Self::Error> {
+        swap_with_front(window, tiles).map(|_| self.update_tiles(tiles.clone()))
+    }
+
+    /// Swap with the previous or next tile, a tile can move to the other part this way
+    fn swap_windows(&mut self, window: Window, dir: PrevOrNext, tiles: &mut VecDeque<Window>) {
+        swap_with_neighbour(window, dir, tiles);
+        self.update_tiles(tiles.clone())
+    }
+
+    /// The layout of a part swaps two tiles of that part, tiles of different parts swap their
+    /// places in the tiles.
+    fn swap_tiles(&mut self, window: Window, other: Window, tiles: &mut VecDeque<Window>) {
+        let (mut first_tiles, mut second_tiles) = self.split_tiles(tiles);
+        let same_part = if first_tiles.contains(&window) && first_tiles.contains(&other) {
+            self.first_layout.swap_tiles(window, other, &mut first_tiles);
+            true
+        } else if second_tiles.contains(&window) && second_tiles.contains(&other) {
+            self.second_layout.swap_tiles(window, other, &mut second_tiles);
+            true
+        } else {
+            false
+        };
+        if same_part {
+            *tiles = first_tiles.into_iter().chain(second_tiles).collect();
+        } else {
+            swap_with_tile(window, other, tiles);
+        }
+        self.update_tiles(tiles.clone())
+    }
+
+    fn get_window_geometry(&self,
+                           window: Window,
+                           screen: &Screen,
+                           tiles: &VecDeque<Window>)
+                           -> Result<Geometry, Self::Error> {
+        let (first_tiles, second_tiles) = self.split_tiles(tiles);
+        let (first_area, second_area) = self.split_screen(screen, &first_tiles, &second_tiles);
+        if first_tiles.contains(&window) {
+            self.first_layout
+                .get_window_geometry(window, &area_screen(first_area), &first_tiles)
+                .map(|geometry| move_to_area(geometry, first_area))
+        } else {
+            self.second_layout
+                .get_window_geometry(window, &area_screen(second_area), &second_tiles)
+                .map(|geometry| move_to_area(geometry, second_area))
+        }
+    }
+
+    fn get_window_geometries(&self,
+                             screen: &Screen,
+                             tiles: &VecDeque<Window>)
+                             -> Vec<(Window, Geometry)> {
+        let (first_tiles, second_tiles) = self.split_tiles(tiles);
+        let (first_area, second_area) = self.split_screen(screen, &first_tiles, &second_tiles);
+        let first_geometries = self.first_layout
+            .get_window_geometries(&area_screen(first_area), &first_tiles)
+            .into_iter()
+            .map(|(window, geometry)| (window, move_to_area(geometry, first_area)));
+        let second_geometries = self.second_layout
+            .get_window_geometries(&area_screen(second_area), &second_tiles)
+            .into_iter()
+            .map(|(window, geometry)| (window, move_to_area(geometry, second_area)));
+        first_geometries.chain(second_geometries).collect()
+    }
+
+    /// Both layouts get the message, it is handled when one of them handles it
+    fn handle_message(&mut self, message: LayoutMessage) -> bool {
+        let first = self.first_layout.handle_message(message);
+        let second = self.second_layout.handle_message(message);
+        first || second
+    }
+
+    fn focus_shifted(&mut self, window: Option<Window>) {
+        self.first_layout.focus_shifted(window);
+        self.second_layout.focus_shifted(window);
+    }
+
+    fn tile_added(&mut self, window: Window) {
+        let mut tiles = self.tiles.clone();
+        tiles.push_back(window);
+        self.update_tiles(tiles)
+    }
+
+    /// The first tile of the second part moves to the first part when a tile of the first part is
+    /// removed
+    fn tile_removed(&mut self, window: Window) {
+        let mut tiles = self.tiles.clone();
+        tiles.retain(|w| *w != window);
+        self.update_tiles(tiles)
+    }
+
+    fn screen_resized(&mut self, screen: &Screen) {
+        self.screen = *screen;
+        self.resize_parts()
+    }
+
+    /// The layout of the part of the window decides within that part
+    fn get_focus_neighbour(&self,
+                           window: Window,
+                           dir: PrevOrNext,
+                           tiles: &VecDeque<Window>)
+                           -> Option<Window> {
+        let (first_tiles, second_tiles) = self.split_tiles(tiles);
+        if first_tiles.contains(&window) {
+            self.first_layout.get_focus_neighbour(window, dir, &first_tiles)
+        } else {
+            self.second_layout.get_focus_neighbour(window, dir, &second_tiles)
+        }
+    }
+
+    /// The parts do not overlap, each part keeps the stacking order of its layout
+    fn get_stacking_order(&self, tiles: &VecDeque<Window>) -> Vec<Window> {
+        let (first_tiles, second_tiles) = self.split_tiles(tiles);
+        let mut windows = self.first_layout.get_stacking_order(&first_tiles);
+        windows.extend(self.second_layout.get_stacking_order(&second_tiles));
+        windows
+    }
+
+    fn get_tabs(&self, screen: &Screen, tiles: &VecDeque<Window>) -> Vec<Tab> {
+        let (first_tiles, second_tiles) = self.split_tiles(tiles);
+        let (first_area, second_area) = self.split_screen(screen, &first_tiles, &second_tiles);
+        let first_tabs = self.first_layout
+            .get_tabs(&area_screen(first_area), &first_tiles)
+            .into_iter()
+            .map(|tab| Tab { geometry: move_to_area(tab.geometry, first_area), ..tab });
+        let second_tabs = self.second_layout
+            .get_tabs(&area_screen(second_area), &second_tiles)
+            .into_iter()
+            .map(|tab| Tab { geometry: move_to_area(tab.geometry, second_area), ..tab });
+        first_tabs.chain(second_tabs).collect()
+    }
+}
+
+#[cfg(test)]
+mod combinator_layout_tests {
+    use super::{CombineLayout, MirrorLayout, ReflectLayout, Reflection};
+    use wm_common::{LayoutManager, LayoutMessage, Manager, MasterTrait, TilingLayout, TilingTrait};
+    use b_tiling_wm::{TileManager, VerticalLayout};
+    use f_gaps::GapLayout;
+    use layouts::bsp::{BspLayout, Orientation};
+    use layouts::monocle::MonocleLayout;
+    use std::collections::VecDeque;
+    use cplwm_api::types::*;
+
+    static SCREEN1: Screen = Screen {
+        width: 800,
+        height: 600,
+    };
+
+    static SCREEN2: Screen = Screen {
+        width: 301,
+        height: 401,
+    };
+
+    static SOME_GEOM: Geometry = Geometry {
+        x: 10,
+        y: 10,
+        width: 100,
+        height: 100,
+    };
+
+    fn tiles(amount: Window) -> VecDeque<Window> {
+        (1..amount + 1).collect()
+    }
+
+    fn geometries<T: TilingLayout>(layout: &T,
+                                   screen: &Screen,
+                                   tiles: &VecDeque<Window>)
+                                   -> Vec<Geometry> {
+        tiles.iter()
+            .map(|w| layout.get_window_geometry(*w, screen, tiles).ok().unwrap())
+            .collect()
+    }
+
+    fn geometry(x: i32, y: i32, width: u32, height: u32) -> Geometry {
+        Geometry {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn test_mirror_layout_master_on_top() {
+        let layout = MirrorLayout::new(VerticalLayout::new());
+        let tiles = tiles(3);
+
+        assert_eq!(vec![geometry(0, 0, 800, 300),
+                        geometry(0, 300, 400, 300),
+                        geometry(400, 300, 400, 300)],
+                   geometries(&layout, &SCREEN1, &tiles));
+        assert!(layout.get_window_geometry(4, &SCREEN1, &tiles).is_err());
+        assert_eq!(Some(1), layout.get_master_window(&tiles));
+
+        // mirroring twice gives the original layout
+        let layout = MirrorLayout::new(layout);
+        assert_eq!(geometries(&VerticalLayout::new(), &SCREEN2, &tiles),
+                   geometries(&layout, &SCREEN2, &tiles));
+    }
+
+    #[test]
+    fn test_mirror_layout_forwards() {
+        let mut layout = MirrorLayout::new(VerticalLayout::new());
+        let mut tiles = tiles(3);
+
+        assert!(layout.handle_message(LayoutMessage::ShrinkMaster));
+        assert_eq!(45, layout.get_master_ratio());
+        assert_eq!(geometry(0, 0, 800, 270),
+                   layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap());
+
+        assert!(layout.swap_with_master(3, &mut tiles).is_ok());
+        assert_eq!(Some(3), layout.get_master_window(&tiles));
+        assert!(layout.swap_with_master(4, &mut tiles).is_err());
+    }
+
+    #[test]
+    fn test_mirror_layout_tabs() {
+        let layout = MirrorLayout::new(MonocleLayout::tabbed(20));
+        let tiles = tiles(2);
+
+        // the tab bar becomes a column on the left
+        assert_eq!(geometry(20, 0, 780, 600),
+                   layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap());
+        assert_eq!(vec![geometry(0, 0, 20, 300), geometry(0, 300, 20, 300)],
+                   layout.get_tabs(&SCREEN1, &tiles)
+                       .iter()
+                       .map(|t| t.geometry)
+                       .collect::<Vec<Geometry>>());
+    }
+
+    #[test]
+    fn test_reflect_layout_master_on_right() {
+        let layout = ReflectLayout::new(VerticalLayout::new(), Reflection::Horizontal);
+        let tiles = tiles(3);
+
+        assert_eq!(vec![geometry(400, 0, 400, 600),
+                        geometry(0, 0, 400, 300),
+                        geometry(0, 300, 400, 300)],
+                   geometries(&layout, &SCREEN1, &tiles));
+        assert!(layout.get_window_geometry(4, &SCREEN1, &tiles).is_err());
+    }
+
+    // test to see the reflected tiles still cover irregular screens
+    #[test]
+    fn test_reflect_layout_vertical_irregular_screen() {
+        let layout = ReflectLayout::new(VerticalLayout::new(), Reflection::Vertical);
+        let tiles = tiles(3);
+
+        assert_eq!(vec![geometry(0, 0, 150, 401),
+                        geometry(150, 201, 151, 200),
+                        geometry(150, 0, 151, 201)],
+                   geometries(&layout, &SCREEN2, &tiles));
+    }
+
+    #[test]
+    fn test_reflect_layout_tabs() {
+        let layout = ReflectLayout::new(MonocleLayout::tabbed(20), Reflection::Vertical);
+        let tiles = tiles(1);
+
+        // the tab bar moves to the bottom
+        assert_eq!(geometry(0, 0, 800, 580),
+                   layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap());
+        assert_eq!(vec![geometry(0, 580, 800, 20)],
+                   layout.get_tabs(&SCREEN1, &tiles)
+                       .iter()
+                       .map(|t| t.geometry)
+                       .collect::<Vec<Geometry>>());
+    }
+
+    #[test]
+    fn test_combine_layout() {
+        let layout = CombineLayout::new(VerticalLayout::new(),
+                                        VerticalLayout::new(),
+                                        Orientation::Horizontal,
+                                        25,
+                                        1);
+
+        assert!(layout.get_window_geometry(1, &SCREEN1, &tiles(0)).is_err());
+        assert_eq!(None, layout.get_master_window(&tiles(0)));
+
+        // a part without tiles leaves the whole screen to the other part
+        assert_eq!(vec![SCREEN1.to_geometry()],
+                   geometries(&layout, &SCREEN1, &tiles(1)));
+
+        assert_eq!(vec![geometry(0, 0, 200, 600),
+                        geometry(200, 0, 300, 600),
+                        geometry(500, 0, 300, 600)],
+                   geometries(&layout, &SCREEN1, &tiles(3)));
+        assert_eq!(Some(1), layout.get_master_window(&tiles(3)));
+        assert!(layout.get_window_geometry(4, &SCREEN1, &tiles(3)).is_err());
+    }
+
+    #[test]
+    fn test_combine_layout_vertical_with_mirror() {
+        let mut layout = CombineLayout::new(MonocleLayout::tabbed(20),
+                                            MirrorLayout::new(VerticalLayout::new()),
+                                            Orientation::Vertical,
+                                            50,
+                                            2);
+        let mut tiles = tiles(4);
+
+        assert_eq!(vec![geometry(0, 20, 800, 280),
+                        geometry(0, 20, 800, 280),
+                        geometry(0, 300, 800, 150),
+                        geometry(0, 450, 800, 150)],
+                   geometries(&layout, &SCREEN1, &tiles));
+        assert_eq!(2, layout.get_tabs(&SCREEN1, &tiles).len());
+
+        // the monocle layout puts the focused tile on top, the other part keeps its order
+        layout.focus_shifted(Some(1));
+        assert_eq!(vec![2, 1, 3, 4], layout.get_stacking_order(&tiles));
+
+        // swapping moves the tile to the other part
+        layout.swap_windows(2, PrevOrNext::Next, &mut tiles);
+        assert_eq!(geometry(0, 300, 800, 150),
+                   layout.get_window_geometry(2, &SCREEN1, &tiles).ok().unwrap());
+        assert!(layout.swap_with_master(4, &mut tiles).is_ok());
+        assert_eq!(Some(4), layout.get_master_window(&tiles));
+
+        // only the VerticalLayout understands the message
+        assert!(layout.handle_message(LayoutMessage::ExpandMaster));
+        assert!(!layout.handle_message(LayoutMessage::Rotate));
+    }
+
+    #[test]
+    fn test_combinators_in_tile_manager() {
+        let reflected = ReflectLayout::new(MirrorLayout::new(VerticalLayout::new()),
+                                           Reflection::Vertical);
+        let layout = GapLayout::new(reflected, 5);
+        let mut manager = TileManager::new(SCREEN1, layout);
+        for window in 1..3 {
+            assert!(manager.add_window(WindowWithInfo::new_tiled(window, SOME_GEOM)).is_ok());
+        }
+
+        // master at the bottom
+        assert_eq!(vec![(1, geometry(5, 305, 790, 290)), (2, geometry(5, 5, 790, 290))],
+                   manager.get_window_layout());
+        assert!(manager.send_layout_message(LayoutMessage::ShrinkMaster));
+        assert_eq!(geometry(5, 335, 790, 260), manager.get_window_layout()[0].1);
+    }
+
+    #[test]
+    fn test_combine_layout_with_stateful_part() {
+        let layout = CombineLayout::new(VerticalLayout::new(),
+                                        BspLayout::new(),
+                                        Orientation::Horizontal,
+                                        50,
+                                        1);
+        let mut manager = TileManager::new(SCREEN1, layout);
+        for window in 1..4 {
+            assert!(manager.add_window(WindowWithInfo::new_tiled(window, SOME_GEOM)).is_ok());
+        }
+        // the BspLayout splits the second part between the tiles it was told about
+        assert_eq!(vec![(1, geometry(0, 0, 400, 600)),
+                        (2, geometry(400, 0, 200, 600)),
+                        (3, geometry(600, 0, 200, 600))],
+                   manager.get_window_layout());
+
+        // window 2 moves to the first part, so it leaves the BspLayout
+        assert!(manager.remove_window(1).is_ok());
+        assert_eq!(vec![(2, geometry(0, 0, 400, 600)), (3, geometry(400, 0, 400, 600))],
+                   manager.get_window_layout());
+
+        // a tile entering the second part takes the place of the tile leaving it
+        assert!(manager.add_window(WindowWithInfo::new_tiled(4, SOME_GEOM)).is_ok());
+        assert!(manager.swap_places(2, 4).is_ok());
+        assert!(manager.swap_places(2, 5).is_err());
+        assert_eq!(vec![(4, geometry(0, 0, 400, 600)),
+                        (3, geometry(400, 0, 200, 600)),
+                        (2, geometry(600, 0, 200, 600))],
+                   manager.get_window_layout());
+    }
+}
@@ -0,0 +1,358 @@
+//! A layout which chooses between two layouts with a condition on the tiles or the screen.
+//!
+//! Rules with more than two layouts nest ConditionalLayouts, e.g. "the whole screen for one tile,
+//! the VerticalLayout up to three tiles and a grid for more tiles" is
+//!
+//! ```text
+//! ConditionalLayout::new(LayoutCondition::MaxTiles(1),
+//!                        MonocleLayout::new(),
+//!                        ConditionalLayout::new(LayoutCondition::MaxTiles(3),
+//!                                               VerticalLayout::new(),
+//!                                               GridLayout::new()))
+//! ```
+//!
+//! A condition on the screen is checked again when the screen is resized, e.g. when it rotates.
+
+use cplwm_api::types::{Geometry, PrevOrNext, Screen, Tab, Window};
+use wm_common::{LayoutMessage, TilingLayout};
+use wm_common::error::StandardError;
+use std::collections::VecDeque;
+
+/// A condition on the tiles or on the screen
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq)]
+pub enum LayoutCondition {
+    /// there are at most the given amount of tiles
+    MaxTiles(usize),
+    /// the screen is higher than it is wide
+    Portrait,
+}
+
+impl LayoutCondition {
+    /// Whether the condition holds for the given amount of tiles on the given screen
+    pub fn holds(&self, screen: &Screen, tile_count: usize) -> bool {
+        match *self {
+            LayoutCondition::MaxTiles(amount) => tile_count <= amount,
+            LayoutCondition::Portrait => screen.height > screen.width,
+        }
+    }
+}
+
+/// The layout of a ConditionalLayout which lays out the tiles
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq)]
+pub enum Branch {
+    /// the layout used when the condition holds
+    WhenMet,
+    /// the layout used when the condition does not hold
+    Otherwise,
+}
+
+/// Layout which lays out the tiles with one layout when its condition holds and with another
+/// layout when it does not. Both layouts are told about the tiles, the focus and the screen, only
+/// the active layout handles messages.
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct ConditionalLayout<A: TilingLayout, B: TilingLayout> {
+    /// when to use the first layout
+    pub condition: LayoutCondition,
+    /// the layout used when the condition holds
+    pub when_met: A,
+    /// the layout used when the condition does not hold
+    pub otherwise: B,
+    /// the last screen the layout was told about, for the methods which get no screen
+    pub screen: Screen,
+    /// the amount of tiles the layout was told about, for the methods which get no tiles
+    pub tile_count: usize,
+}
+
+impl<A, B> ConditionalLayout<A, B>
+    where A: TilingLayout<Error = StandardError>,
+          B: TilingLayout<Error = StandardError>
+{
+    /// Use when_met when the condition holds, otherwise use otherwise. The screen is unknown
+    /// until the layout is given to a TileManager.
+    pub fn new(condition: LayoutCondition, when_met: A, otherwise: B) -> ConditionalLayout<A, B> {
+        ConditionalLayout {
+            condition,
+            when_met,
+            otherwise,
+            screen: Screen {
+                width: 0,
+                height: 0,
+            },
+            tile_count: 0,
+        }
+    }
+
+    /// The layout which lays out the tiles the layout was told about on the last screen the
+    /// layout was told about
+    pub fn get_active_branch(&self) -> Branch {
+        self.get_branch(&self.screen, self.tile_count)
+    }
+
+    /// The layout which lays out the given amount of tiles on the given screen
+    pub fn get_branch(&self, screen: &Screen, tile_count: usize) -> Branch {
+        if self.condition.holds(screen, tile_count) {
+            Branch::WhenMet
+        } else {
+            Branch::Otherwise
+        }
+    }
+}
+
+impl<A, B> TilingLayout for ConditionalLayout<A, B>
+    where A: TilingLayout<Error = StandardError>,
+          B: TilingLayout<Error = StandardError>
+{
+    type Error = StandardError;
+
+    fn get_master_window(&self, tiles: &VecDeque<Window>) -> Option<Window> {
+        match self.get_branch(&self.screen, tiles.len()) {
+            Branch::WhenMet => self.when_met.get_master_window(tiles),
+            Branch::Otherwise => self.otherwise.get_master_window(tiles),
+        }
+    }
+
+    fn swap_with_master(&mut self,
+                        window: Window,
+                        tiles: &mut VecDeque<Window>)
+                        -> Result<(), Self::Error> {
+        match self.get_branch(&self.screen, tiles.len()) {
+            Branch::WhenMet => self.when_met.swap_with_master(window, tiles),
+            Branch::Otherwise => self.otherwise.swap_with_master(window, tiles),
+        }
+    }
+
+    fn swap_windows(&mut self, window: Window, dir: PrevOrNext, tiles: &mut VecDeque<Window>) {
+        match self.get_branch(&self.screen, tiles.len()) {
+            Branch::WhenMet => self.when_met.swap_windows(window, dir, tiles),
+            Branch::Otherwise => self.otherwise.swap_windows(window, dir, tiles),
+        }
+    }
+
+    fn swap_tiles(&mut self, window: Window, other: Window, tiles: &mut VecDeque<Window>) {
+        match self.get_branch(&self.screen, tiles.len()) {
+            Branch::WhenMet => self.when_met.swap_tiles(window, other, tiles),
+            Branch::Otherwise => self.otherwise.swap_tiles(window, other, tiles),
+        }
+    }
+
+    fn get_window_geometry(&self,
+                           window: Window,
+                           screen: &Screen,
+                           tiles: &VecDeque<Window>)
+                           -> Result<Geometry, Self::Error> {
+        match self.get_branch(screen, tiles.len()) {
+            Branch::WhenMet => self.when_met.get_window_geometry(window, screen, tiles),
+            Branch::Otherwise => self.otherwise.get_window_geometry(window, screen, tiles),
+        }
+    }
+
+    fn get_window_geometries(&self,
+                             screen: &Screen,
+                             tiles: &VecDeque<Window>)
+                             -> Vec<(Window, Geometry)> {
+        match self.get_branch(screen, tiles.len()) {
+            Branch::WhenMet => self.when_met.get_window_geometries(screen, tiles),
+            Branch::Otherwise => self.otherwise.get_window_geometries(screen, tiles),
+        }
+    }
+
+    /// The messages go to the layout which lays out the tiles at the moment
+    fn handle_message(&mut self, message: LayoutMessage) -> bool {
+        match self.get_active_branch() {
+            Branch::WhenMet => self.when_met.handle_message(message),
+            Branch::Otherwise => self.otherwise.handle_message(message),
+        }
+    }
+
+    fn focus_shifted(&mut self, window: Option<Window>) {
+        self.when_met.focus_shifted(window);
+        self.otherwise.focus_shifted(window);
+    }
+
+    fn tile_added(&mut self, window: Window) {
+        self.tile_count += 1;
+        self.when_met.tile_added(window);
+        self.otherwise.tile_added(window);
+    }
+
+    fn tile_removed(&mut self, window: Window) {
+        self.tile_count = self.tile_count.saturating_sub(1);
+        self.when_met.tile_removed(window);
+        self.otherwise.tile_removed(window);
+    }
+
+    fn screen_resized(&mut self, screen: &Screen) {
+        self.screen = *screen;
+        self.when_met.screen_resized(screen);
+        self.otherwise.screen_resized(screen);
+    }
+
+    fn get_stacking_order(&self, tiles: &VecDeque<Window>) -> Vec<Window> {
+        match self.get_branch(&self.screen, tiles.len()) {
+            Branch::WhenMet => self.when_met.get_stacking_order(tiles),
+            Branch::Otherwise => self.otherwise.get_stacking_order(tiles),
+        }
+    }
+
+    fn get_tabs(&self, screen: &Screen, tiles: &VecDeque<Window>) -> Vec<Tab> {
+        match self.get_branch(screen, tiles.len()) {
+            Branch::WhenMet => self.when_met.get_tabs(screen, tiles),
+            Branch::Otherwise => self.otherwise.get_tabs(screen, tiles),
+        }
+    }
+
+    fn get_focus_neighbour(&self,
+                           window: Window,
+                           dir: PrevOrNext,
+                           tiles: &VecDeque<Window>)
+                           -> Option<Window> {
+        match self.get_branch(&self.screen, tiles.len()) {
+            Branch::WhenMet => self.when_met.get_focus_neighbour(window, dir, tiles),
+            Branch::Otherwise => self.otherwise.get_focus_neighbour(window, dir, tiles),
+        }
+    }
+}
+
+#[cfg(test)]
+mod conditional_layout_tests {
+    use super::{Branch, ConditionalLayout, LayoutCondition};
+    use wm_common::{LayoutManager, LayoutMessage, Manager, MasterTrait, TilingLayout, TilingTrait};
+    use b_tiling_wm::{TileManager, VerticalLayout};
+    use f_gaps::GapLayout;
+    use layouts::combinators::MirrorLayout;
+    use layouts::grid::GridLayout;
+    use layouts::monocle::MonocleLayout;
+    use std::collections::VecDeque;
+    use cplwm_api::types::*;
+
+    static SCREEN1: Screen = Screen {
+        width: 800,
+        height: 600,
+    };
+
+    static PORTRAIT: Screen = Screen {
+        width: 600,
+        height: 800,
+    };
+
+    static SOME_GEOM: Geometry = Geometry {
+        x: 10,
+        y: 10,
+        width: 100,
+        height: 100,
+    };
+
+    fn tiles(amount: Window) -> VecDeque<Window> {
+        (1..amount + 1).collect()
+    }
+
+    /// The whole screen for one tile, the VerticalLayout up to three tiles and a grid for more
+    fn by_count() -> ConditionalLayout<MonocleLayout,
+                                       ConditionalLayout<VerticalLayout, GridLayout>> {
+        ConditionalLayout::new(LayoutCondition::MaxTiles(1),
+                               MonocleLayout::new(),
+                               ConditionalLayout::new(LayoutCondition::MaxTiles(3),
+                                                      VerticalLayout::new(),
+                                                      GridLayout::new()))
+    }
+
+    #[test]
+    fn test_conditional_layout_by_tile_count() {
+        let layout = by_count();
+
+        assert!(layout.get_window_geometry(1, &SCREEN1, &tiles(0)).is_err());
+        assert_eq!(SCREEN1.to_geometry(),
+                   layout.get_window_geometry(1, &SCREEN1, &tiles(1)).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 400,
+                       y: 300,
+                       width: 400,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(3, &SCREEN1, &tiles(3)).ok().unwrap());
+
+        // the grid puts the fourth tile at the bottom right
+        assert_eq!(Geometry {
+                       x: 400,
+                       y: 300,
+                       width: 400,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(4, &SCREEN1, &tiles(4)).ok().unwrap());
+        assert_eq!(Geometry {
+                       x: 400,
+                       y: 0,
+                       width: 400,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(2, &SCREEN1, &tiles(4)).ok().unwrap());
+    }
+
+    #[test]
+    fn test_conditional_layout_active_branch() {
+        let mut layout = by_count();
+        assert_eq!(Branch::WhenMet, layout.get_active_branch());
+
+        layout.tile_added(1);
+        layout.tile_added(2);
+        assert_eq!(Branch::Otherwise, layout.get_active_branch());
+        assert_eq!(Branch::WhenMet, layout.otherwise.get_active_branch());
+
+        // only the active layout gets the message
+        assert!(layout.handle_message(LayoutMessage::ShrinkMaster));
+        assert_eq!(45, layout.otherwise.when_met.get_master_ratio());
+
+        layout.tile_removed(2);
+        assert_eq!(Branch::WhenMet, layout.get_active_branch());
+        assert!(!layout.handle_message(LayoutMessage::ShrinkMaster));
+    }
+
+    #[test]
+    fn test_conditional_layout_rotated_screen() {
+        let layout = ConditionalLayout::new(LayoutCondition::Portrait,
+                                            MirrorLayout::new(VerticalLayout::new()),
+                                            VerticalLayout::new());
+        let mut manager = TileManager::new(SCREEN1, layout);
+        for window in 1..3 {
+            assert!(manager.add_window(WindowWithInfo::new_tiled(window, SOME_GEOM)).is_ok());
+        }
+        assert_eq!(Branch::Otherwise, manager.layout.get_active_branch());
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 400,
+                       height: 600,
+                   },
+                   manager.get_window_layout()[0].1);
+
+        // after rotating the screen the tiles are above each other
+        manager.resize_screen(PORTRAIT);
+        assert_eq!(Branch::WhenMet, manager.layout.get_active_branch());
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 600,
+                       height: 400,
+                   },
+                   manager.get_window_layout()[0].1);
+        assert_eq!(Some(1), manager.get_master_window());
+    }
+
+    #[test]
+    fn test_conditional_layout_with_gap() {
+        let layout = GapLayout::new(by_count(), 5);
+        let mut manager = TileManager::new(SCREEN1, layout);
+        assert!(manager.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+
+        assert_eq!(vec![(1,
+                         Geometry {
+                            x: 5,
+                            y: 5,
+                            width: 790,
+                            height: 590,
+                        })],
+                   manager.get_window_layout());
+        assert_eq!(SCREEN1, manager.layout.tiling_layout.screen);
+    }
+}
@@ -0,0 +1,1029 @@
+//! A manual tiling layout in the style of i3.
+//!
+//! The tiles live in a tree of nested containers. Every container shows its children side by
+//! side, above each other, as tabs or as a stack of title bars. A new tile is placed right after
+//! the focused tile in the container of the focused tile, so the user chooses where the next
+//! tile goes by splitting the focused tile first: a SplitHorizontal or SplitVertical message puts
+//! the focused tile in a new container with that orientation.
+//!
+//! ```text
+//! +--------+--------+
+//! |        |   2    |
+//! |   1    +--------+
+//! |        |   3    |
+//! +--------+--------+
+//! ```
+//!
+//! Swapping a tile moves it within its container, into a neighbouring container or out of its
+//! container when it is at the edge of it, like the move command of i3. Cycling the focus visits
+//! the tiles in the order of the tree, entering a container gives the focus to the tile which had
+//! it last in that container.
+
+use cplwm_api::types::{Geometry, PrevOrNext, Screen, Tab, Window};
+use wm_common::{LayoutMessage, TilingLayout};
+use wm_common::error::StandardError;
+use layouts::split;
+use std::cmp;
+use std::collections::VecDeque;
+
+/// The way a container shows its children
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq)]
+pub enum ContainerKind {
+    /// the children are side by side, the first child on the left
+    SplitHorizontal,
+    /// the children are above each other, the first child on top
+    SplitVertical,
+    /// only the focused child is shown, below a tab bar with a tab for every child
+    Tabbed,
+    /// only the focused child is shown, below a title bar for every child
+    Stacked,
+}
+
+/// A child of a container
+#[derive(Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq)]
+pub enum ContainerNode {
+    /// a tile
+    Window(Window),
+    /// a nested container
+    Container(Container),
+}
+
+impl ContainerNode {
+    /// The tile which gets the focus when this node is focused, see Container.get_focused_window
+    pub fn get_focused_window(&self) -> Option<Window> {
+        match *self {
+            ContainerNode::Window(window) => Some(window),
+            ContainerNode::Container(ref container) => container.get_focused_window(),
+        }
+    }
+
+    /// The nested container, None for a tile
+    fn as_container(&self) -> Option<&Container> {
+        match *self {
+            ContainerNode::Window(_) => None,
+            ContainerNode::Container(ref container) => Some(container),
+        }
+    }
+
+    /// The nested container, None for a tile
+    fn as_container_mut(&mut self) -> Option<&mut Container> {
+        match *self {
+            ContainerNode::Window(_) => None,
+            ContainerNode::Container(ref mut container) => Some(container),
+        }
+    }
+}
+
+/// A container of tiles and other containers
+#[derive(Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq)]
+pub struct Container {
+    /// how the children are shown
+    pub kind: ContainerKind,
+    /// the tiles and containers in this container
+    pub children: Vec<ContainerNode>,
+    /// index of the child which had the focus last
+    pub focused: usize,
+}
+
+impl Container {
+    /// An empty container of the given kind
+    pub fn new(kind: ContainerKind) -> Container {
+        Container {
+            kind,
+            children: Vec::new(),
+            focused: 0,
+        }
+    }
+
+    /// The tiles in this container and the containers in it, in the order of the tree
+    pub fn get_windows(&self) -> Vec<Window> {
+        let mut windows = Vec::new();
+        for child in &self.children {
+            match *child {
+                ContainerNode::Window(window) => windows.push(window),
+                ContainerNode::Container(ref container) => {
+                    windows.extend(container.get_windows())
+                }
+            }
+        }
+        windows
+    }
+
+    /// The tile which gets the focus when this container is focused: the tile which had the focus
+    /// last in this container. None when the container is empty.
+    pub fn get_focused_window(&self) -> Option<Window> {
+        self.children.get(self.focused).and_then(|child| child.get_focused_window())
+    }
+
+    /// The indices of the children to follow from this container to reach the given window
+    fn find(&self, window: Window) -> Option<Vec<usize>> {
+        for (index, child) in self.children.iter().enumerate() {
+            match *child {
+                ContainerNode::Window(w) if w == window => return Some(vec![index]),
+                ContainerNode::Window(_) => {}
+                ContainerNode::Container(ref container) => {
+                    match container.find(window) {
+                        None => {}
+                        Some(mut path) => {
+                            path.insert(0, index);
+                            return Some(path);
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// The indices of the children to follow from this container to reach the container of the
+    /// given window and the index of the window in that container
+    fn find_parent(&self, window: Window) -> Option<(Vec<usize>, usize)> {
+        self.find(window).and_then(|mut path| path.pop().map(|index| (path, index)))
+    }
+
+    /// The container reached by following the given indices, None when they do not lead to a
+    /// container
+    fn get_container(&self, path: &[usize]) -> Option<&Container> {
+        match path.split_first() {
+            None => Some(self),
+            Some((&index, rest)) => {
+                self.children
+                    .get(index)
+                    .and_then(|child| child.as_container())
+                    .and_then(|container| container.get_container(rest))
+            }
+        }
+    }
+
+    /// The container reached by following the given indices, None when they do not lead to a
+    /// container
+    fn get_container_mut(&mut self, path: &[usize]) -> Option<&mut Container> {
+        match path.split_first() {
+            None => Some(self),
+            Some((&index, rest)) => {
+                self.children
+                    .get_mut(index)
+                    .and_then(|child| child.as_container_mut())
+                    .and_then(|container| container.get_container_mut(rest))
+            }
+        }
+    }
+
+    /// Replace the child at the given index of the container reached by following the given
+    /// indices by the tile of the window
+    fn set_window(&mut self, path: &[usize], index: usize, window: Window) {
+        match self.get_container_mut(path).and_then(|container| container.children.get_mut(index)) {
+            None => {}
+            Some(child) => *child = ContainerNode::Window(window),
+        }
+    }
+
+    /// Remove the empty containers in this container and keep the focused indices in range
+    fn remove_empty(&mut self) {
+        for child in &mut self.children {
+            match *child {
+                ContainerNode::Window(_) => {}
+                ContainerNode::Container(ref mut container) => container.remove_empty(),
+            }
+        }
+        self.children.retain(|child| {
+            match *child {
+                ContainerNode::Container(ref container) => !container.children.is_empty(),
+                ContainerNode::Window(_) => true,
+            }
+        });
+        if self.focused >= self.children.len() {
+            self.focused = self.children.len().saturating_sub(1);
+        }
+    }
+
+    /// True when only the focused child is shown
+    fn shows_one_child(&self) -> bool {
+        self.kind == ContainerKind::Tabbed || self.kind == ContainerKind::Stacked
+    }
+
+    /// The height of the tab bar or the title bars of this container in the given area
+    fn get_bar_height(&self, area: Geometry, tab_height: u32) -> u32 {
+        match self.kind {
+            ContainerKind::SplitHorizontal | ContainerKind::SplitVertical => 0,
+            ContainerKind::Tabbed => cmp::min(tab_height, area.height),
+            ContainerKind::Stacked => {
+                cmp::min(tab_height * self.children.len() as u32, area.height)
+            }
+        }
+    }
+
+    /// The area of the child at the given index when this container takes the given area
+    fn get_child_area(&self, index: usize, area: Geometry, tab_height: u32) -> Geometry {
+        match self.kind {
+            ContainerKind::SplitHorizontal => {
+                let (x, width) = split(area.width, self.children.len(), index);
+                Geometry {
+                    x: area.x + x as i32,
+                    width,
+                    ..area
+                }
+            }
+            ContainerKind::SplitVertical => {
+                let (y, height) = split(area.height, self.children.len(), index);
+                Geometry {
+                    y: area.y + y as i32,
+                    height,
+                    ..area
+                }
+            }
+            ContainerKind::Tabbed | ContainerKind::Stacked => {
+                let bar_height = self.get_bar_height(area, tab_height);
+                Geometry {
+                    y: area.y + bar_height as i32,
+                    height: area.height - bar_height,
+                    ..area
+                }
+            }
+        }
+    }
+
+    /// Add the geometries of the tiles in this container to the given geometries
+    fn get_geometries(&self,
+                      area: Geometry,
+                      tab_height: u32,
+                      geometries: &mut Vec<(Window, Geometry)>) {
+        for (index, child) in self.children.iter().enumerate() {
+            let child_area = self.get_child_area(index, area, tab_height);
+            match *child {
+                ContainerNode::Window(window) => geometries.push((window, child_area)),
+                ContainerNode::Container(ref container) => {
+                    container.get_geometries(child_area, tab_height, geometries)
+                }
+            }
+        }
+    }
+
+    /// Add the tiles in this container to hidden or to shown, depending on whether they are
+    /// hidden behind the focused child of a tabbed or stacked container.
+    fn get_stacking_order(&self,
+                          visible: bool,
+                          hidden: &mut Vec<Window>,
+                          shown: &mut Vec<Window>) {
+        for (index, child) in self.children.iter().enumerate() {
+            let visible = visible && (!self.shows_one_child() || index == self.focused);
+            match *child {
+                ContainerNode::Window(window) if visible => shown.push(window),
+                ContainerNode::Window(window) => hidden.push(window),
+                ContainerNode::Container(ref container) => {
+                    container.get_stacking_order(visible, hidden, shown)
+                }
+            }
+        }
+    }
+
+    /// Add the tabs of this container and of the shown containers in it to the given tabs. The
+    /// tab of a nested container shows the tile which had the focus last in it.
+    fn get_tabs(&self, area: Geometry, tab_height: u32, tabs: &mut Vec<Tab>) {
+        let bar_height = self.get_bar_height(area, tab_height);
+        for (index, child) in self.children.iter().enumerate() {
+            let tab_geometry = match self.kind {
+                ContainerKind::SplitHorizontal | ContainerKind::SplitVertical => None,
+                ContainerKind::Tabbed => {
+                    let (x, width) = split(area.width, self.children.len(), index);
+                    Some(Geometry {
+                        x: area.x + x as i32,
+                        width,
+                        height: bar_height,
+                        ..area
+                    })
+                }
+                ContainerKind::Stacked => {
+                    let (y, height) = split(bar_height, self.children.len(), index);
+                    Some(Geometry {
+                        y: area.y + y as i32,
+                        height,
+                        ..area
+                    })
+                }
+            };
+            match (tab_geometry, child.get_focused_window()) {
+                (Some(geometry), Some(window)) if bar_height > 0 => {
+                    tabs.push(Tab {
+                        window,
+                        geometry,
+                        focused: index == self.focused,
+                    })
+                }
+                _ => {}
+            }
+            match *child {
+                ContainerNode::Container(ref container) if !self.shows_one_child() ||
+                                                           index == self.focused => {
+                    let child_area = self.get_child_area(index, area, tab_height);
+                    container.get_tabs(child_area, tab_height, tabs)
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Layout which keeps the tiles in a tree of containers, the first tile of the tree is the master
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct ContainerLayout {
+    /// the container which takes the whole tile area, it is never removed
+    pub root: Container,
+    /// the focused tile, new tiles are placed after it
+    pub focused_window: Option<Window>,
+    /// height of a tab of a tabbed container and of a title bar of a stacked container
+    pub tab_height: u32,
+}
+
+impl ContainerLayout {
+    /// A ContainerLayout without tiles, of which the root container splits horizontally
+    pub fn new(tab_height: u32) -> ContainerLayout {
+        ContainerLayout {
+            root: Container::new(ContainerKind::SplitHorizontal),
+            focused_window: None,
+            tab_height,
+        }
+    }
+
+    /// The tiles in the order of the tree
+    pub fn get_windows(&self) -> Vec<Window> {
+        self.root.get_windows()
+    }
+
+    /// The path to the given window, see Container.find
+    fn find(&self, window: Window) -> Result<Vec<usize>, StandardError> {
+        self.root.find(window).ok_or(StandardError::UnknownWindow(window))
+    }
+
+    /// Let every container on the path remember the child on the path as its focused child
+    fn focus_path(&mut self, path: &[usize]) {
+        for depth in 0..path.len() {
+            match self.root.get_container_mut(&path[..depth]) {
+                None => {}
+                Some(container) => container.focused = path[depth],
+            }
+        }
+    }
+
+    /// Call f with the container of the window and the index of the window in it
+    fn with_parent<F>(&mut self, window: Window, f: F) -> Result<(), StandardError>
+        where F: FnOnce(&mut Container, usize)
+    {
+        match self.root.find_parent(window) {
+            None => Err(StandardError::UnknownWindow(window)),
+            Some((parent_path, index)) => {
+                self.root
+                    .get_container_mut(&parent_path)
+                    .map(|parent| f(parent, index))
+                    .ok_or(StandardError::UnknownWindow(window))
+            }
+        }
+    }
+
+    /// Split the window in the given orientation, the tiles added while it is focused are placed
+    /// next to it in that orientation. When the window is the only child of its container, the
+    /// container gets that orientation, otherwise the window is put in a new container.
+    pub fn split(&mut self, window: Window, kind: ContainerKind) -> Result<(), StandardError> {
+        self.with_parent(window, |parent, index| {
+            if parent.children.len() == 1 {
+                parent.kind = kind;
+            } else {
+                parent.children[index] = ContainerNode::Container(Container {
+                    kind,
+                    children: vec![ContainerNode::Window(window)],
+                    focused: 0,
+                });
+            }
+        })
+    }
+
+    /// Change the way the container of the window shows its children
+    pub fn set_container_kind(&mut self,
+                              window: Window,
+                              kind: ContainerKind)
+                              -> Result<(), StandardError> {
+        self.with_parent(window, |parent, _| parent.kind = kind)
+    }
+
+    /// Toggle the container of the window between splitting horizontally and vertically, a
+    /// tabbed or stacked container splits horizontally.
+    pub fn toggle_split(&mut self, window: Window) -> Result<(), StandardError> {
+        self.with_parent(window, |parent, _| {
+            parent.kind = match parent.kind {
+                ContainerKind::SplitHorizontal => ContainerKind::SplitVertical,
+                _ => ContainerKind::SplitHorizontal,
+            }
+        })
+    }
+
+    /// Move the window to the previous or next place in the tree. A window next to another
+    /// window swaps with it, a window next to a container moves into that container and a window
+    /// at the edge of its container moves out of it. Containers left empty are removed, a window
+    /// at the edge of the root container stays where it is.
+    pub fn move_window(&mut self, window: Window, dir: PrevOrNext) -> Result<(), StandardError> {
+        self.root
+            .find_parent(window)
+            .map(|(parent_path, index)| self.move_child(&parent_path, index, dir))
+            .ok_or(StandardError::UnknownWindow(window))
+            .and_then(|_| {
+                // the containers on the new path of the window remember it as their focused child
+                self.root.remove_empty();
+                self.find(window).map(|path| self.focus_path(&path))
+            })
+    }
+
+    /// Move the child at the given index of the container reached by following the given indices
+    /// to the previous or next place in the tree, see move_window
+    fn move_child(&mut self, parent_path: &[usize], index: usize, dir: PrevOrNext) {
+        let length = self.root.get_container(parent_path).map_or(0, |parent| parent.children.len());
+        let neighbour = match dir {
+            PrevOrNext::Prev => index.checked_sub(1),
+            PrevOrNext::Next if index + 1 < length => Some(index + 1),
+            PrevOrNext::Next => None,
+        };
+        match (neighbour, parent_path.split_last()) {
+            (Some(neighbour), _) => {
+                match self.root.get_container_mut(parent_path) {
+                    None => {}
+                    Some(parent) => {
+                        let node = parent.children.remove(index);
+                        // the removal of the child leaves the neighbour at the lower index
+                        match parent.children
+                            .get_mut(cmp::min(index, neighbour))
+                            .and_then(|child| child.as_container_mut()) {
+                            Some(container) => {
+                                let position = match dir {
+                                    PrevOrNext::Prev => container.children.len(),
+                                    PrevOrNext::Next => 0,
+                                };
+                                container.children.insert(position, node);
+                            }
+                            None => parent.children.insert(neighbour, node),
+                        }
+                    }
+                }
+            }
+            // a child at the edge of the root container stays where it is
+            (None, None) => {}
+            (None, Some((&parent_index, grandparent_path))) => {
+                match self.root.get_container_mut(grandparent_path) {
+                    None => {}
+                    Some(grandparent) => {
+                        let node = grandparent.children
+                            .get_mut(parent_index)
+                            .and_then(|child| child.as_container_mut())
+                            .map(|parent| parent.children.remove(index));
+                        let position = match dir {
+                            PrevOrNext::Prev => parent_index,
+                            PrevOrNext::Next => parent_index + 1,
+                        };
+                        match node {
+                            None => {}
+                            Some(node) => grandparent.children.insert(position, node),
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// The tile which gets the focus when the focus moves from the window to the previous or next
+    /// child of its container. When the window is at the edge of its container, the focus moves
+    /// to the neighbour of the container instead, wrapping around at the edges of the root
+    /// container. When the focus moves into a container, it goes to the tile which had the focus
+    /// last in that container.
+    pub fn get_focus_neighbour(&self, window: Window, dir: PrevOrNext) -> Option<Window> {
+        self.root.find(window).and_then(|path| {
+            let neighbour = (0..path.len()).rev().find_map(|depth| {
+                let children = self.root
+                    .get_container(&path[..depth])
+                    .map_or(&[][..], |container| &container.children[..]);
+                let index = path[depth];
+                let neighbour = match dir {
+                    PrevOrNext::Prev => index.checked_sub(1),
+                    PrevOrNext::Next => Some(index + 1),
+                };
+                neighbour.and_then(|neighbour| children.get(neighbour))
+            });
+            let wrapped = match dir {
+                PrevOrNext::Prev => self.root.children.last(),
+                PrevOrNext::Next => self.root.children.first(),
+            };
+            neighbour.or(wrapped).and_then(|child| child.get_focused_window())
+        })
+    }
+}
+
+impl TilingLayout for ContainerLayout {
+    type Error = StandardError;
+
+    fn get_master_window(&self, _: &VecDeque<Window>) -> Option<Window> {
+        self.get_windows().first().copied()
+    }
+
+    /// The window and the master swap their places in the tree
+    fn swap_with_master(&mut self,
+                        window: Window,
+                        tiles: &mut VecDeque<Window>)
+                        -> Result<(), Self::Error> {
+        match (self.get_master_window(tiles), self.root.find(window)) {
+            (Some(master), Some(_)) => {
+                self.swap_tiles(window, master, tiles);
+                Ok(())
+            }
+            _ => Err(StandardError::UnknownWindow(window)),
+        }
+    }
+
+    /// Swap the leaves of the two windows in the tree
+    fn swap_tiles(&mut self, window: Window, other: Window, _: &mut VecDeque<Window>) {
+        let (parent_path, index) = match self.root.find_parent(window) {
+            Some(parent) => parent,
+            None => return,
+        };
+        let (other_parent_path, other_index) = match self.root.find_parent(other) {
+            Some(parent) => parent,
+            None => return,
+        };
+        self.root.set_window(&parent_path, index, other);
+        self.root.set_window(&other_parent_path, other_index, window);
+    }
+
+    /// Move the window in the tree, see ContainerLayout.move_window
+    fn swap_windows(&mut self, window: Window, dir: PrevOrNext, _: &mut VecDeque<Window>) {
+        // like the other layouts, a window which is not a tile is not swapped
+        self.move_window(window, dir).unwrap_or(());
+    }
+
+    fn get_window_geometry(&self,
+                           window: Window,
+                           screen: &Screen,
+                           _: &VecDeque<Window>)
+                           -> Result<Geometry, Self::Error> {
+        let mut geometries = Vec::new();
+        self.root.get_geometries(screen.to_geometry(), self.tab_height, &mut geometries);
+        geometries.into_iter()
+            .find(|&(w, _)| w == window)
+            .map(|(_, geometry)| geometry)
+            .ok_or(StandardError::UnknownWindow(window))
+    }
+
+    /// Handles SplitHorizontal, SplitVertical, Tabbed, Stacked and ToggleSplit by changing the
+    /// container of the focused tile.
+    fn handle_message(&mut self, message: LayoutMessage) -> bool {
+        match message {
+            LayoutMessage::SplitHorizontal |
+            LayoutMessage::SplitVertical |
+            LayoutMessage::Tabbed |
+            LayoutMessage::Stacked |
+            LayoutMessage::ToggleSplit => {}
+            _ => return false,
+        }
+        let window = match self.focused_window {
+            Some(window) => window,
+            // without focused tile there is nothing to act on
+            None => return true,
+        };
+        let result = match message {
+            LayoutMessage::SplitHorizontal => self.split(window, ContainerKind::SplitHorizontal),
+            LayoutMessage::SplitVertical => self.split(window, ContainerKind::SplitVertical),
+            LayoutMessage::Tabbed => self.set_container_kind(window, ContainerKind::Tabbed),
+            LayoutMessage::Stacked => self.set_container_kind(window, ContainerKind::Stacked),
+            _ => self.toggle_split(window),
+        };
+        // an error means the focused window is not a tile, e.g. a floating window, so it has no
+        // container to act on
+        result.unwrap_or(());
+        true
+    }
+
+    /// The containers on the path to the focused tile remember it as their focused child
+    fn focus_shifted(&mut self, window: Option<Window>) {
+        self.focused_window = window;
+        match window.and_then(|w| self.root.find(w)) {
+            None => {}
+            Some(path) => self.focus_path(&path),
+        }
+    }
+
+    /// The new tile is placed after the focused tile in its container, or at the end of the root
+    /// container when the focused tile is unknown.
+    fn tile_added(&mut self, window: Window) {
+        let place = self.focused_window
+            .and_then(|w| self.root.find_parent(w))
+            .and_then(|(parent_path, index)| {
+                self.root.get_container_mut(&parent_path).map(|parent| (parent, index + 1))
+            });
+        match place {
+            None => self.root.children.push(ContainerNode::Window(window)),
+            Some((parent, index)) => parent.children.insert(index, ContainerNode::Window(window)),
+        }
+    }
+
+    /// The containers left empty are removed
+    fn tile_removed(&mut self, window: Window) {
+        match self.root.find_parent(window) {
+            None => {}
+            Some((parent_path, index)) => {
+                match self.root.get_container_mut(&parent_path) {
+                    None => {}
+                    Some(parent) => {
+                        parent.children.remove(index);
+                    }
+                }
+                self.root.remove_empty();
+            }
+        }
+    }
+
+    /// The tiles hidden by a tabbed or stacked container come first
+    fn get_stacking_order(&self, _: &VecDeque<Window>) -> Vec<Window> {
+        let mut hidden = Vec::new();
+        let mut shown = Vec::new();
+        self.root.get_stacking_order(true, &mut hidden, &mut shown);
+        hidden.extend(shown);
+        hidden
+    }
+
+    fn get_tabs(&self, screen: &Screen, _: &VecDeque<Window>) -> Vec<Tab> {
+        let mut tabs = Vec::new();
+        self.root.get_tabs(screen.to_geometry(), self.tab_height, &mut tabs);
+        tabs
+    }
+
+    fn get_focus_neighbour(&self,
+                           window: Window,
+                           dir: PrevOrNext,
+                           _: &VecDeque<Window>)
+                           -> Option<Window> {
+        self.get_focus_neighbour(window, dir)
+    }
+}
+
+#[cfg(test)]
+mod container_layout_tests {
+    use super::{Container, ContainerKind, ContainerLayout, ContainerNode};
+    use wm_common::{LayoutManager, LayoutMessage, Manager, TilingLayout, TilingTrait};
+    use b_tiling_wm::TileManager;
+    use c_floating_windows::FloatOrTileManager;
+    use std::collections::VecDeque;
+    use cplwm_api::types::*;
+
+    static SCREEN1: Screen = Screen {
+        width: 800,
+        height: 600,
+    };
+
+    static SCREEN2: Screen = Screen {
+        width: 301,
+        height: 401,
+    };
+
+    static SOME_GEOM: Geometry = Geometry {
+        x: 10,
+        y: 10,
+        width: 100,
+        height: 100,
+    };
+
+    fn window(window: Window) -> ContainerNode {
+        ContainerNode::Window(window)
+    }
+
+    fn container(kind: ContainerKind, focused: usize, children: Vec<ContainerNode>) -> Container {
+        Container {
+            kind,
+            children,
+            focused,
+        }
+    }
+
+    fn node(kind: ContainerKind, focused: usize, children: Vec<ContainerNode>) -> ContainerNode {
+        ContainerNode::Container(container(kind, focused, children))
+    }
+
+    /// Add a tile like a TileManager does, the new tile gets the focus
+    fn add(layout: &mut ContainerLayout, tiles: &mut VecDeque<Window>, window: Window) {
+        tiles.push_back(window);
+        layout.tile_added(window);
+        layout.focus_shifted(Some(window));
+    }
+
+    /// The layout of the diagram in the module documentation: window 2 is split vertically
+    /// before window 3 is added.
+    fn layout_with_split() -> (ContainerLayout, VecDeque<Window>) {
+        let mut layout = ContainerLayout::new(20);
+        let mut tiles = VecDeque::new();
+        add(&mut layout, &mut tiles, 1);
+        add(&mut layout, &mut tiles, 2);
+        assert!(layout.handle_message(LayoutMessage::SplitVertical));
+        add(&mut layout, &mut tiles, 3);
+        (layout, tiles)
+    }
+
+    #[test]
+    fn test_container_layout_no_window() {
+        let mut layout = ContainerLayout::new(20);
+        let mut tiles = VecDeque::new();
+
+        // make sure there is no geometry.
+        assert!(layout.get_window_geometry(1, &SCREEN1, &tiles).is_err());
+        assert_eq!(None, layout.get_master_window(&tiles));
+        assert!(layout.swap_with_master(1, &mut tiles).is_err());
+        assert!(layout.split(1, ContainerKind::SplitVertical).is_err());
+        assert!(layout.move_window(1, PrevOrNext::Next).is_err());
+        assert_eq!(None, layout.get_focus_neighbour(1, PrevOrNext::Next));
+        // without focused tile the message does nothing, but it is understood
+        assert!(layout.handle_message(LayoutMessage::Tabbed));
+        assert!(!layout.handle_message(LayoutMessage::ExpandMaster));
+    }
+
+    #[test]
+    fn test_container_paths() {
+        let (layout, _) = layout_with_split();
+        assert_eq!(Some((vec![1], 1)), layout.root.find_parent(3));
+        assert_eq!(None, layout.root.find_parent(4));
+        assert_eq!(Some(ContainerKind::SplitVertical),
+                   layout.root.get_container(&[1]).map(|container| container.kind));
+        // paths through a tile or past the children lead to no container
+        assert!(layout.root.get_container(&[0]).is_none());
+        assert!(layout.root.get_container(&[1, 0]).is_none());
+        assert!(layout.root.get_container(&[2]).is_none());
+    }
+
+    #[test]
+    fn test_container_layout_split_next() {
+        let (layout, tiles) = layout_with_split();
+
+        assert_eq!(container(ContainerKind::SplitHorizontal,
+                             1,
+                             vec![window(1),
+                                  node(ContainerKind::SplitVertical,
+                                       1,
+                                       vec![window(2), window(3)])]),
+                   layout.root);
+
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 400,
+                       height: 600,
+                   },
+                   layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 400,
+                       y: 0,
+                       width: 400,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(2, &SCREEN1, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 400,
+                       y: 300,
+                       width: 400,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(3, &SCREEN1, &tiles).ok().unwrap());
+
+        assert_eq!(vec![1, 2, 3], layout.get_windows());
+        assert_eq!(Some(1), layout.get_master_window(&tiles));
+        assert!(layout.get_window_geometry(4, &SCREEN1, &tiles).is_err());
+    }
+
+    // test to see the first children get the leftover pixels of irregular screens
+    #[test]
+    fn test_container_layout_irregular_screen() {
+        let (layout, tiles) = layout_with_split();
+
+        assert_eq!(Geometry {
+                       x: 151,
+                       y: 201,
+                       width: 150,
+                       height: 200,
+                   },
+                   layout.get_window_geometry(3, &SCREEN2, &tiles).ok().unwrap());
+    }
+
+    #[test]
+    fn test_container_layout_split_only_child() {
+        let mut layout = ContainerLayout::new(20);
+        let mut tiles = VecDeque::new();
+        add(&mut layout, &mut tiles, 1);
+
+        // the only child of a container does not get a container of its own
+        assert!(layout.split(1, ContainerKind::SplitVertical).is_ok());
+        add(&mut layout, &mut tiles, 2);
+        assert_eq!(container(ContainerKind::SplitVertical, 1, vec![window(1), window(2)]),
+                   layout.root);
+
+        assert!(layout.handle_message(LayoutMessage::ToggleSplit));
+        assert_eq!(ContainerKind::SplitHorizontal, layout.root.kind);
+    }
+
+    #[test]
+    fn test_container_layout_tabbed() {
+        let (mut layout, tiles) = layout_with_split();
+        assert!(layout.handle_message(LayoutMessage::Tabbed));
+
+        // only the focused child of the tabbed container is shown, below the tab bar
+        assert_eq!(Geometry {
+                       x: 400,
+                       y: 20,
+                       width: 400,
+                       height: 580,
+                   },
+                   layout.get_window_geometry(2, &SCREEN1, &tiles).ok().unwrap());
+        assert_eq!(vec![2, 1, 3], layout.get_stacking_order(&tiles));
+        assert_eq!(vec![Tab {
+                            window: 2,
+                            geometry: Geometry {
+                                x: 400,
+                                y: 0,
+                                width: 200,
+                                height: 20,
+                            },
+                            focused: false,
+                        },
+                        Tab {
+                            window: 3,
+                            geometry: Geometry {
+                                x: 600,
+                                y: 0,
+                                width: 200,
+                                height: 20,
+                            },
+                            focused: true,
+                        }],
+                   layout.get_tabs(&SCREEN1, &tiles));
+
+        // focusing the hidden tab shows it
+        layout.focus_shifted(Some(2));
+        assert_eq!(vec![3, 1, 2], layout.get_stacking_order(&tiles));
+        assert_eq!(vec![true, false],
+                   layout.get_tabs(&SCREEN1, &tiles)
+                       .iter()
+                       .map(|t| t.focused)
+                       .collect::<Vec<bool>>());
+    }
+
+    #[test]
+    fn test_container_layout_stacked() {
+        let (mut layout, tiles) = layout_with_split();
+        assert!(layout.handle_message(LayoutMessage::Stacked));
+
+        assert_eq!(Geometry {
+                       x: 400,
+                       y: 40,
+                       width: 400,
+                       height: 560,
+                   },
+                   layout.get_window_geometry(3, &SCREEN1, &tiles).ok().unwrap());
+        assert_eq!(vec![Geometry {
+                            x: 400,
+                            y: 0,
+                            width: 400,
+                            height: 20,
+                        },
+                        Geometry {
+                            x: 400,
+                            y: 20,
+                            width: 400,
+                            height: 20,
+                        }],
+                   layout.get_tabs(&SCREEN1, &tiles)
+                       .iter()
+                       .map(|t| t.geometry)
+                       .collect::<Vec<Geometry>>());
+    }
+
+    #[test]
+    fn test_container_layout_nested_tabs() {
+        let mut layout = ContainerLayout::new(20);
+        let mut tiles = VecDeque::new();
+        add(&mut layout, &mut tiles, 1);
+        add(&mut layout, &mut tiles, 2);
+        assert!(layout.handle_message(LayoutMessage::Tabbed));
+        assert!(layout.split(2, ContainerKind::Tabbed).is_ok());
+        add(&mut layout, &mut tiles, 3);
+
+        // the tab of the nested container shows its focused tile, its own tab bar is below
+        let tabs = layout.get_tabs(&SCREEN1, &tiles);
+        assert_eq!(vec![(1, false, 0, 0), (3, true, 400, 0), (2, false, 0, 20), (3, true, 400, 20)],
+                   tabs.iter()
+                       .map(|t| (t.window, t.focused, t.geometry.x, t.geometry.y))
+                       .collect::<Vec<(Window, bool, i32, i32)>>());
+        assert_eq!(vec![1, 2, 3], layout.get_stacking_order(&tiles));
+
+        // the tabs of a hidden container are not shown
+        layout.focus_shifted(Some(1));
+        assert_eq!(2, layout.get_tabs(&SCREEN1, &tiles).len());
+    }
+
+    #[test]
+    fn test_container_layout_move() {
+        let (mut layout, mut tiles) = layout_with_split();
+
+        // a window next to a container moves into it
+        layout.swap_windows(1, PrevOrNext::Next, &mut tiles);
+        assert_eq!(container(ContainerKind::SplitHorizontal,
+                             0,
+                             vec![node(ContainerKind::SplitVertical,
+                                       0,
+                                       vec![window(1), window(2), window(3)])]),
+                   layout.root);
+
+        // within its container it swaps with its neighbour
+        assert!(layout.move_window(1, PrevOrNext::Next).is_ok());
+        assert_eq!(vec![2, 1, 3], layout.get_windows());
+
+        // at the edge of its container it moves out of it
+        assert!(layout.move_window(3, PrevOrNext::Next).is_ok());
+        assert_eq!(container(ContainerKind::SplitHorizontal,
+                             1,
+                             vec![node(ContainerKind::SplitVertical,
+                                       1,
+                                       vec![window(2), window(1)]),
+                                  window(3)]),
+                   layout.root);
+
+        // the container left empty is removed
+        assert!(layout.move_window(2, PrevOrNext::Prev).is_ok());
+        assert!(layout.move_window(1, PrevOrNext::Prev).is_ok());
+        assert_eq!(container(ContainerKind::SplitHorizontal,
+                             1,
+                             vec![window(2), window(1), window(3)]),
+                   layout.root);
+
+        // at the edge of the root container the window stays where it is
+        assert!(layout.move_window(2, PrevOrNext::Prev).is_ok());
+        assert_eq!(vec![2, 1, 3], layout.get_windows());
+    }
+
+    #[test]
+    fn test_container_layout_remove() {
+        let (mut layout, mut tiles) = layout_with_split();
+
+        layout.tile_removed(2);
+        layout.tile_removed(3);
+        assert_eq!(container(ContainerKind::SplitHorizontal, 0, vec![window(1)]), layout.root);
+
+        // the focused tile is gone, so the new tile is added to the root container
+        layout.tile_added(4);
+        assert_eq!(vec![1, 4], layout.get_windows());
+
+        assert!(layout.swap_with_master(4, &mut tiles).is_ok());
+        assert_eq!(vec![4, 1], layout.get_windows());
+        assert!(layout.swap_with_master(5, &mut tiles).is_err());
+    }
+
+    #[test]
+    fn test_container_layout_focus_navigation() {
+        let (mut layout, _) = layout_with_split();
+
+        assert_eq!(Some(2), layout.get_focus_neighbour(3, PrevOrNext::Prev));
+        assert_eq!(Some(1), layout.get_focus_neighbour(2, PrevOrNext::Prev));
+        // the focus wraps around at the edges of the root container
+        assert_eq!(Some(1), layout.get_focus_neighbour(3, PrevOrNext::Next));
+
+        // entering a container focuses the tile which had the focus last in it
+        assert_eq!(Some(3), layout.get_focus_neighbour(1, PrevOrNext::Next));
+        assert_eq!(Some(3), layout.get_focus_neighbour(1, PrevOrNext::Prev));
+        layout.focus_shifted(Some(2));
+        layout.focus_shifted(Some(1));
+        assert_eq!(Some(2), layout.get_focus_neighbour(1, PrevOrNext::Next));
+        assert_eq!(None, layout.get_focus_neighbour(4, PrevOrNext::Next));
+    }
+
+    #[test]
+    fn test_container_layout_in_tile_manager() {
+        let mut manager = TileManager::new(SCREEN1, ContainerLayout::new(20));
+        for window in 1..3 {
+            assert!(manager.add_window(WindowWithInfo::new_tiled(window, SOME_GEOM)).is_ok());
+        }
+        assert!(manager.send_layout_message(LayoutMessage::SplitVertical));
+        assert!(manager.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).is_ok());
+        assert!(manager.focus_shifted(Some(2)).is_ok());
+
+        assert_eq!(Some(1), manager.get_focus_neighbour(2, PrevOrNext::Prev));
+        assert_eq!(Some(2), manager.get_focus_neighbour(1, PrevOrNext::Next));
+
+        assert!(manager.remove_window(2).is_ok());
+        assert_eq!(vec![1, 3],
+                   manager.get_window_layout().iter().map(|&(w, _)| w).collect::<Vec<Window>>());
+        assert_eq!(Some(3), manager.get_focus_neighbour(1, PrevOrNext::Next));
+    }
+
+    #[test]
+    fn test_container_layout_in_float_manager() {
+        let mut manager = FloatOrTileManager::new(SCREEN1, ContainerLayout::new(20));
+        assert!(manager.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+        assert!(manager.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+        assert!(manager.add_window(WindowWithInfo::new_float(3, SOME_GEOM)).is_ok());
+
+        assert_eq!(Some(1), manager.get_focus_neighbour(2, PrevOrNext::Next));
+        // floating windows are not in the tree, they are cycled as usual
+        assert_eq!(None, manager.get_focus_neighbour(3, PrevOrNext::Next));
+    }
+}
@@ -0,0 +1,356 @@
+//! A layout which arranges the tiles in a near-square grid.
+//!
+//! The amount of columns is chosen so the cells are as close to square as possible, taking the
+//! aspect ratio of the screen into account. The tiles fill the grid in reading order: from left
+//! to right, from top to bottom. When the last row is not full, its tiles share the whole width
+//! of the screen.
+//!
+//! ```text
+//! +-----+-----+-----+
+//! |  1  |  2  |  3  |
+//! +-----+--+--+-----+
+//! |   4    |   5    |
+//! +--------+--------+
+//! ```
+
+use cplwm_api::types::{Geometry, PrevOrNext, Screen, Window};
+use wm_common::{LayoutMessage, TilingLayout};
+use wm_common::error::StandardError;
+use layouts::{split, swap_with_front, swap_with_neighbour};
+use std::collections::VecDeque;
+
+/// Layout which arranges the tiles in a grid, the first tile is the master
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct GridLayout {}
+
+impl GridLayout {
+    /// A new GridLayout
+    pub fn new() -> GridLayout {
+        GridLayout {}
+    }
+
+    /// The amount of columns and rows of the grid for the given amount of tiles.
+    pub fn get_grid_size(&self, tiles: usize, screen: &Screen) -> (usize, usize) {
+        if tiles == 0 {
+            return (0, 0);
+        }
+        // square cells need sqrt(tiles * width / height) columns, take the nearest amount
+        // of columns c, comparing the geometric mean of c and c + 1 to the square root.
+        let mut columns = 1;
+        while columns < tiles &&
+              (columns * (columns + 1)) as u64 * (screen.height as u64) <
+              tiles as u64 * screen.width as u64 {
+            columns += 1;
+        }
+        let rows = tiles.div_ceil(columns);
+        // drop the columns which would stay empty with this amount of rows
+        (tiles.div_ceil(rows), rows)
+    }
+}
+
+impl Default for GridLayout {
+    fn default() -> GridLayout {
+        GridLayout::new()
+    }
+}
+
+impl TilingLayout for GridLayout {
+    type Error = StandardError;
+
+    fn get_master_window(&self, tiles: &VecDeque<Window>) -> Option<Window> {
+        tiles.front().copied()
+    }
+
+    fn swap_with_master(&mut self,
+                        window: Window,
+                        tiles: &mut VecDeque<Window>)
+                        -> Result<(), Self::Error> {
+        swap_with_front(window, tiles)
+    }
+
+    /// Swap with the tile before or after it in reading order.
+    fn swap_windows(&mut self, window: Window, dir: PrevOrNext, tiles: &mut VecDeque<Window>) {
+        swap_with_neighbour(window, dir, tiles)
+    }
+
+    fn get_window_geometry(&self,
+                           window: Window,
+                           screen: &Screen,
+                           tiles: &VecDeque<Window>)
+                           -> Result<Geometry, Self::Error> {
+        let (columns, rows) = self.get_grid_size(tiles.len(), screen);
+        match tiles.iter().position(|w| *w == window) {
+            None => Err(StandardError::UnknownWindow(window)),
+            Some(index) => {
+                let row = index / columns;
+                let column = index % columns;
+                // only the last row can have less tiles than there are columns
+                let row_tiles = if row == rows - 1 {
+                    tiles.len() - row * columns
+                } else {
+                    columns
+                };
+                let (x, width) = split(screen.width, row_tiles, column);
+                let (y, height) = split(screen.height, rows, row);
+                Ok(Geometry {
+                    x: x as i32,
+                    y: y as i32,
+                    width,
+                    height,
+                })
+            }
+        }
+    }
+
+    /// There is nothing to adjust in this layout, so it ignores every message
+    fn handle_message(&mut self, _: LayoutMessage) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod grid_layout_tests {
+    use super::GridLayout;
+    use wm_common::TilingLayout;
+    use f_gaps::GapLayout;
+    use std::collections::VecDeque;
+    use cplwm_api::types::*;
+
+    static SCREEN1: Screen = Screen {
+        width: 800,
+        height: 600,
+    };
+
+    static SCREEN2: Screen = Screen {
+        width: 301,
+        height: 401,
+    };
+
+    fn tiles(amount: Window) -> VecDeque<Window> {
+        (1..amount + 1).collect()
+    }
+
+    #[test]
+    fn test_grid_layout_no_window() {
+        let layout = GridLayout::new();
+        let tiles = VecDeque::new();
+
+        // make sure there is no geometry.
+        assert!(layout.get_window_geometry(1, &SCREEN1, &tiles).is_err());
+        assert_eq!(None, layout.get_master_window(&tiles));
+    }
+
+    #[test]
+    fn test_grid_layout_one_window() {
+        let layout = GridLayout::new();
+        let tiles = tiles(1);
+
+        assert_eq!(SCREEN1.to_geometry(),
+                   layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap());
+    }
+
+    #[test]
+    fn test_grid_size() {
+        let layout = GridLayout::new();
+        assert_eq!((1, 1), layout.get_grid_size(1, &SCREEN1));
+        assert_eq!((2, 1), layout.get_grid_size(2, &SCREEN1));
+        assert_eq!((2, 2), layout.get_grid_size(3, &SCREEN1));
+        assert_eq!((2, 2), layout.get_grid_size(4, &SCREEN1));
+        assert_eq!((3, 2), layout.get_grid_size(5, &SCREEN1));
+        assert_eq!((3, 2), layout.get_grid_size(6, &SCREEN1));
+        assert_eq!((3, 3), layout.get_grid_size(7, &SCREEN1));
+        assert_eq!((4, 3), layout.get_grid_size(12, &SCREEN1));
+
+        // a portrait screen gets more rows than columns
+        assert_eq!((1, 2), layout.get_grid_size(2, &SCREEN2));
+        assert_eq!((2, 3), layout.get_grid_size(5, &SCREEN2));
+    }
+
+    #[test]
+    fn test_grid_layout_multiple_windows_regular_screen() {
+        let layout = GridLayout::new();
+        let tiles = tiles(4);
+
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 400,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 400,
+                       y: 0,
+                       width: 400,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(2, &SCREEN1, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 300,
+                       width: 400,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(3, &SCREEN1, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 400,
+                       y: 300,
+                       width: 400,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(4, &SCREEN1, &tiles).ok().unwrap());
+
+        // any other window should return an error
+        assert!(layout.get_window_geometry(5, &SCREEN1, &tiles).is_err());
+    }
+
+    #[test]
+    fn test_grid_layout_last_row_not_full() {
+        let layout = GridLayout::new();
+        let tiles = tiles(5);
+
+        // 800 is not divisible by 3, the first two columns get the leftover pixels
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 267,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 267,
+                       y: 0,
+                       width: 267,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(2, &SCREEN1, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 534,
+                       y: 0,
+                       width: 266,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(3, &SCREEN1, &tiles).ok().unwrap());
+
+        // the tiles of the last row share the whole width
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 300,
+                       width: 400,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(4, &SCREEN1, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 400,
+                       y: 300,
+                       width: 400,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(5, &SCREEN1, &tiles).ok().unwrap());
+    }
+
+    // test to see this layout spreads the leftover pixels of irregular screens evenly
+    #[test]
+    fn test_grid_layout_multiple_windows_irregular_screen() {
+        let layout = GridLayout::new();
+        let tiles = tiles(5);
+
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 151,
+                       height: 134,
+                   },
+                   layout.get_window_geometry(1, &SCREEN2, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 151,
+                       y: 0,
+                       width: 150,
+                       height: 134,
+                   },
+                   layout.get_window_geometry(2, &SCREEN2, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 134,
+                       width: 151,
+                       height: 134,
+                   },
+                   layout.get_window_geometry(3, &SCREEN2, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 151,
+                       y: 134,
+                       width: 150,
+                       height: 134,
+                   },
+                   layout.get_window_geometry(4, &SCREEN2, &tiles).ok().unwrap());
+
+        // the last row has one pixel less and a single tile
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 268,
+                       width: 301,
+                       height: 133,
+                   },
+                   layout.get_window_geometry(5, &SCREEN2, &tiles).ok().unwrap());
+    }
+
+    #[test]
+    fn test_grid_layout_tiles_cover_screen() {
+        let layout = GridLayout::new();
+        for amount in 1..20 {
+            let tiles = tiles(amount);
+            let area: u32 = tiles.iter()
+                .map(|w| layout.get_window_geometry(*w, &SCREEN2, &tiles).ok().unwrap())
+                .map(|g| g.width * g.height)
+                .sum();
+            assert_eq!(SCREEN2.width * SCREEN2.height, area);
+        }
+    }
+
+    #[test]
+    fn test_grid_layout_swap_windows_in_reading_order() {
+        let mut layout = GridLayout::new();
+        let mut tiles = tiles(5);
+
+        layout.swap_windows(3, PrevOrNext::Next, &mut tiles);
+        assert_eq!(vec![1, 2, 4, 3, 5], tiles.iter().cloned().collect::<Vec<Window>>());
+        // window 3 moved from the end of the first row to the start of the second one
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 300,
+                       width: 400,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(3, &SCREEN1, &tiles).ok().unwrap());
+
+        layout.swap_windows(1, PrevOrNext::Prev, &mut tiles);
+        assert_eq!(vec![5, 2, 4, 3, 1], tiles.iter().cloned().collect::<Vec<Window>>());
+
+        assert!(layout.swap_with_master(3, &mut tiles).is_ok());
+        assert_eq!(Some(3), layout.get_master_window(&tiles));
+        assert!(layout.swap_with_master(6, &mut tiles).is_err());
+    }
+
+    #[test]
+    fn test_grid_layout_with_gap() {
+        let layout = GapLayout::new(GridLayout::new(), 5);
+        let tiles = tiles(4);
+
+        assert_eq!(Geometry {
+                       x: 405,
+                       y: 305,
+                       width: 390,
+                       height: 290,
+                   },
+                   layout.get_window_geometry(4, &SCREEN1, &tiles).ok().unwrap());
+    }
+}
@@ -0,0 +1,71 @@
+//! Additional tiling layouts, each implementing the TilingLayout trait of wm_common.
+//!
+//! These layouts can be used by any TileManager and can be wrapped in a GapLayout.
+
+use cplwm_api::types::{PrevOrNext, Window};
+use wm_common::error::StandardError;
+use std::collections::VecDeque;
+
+pub mod border;
+pub mod bsp;
+pub mod centered;
+pub mod combinators;
+pub mod conditional;
+pub mod container;
+pub mod grid;
+pub mod monocle;
+pub mod selector;
+pub mod spiral;
+
+/// Swap the given window with the first tile, the master in most layouts.
+/// Returns an error when the window is not in the given tiles.
+pub fn swap_with_front(window: Window, tiles: &mut VecDeque<Window>) -> Result<(), StandardError> {
+    match tiles.iter().position(|w| *w == window) {
+        None => Err(StandardError::UnknownWindow(window)),
+        Some(index) => {
+            tiles.swap(0, index);
+            Ok(())
+        }
+    }
+}
+
+/// Swap the given window with the next or previous tile, wrapping around at the ends.
+/// Does nothing when the window is not in the given tiles.
+pub fn swap_with_neighbour(window: Window, dir: PrevOrNext, tiles: &mut VecDeque<Window>) {
+    match tiles.iter().position(|w| *w == window) {
+        None => {}
+        Some(index) => {
+            let neighbour = match dir {
+                PrevOrNext::Prev => (index + tiles.len() - 1) % tiles.len(),
+                PrevOrNext::Next => (index + 1) % tiles.len(),
+            };
+            tiles.swap(index, neighbour);
+        }
+    }
+}
+
+/// Swap the given window with the other tile.
+/// Does nothing when one of the windows is not in the given tiles.
+pub fn swap_with_tile(window: Window, other: Window, tiles: &mut VecDeque<Window>) {
+    let index = match tiles.iter().position(|w| *w == window) {
+        Some(index) => index,
+        None => return,
+    };
+    let other_index = match tiles.iter().position(|w| *w == other) {
+        Some(other_index) => other_index,
+        None => return,
+    };
+    tiles.swap(index, other_index);
+}
+
+/// Split the given length in the given amount of parts and return the offset and the size of the
+/// part at the given index. The leftover of the division is spread over the first parts, so no
+/// two parts differ more than one in size.
+pub fn split(length: u32, parts: usize, index: usize) -> (u32, u32) {
+    let parts = parts as u32;
+    let index = index as u32;
+    let size = length / parts;
+    let leftover = length % parts;
+    let offset = index * size + if index < leftover { index } else { leftover };
+    (offset, size + if index < leftover { 1 } else { 0 })
+}
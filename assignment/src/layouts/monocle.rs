@@ -0,0 +1,364 @@
+//! Layouts which give every tile the whole tile area and show the focused tile on top, like the
+//! Full layout of xmonad and the tabbed layout of i3.
+//!
+//! The tabbed variant reserves a strip at the top of the screen for a tab bar with a tab for
+//! every tile, in the order of the tiles. The layout itself does not draw the tab bar, it only
+//! reports the tabs so a backend can draw them, see TabSupport.
+//!
+//! ```text
+//! +-----+-----+-----+
+//! |  1  | [2] |  3  |
+//! +-----+-----+-----+
+//! |                 |
+//! |        2        |
+//! |                 |
+//! +-----------------+
+//! ```
+
+use cplwm_api::types::{Geometry, PrevOrNext, Screen, Tab, Window};
+use wm_common::{LayoutMessage, TilingLayout};
+use wm_common::error::StandardError;
+use layouts::{split, swap_with_front, swap_with_neighbour};
+use std::cmp;
+use std::collections::VecDeque;
+
+/// Layout in which all tiles take the whole tile area and the last focused tile is on top, the
+/// first tile is the master.
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct MonocleLayout {
+    /// height of the tab bar, the monocle layout has no tab bar
+    pub tab_bar_height: u32,
+    /// the last focused tile
+    pub focused_window: Option<Window>,
+}
+
+impl MonocleLayout {
+    /// A monocle layout without tab bar
+    pub fn new() -> MonocleLayout {
+        MonocleLayout {
+            tab_bar_height: 0,
+            focused_window: None,
+        }
+    }
+
+    /// A monocle layout with a tab bar of the given height
+    pub fn tabbed(tab_bar_height: u32) -> MonocleLayout {
+        MonocleLayout { tab_bar_height, ..MonocleLayout::new() }
+    }
+
+    /// The tile on top: the last focused tile, or the last tile when none of the tiles got the
+    /// focus yet.
+    pub fn get_top_window(&self, tiles: &VecDeque<Window>) -> Option<Window> {
+        match self.focused_window {
+            Some(w) if tiles.contains(&w) => Some(w),
+            _ => tiles.back().copied(),
+        }
+    }
+
+    /// The height of the tab bar on the given screen
+    fn get_tab_bar_height(&self, screen: &Screen) -> u32 {
+        cmp::min(self.tab_bar_height, screen.height)
+    }
+}
+
+impl Default for MonocleLayout {
+    fn default() -> MonocleLayout {
+        MonocleLayout::new()
+    }
+}
+
+impl TilingLayout for MonocleLayout {
+    type Error = StandardError;
+
+    fn get_master_window(&self, tiles: &VecDeque<Window>) -> Option<Window> {
+        tiles.front().copied()
+    }
+
+    fn swap_with_master(&mut self,
+                        window: Window,
+                        tiles: &mut VecDeque<Window>)
+                        -> Result<(), Self::Error> {
+        swap_with_front(window, tiles)
+    }
+
+    /// Swap with the previous or next tile, which moves the tab in the tab bar.
+    fn swap_windows(&mut self, window: Window, dir: PrevOrNext, tiles: &mut VecDeque<Window>) {
+        swap_with_neighbour(window, dir, tiles)
+    }
+
+    fn get_window_geometry(&self,
+                           window: Window,
+                           screen: &Screen,
+                           tiles: &VecDeque<Window>)
+                           -> Result<Geometry, Self::Error> {
+        if tiles.contains(&window) {
+            let tab_bar_height = self.get_tab_bar_height(screen);
+            Ok(Geometry {
+                x: 0,
+                y: tab_bar_height as i32,
+                width: screen.width,
+                height: screen.height - tab_bar_height,
+            })
+        } else {
+            Err(StandardError::UnknownWindow(window))
+        }
+    }
+
+    /// There is nothing to adjust in this layout, so it ignores every message
+    fn handle_message(&mut self, _: LayoutMessage) -> bool {
+        false
+    }
+
+    fn focus_shifted(&mut self, window: Option<Window>) {
+        self.focused_window = window;
+    }
+
+    /// The tile on top comes last, the other tiles keep their order.
+    fn get_stacking_order(&self, tiles: &VecDeque<Window>) -> Vec<Window> {
+        let top = self.get_top_window(tiles);
+        let mut windows: Vec<Window> =
+            tiles.iter().copied().filter(|w| Some(*w) != top).collect();
+        windows.extend(top);
+        windows
+    }
+
+    /// The tabs share the width of the tab bar, the tab of the tile on top is focused.
+    fn get_tabs(&self, screen: &Screen, tiles: &VecDeque<Window>) -> Vec<Tab> {
+        let tab_bar_height = self.get_tab_bar_height(screen);
+        if tab_bar_height == 0 {
+            return Vec::new();
+        }
+        let top = self.get_top_window(tiles);
+        tiles.iter()
+            .enumerate()
+            .map(|(index, window)| {
+                let (x, width) = split(screen.width, tiles.len(), index);
+                Tab {
+                    window: *window,
+                    geometry: Geometry {
+                        x: x as i32,
+                        y: 0,
+                        width,
+                        height: tab_bar_height,
+                    },
+                    focused: Some(*window) == top,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod monocle_layout_tests {
+    use super::MonocleLayout;
+    use wm_common::{LayoutManager, LayoutMessage, Manager, TilingLayout, TilingTrait};
+    use a_fullscreen_wm::FocusManager;
+    use b_tiling_wm::TileManager;
+    use c_floating_windows::FloatOrTileManager;
+    use f_gaps::GapLayout;
+    use std::collections::VecDeque;
+    use cplwm_api::types::*;
+
+    static SCREEN1: Screen = Screen {
+        width: 800,
+        height: 600,
+    };
+
+    static SCREEN2: Screen = Screen {
+        width: 301,
+        height: 401,
+    };
+
+    static SOME_GEOM: Geometry = Geometry {
+        x: 10,
+        y: 10,
+        width: 100,
+        height: 100,
+    };
+
+    fn tiles(amount: Window) -> VecDeque<Window> {
+        (1..amount + 1).collect()
+    }
+
+    fn tab(window: Window, x: i32, width: u32, focused: bool) -> Tab {
+        Tab {
+            window,
+            geometry: Geometry {
+                x,
+                y: 0,
+                width,
+                height: 20,
+            },
+            focused,
+        }
+    }
+
+    #[test]
+    fn test_monocle_layout_no_window() {
+        let layout = MonocleLayout::new();
+        let tiles = VecDeque::new();
+
+        // make sure there is no geometry.
+        assert!(layout.get_window_geometry(1, &SCREEN1, &tiles).is_err());
+        assert_eq!(None, layout.get_master_window(&tiles));
+        assert_eq!(Vec::<Window>::new(), layout.get_stacking_order(&tiles));
+    }
+
+    #[test]
+    fn test_monocle_layout_multiple_windows() {
+        let layout = MonocleLayout::new();
+        let tiles = tiles(3);
+
+        for window in 1..4 {
+            assert_eq!(SCREEN1.to_geometry(),
+                       layout.get_window_geometry(window, &SCREEN1, &tiles).ok().unwrap());
+        }
+        assert!(layout.get_window_geometry(4, &SCREEN1, &tiles).is_err());
+        assert_eq!(Vec::<Tab>::new(), layout.get_tabs(&SCREEN1, &tiles));
+    }
+
+    #[test]
+    fn test_monocle_layout_focused_on_top() {
+        let mut layout = MonocleLayout::new();
+        let tiles = tiles(3);
+
+        // without focus the last tile is on top
+        assert_eq!(vec![1, 2, 3], layout.get_stacking_order(&tiles));
+
+        layout.focus_shifted(Some(1));
+        assert_eq!(vec![2, 3, 1], layout.get_stacking_order(&tiles));
+        assert_eq!(Some(1), layout.get_master_window(&tiles));
+
+        // a focused window which is not a tile anymore is ignored
+        layout.focus_shifted(Some(5));
+        assert_eq!(vec![1, 2, 3], layout.get_stacking_order(&tiles));
+    }
+
+    #[test]
+    fn test_tabbed_layout_geometry() {
+        let layout = MonocleLayout::tabbed(20);
+        let tiles = tiles(2);
+
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 20,
+                       width: 800,
+                       height: 580,
+                   },
+                   layout.get_window_geometry(2, &SCREEN1, &tiles).ok().unwrap());
+    }
+
+    // test to see the tabs of irregular screens share the leftover pixels
+    #[test]
+    fn test_tabbed_layout_tabs() {
+        let mut layout = MonocleLayout::tabbed(20);
+        let mut tiles = tiles(3);
+        layout.focus_shifted(Some(2));
+
+        assert_eq!(vec![tab(1, 0, 101, false), tab(2, 101, 100, true), tab(3, 201, 100, false)],
+                   layout.get_tabs(&SCREEN2, &tiles));
+
+        // swapping moves the tab
+        layout.swap_windows(2, PrevOrNext::Prev, &mut tiles);
+        assert_eq!(vec![tab(2, 0, 101, true), tab(1, 101, 100, false), tab(3, 201, 100, false)],
+                   layout.get_tabs(&SCREEN2, &tiles));
+
+        assert!(!layout.handle_message(LayoutMessage::ExpandMaster));
+    }
+
+    #[test]
+    fn test_tabbed_layout_with_gap() {
+        let mut layout = GapLayout::new(MonocleLayout::tabbed(20), 5);
+        let tiles = tiles(2);
+        layout.focus_shifted(Some(1));
+
+        assert_eq!(Geometry {
+                       x: 5,
+                       y: 25,
+                       width: 790,
+                       height: 570,
+                   },
+                   layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap());
+        assert_eq!(vec![Tab {
+                            window: 1,
+                            geometry: Geometry {
+                                x: 5,
+                                y: 5,
+                                width: 390,
+                                height: 20,
+                            },
+                            focused: true,
+                        },
+                        Tab {
+                            window: 2,
+                            geometry: Geometry {
+                                x: 405,
+                                y: 5,
+                                width: 390,
+                                height: 20,
+                            },
+                            focused: false,
+                        }],
+                   layout.get_tabs(&SCREEN1, &tiles));
+        assert_eq!(vec![2, 1], layout.get_stacking_order(&tiles));
+    }
+
+    // only one tile is visible, so smart gaps drop the gaps even with several tiles
+    #[test]
+    fn test_monocle_layout_with_smart_gaps() {
+        let layout = GapLayout::new(MonocleLayout::new(), 5)
+            .with_inner_gap(10)
+            .with_smart_gaps(true);
+        let tiles = tiles(3);
+
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 800,
+                       height: 600,
+                   },
+                   layout.get_window_geometry(2, &SCREEN1, &tiles).ok().unwrap());
+    }
+
+    #[test]
+    fn test_monocle_layout_in_tile_manager() {
+        let mut focus_manager = FocusManager::new();
+        let mut manager = TileManager::new(SCREEN1, MonocleLayout::tabbed(20));
+        for window in 1..4 {
+            let info = WindowWithInfo::new_tiled(window, SOME_GEOM);
+            assert!(focus_manager.add_window(info.clone()).is_ok());
+            assert!(manager.add_window(info).is_ok());
+        }
+
+        // the added window is on top
+        assert_eq!(Some(3), manager.get_window_layout().last().map(|&(w, _)| w));
+
+        assert!(focus_manager.focus_window(Some(1)).is_ok());
+        assert!(manager.focus_shifted(Some(1)).is_ok());
+        assert_eq!(Some(1), manager.get_window_layout().last().map(|&(w, _)| w));
+        assert_eq!(vec![true, false, false],
+                   manager.get_tabs().iter().map(|t| t.focused).collect::<Vec<bool>>());
+
+        // swapping with the master focuses the window, so it comes on top
+        assert!(manager.swap_with_master(2, &mut focus_manager).is_ok());
+        assert_eq!(Some(2), manager.get_window_layout().last().map(|&(w, _)| w));
+        assert_eq!(vec![2, 1, 3],
+                   manager.get_tabs().iter().map(|t| t.window).collect::<Vec<Window>>());
+    }
+
+    #[test]
+    fn test_monocle_layout_in_float_manager() {
+        let mut manager = FloatOrTileManager::new(SCREEN1, MonocleLayout::tabbed(20));
+        assert!(manager.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+        assert!(manager.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+        assert!(manager.add_window(WindowWithInfo::new_float(3, SOME_GEOM)).is_ok());
+        assert!(manager.focus_shifted(Some(1)).is_ok());
+
+        // focusing a floating window keeps the last focused tile on top of the tiles
+        assert!(manager.focus_shifted(Some(3)).is_ok());
+        assert_eq!(vec![2, 1, 3],
+                   manager.get_window_layout().iter().map(|&(w, _)| w).collect::<Vec<Window>>());
+        assert_eq!(2, manager.get_tabs().len());
+        assert!(manager.get_tabs()[0].focused);
+    }
+}
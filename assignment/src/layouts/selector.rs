@@ -0,0 +1,479 @@
+//! A layout which switches between other layouts while running.
+//!
+//! A TileManager fixes the type of its layout, so the LayoutSelector holds its layouts as
+//! SelectableLayouts: one variant for every layout of this crate. Only the current layout lays out
+//! the tiles and handles the messages, but every layout is told about added, removed and swapped
+//! tiles and about the focus, so layouts which keep their own state about the tiles, like the
+//! BspLayout, are up to date when they are selected. Switching does not change the order of the
+//! tiles.
+
+use cplwm_api::types::{Geometry, PrevOrNext, Screen, Tab, Window};
+use wm_common::{LayoutMessage, LayoutSwitchTrait, MasterArea, MasterTrait, TilingLayout};
+use wm_common::error::StandardError;
+use b_tiling_wm::VerticalLayout;
+use h_different_tiling_layout::BasicDockLayout;
+use layouts::bsp::BspLayout;
+use layouts::centered::CenteredMasterLayout;
+use layouts::container::ContainerLayout;
+use layouts::grid::GridLayout;
+use layouts::monocle::MonocleLayout;
+use layouts::spiral::SpiralLayout;
+use std::collections::VecDeque;
+
+/// A layout a LayoutSelector can switch to
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub enum SelectableLayout {
+    /// see VerticalLayout
+    Vertical(VerticalLayout),
+    /// see BasicDockLayout
+    BasicDock(BasicDockLayout),
+    /// see GridLayout
+    Grid(GridLayout),
+    /// see SpiralLayout
+    Spiral(SpiralLayout),
+    /// see CenteredMasterLayout
+    CenteredMaster(CenteredMasterLayout),
+    /// see MonocleLayout
+    Monocle(MonocleLayout),
+    /// see BspLayout
+    Bsp(BspLayout),
+    /// see ContainerLayout
+    Container(ContainerLayout),
+}
+
+/// Evaluate the body with the layout in the given SelectableLayout bound to the given pattern
+macro_rules! with_layout {
+    ($selectable:expr, $binding:pat => $body:expr) => {
+        match $selectable {
+            SelectableLayout::Vertical($binding) => $body,
+            SelectableLayout::BasicDock($binding) => $body,
+            SelectableLayout::Grid($binding) => $body,
+            SelectableLayout::Spiral($binding) => $body,
+            SelectableLayout::CenteredMaster($binding) => $body,
+            SelectableLayout::Monocle($binding) => $body,
+            SelectableLayout::Bsp($binding) => $body,
+            SelectableLayout::Container($binding) => $body,
+        }
+    }
+}
+
+impl TilingLayout for SelectableLayout {
+    type Error = StandardError;
+
+    fn get_master_window(&self, tiles: &VecDeque<Window>) -> Option<Window> {
+        with_layout!(*self, ref layout => layout.get_master_window(tiles))
+    }
+
+    fn swap_with_master(&mut self,
+                        window: Window,
+                        tiles: &mut VecDeque<Window>)
+                        -> Result<(), Self::Error> {
+        with_layout!(*self, ref mut layout => layout.swap_with_master(window, tiles))
+    }
+
+    fn swap_windows(&mut self, window: Window, dir: PrevOrNext, tiles: &mut VecDeque<Window>) {
+        with_layout!(*self, ref mut layout => layout.swap_windows(window, dir, tiles))
+    }
+
+    fn swap_tiles(&mut self, window: Window, other: Window, tiles: &mut VecDeque<Window>) {
+        with_layout!(*self, ref mut layout => layout.swap_tiles(window, other, tiles))
+    }
+
+    fn get_window_geometry(&self,
+                           window: Window,
+                           screen: &Screen,
+                           tiles: &VecDeque<Window>)
+                           -> Result<Geometry, Self::Error> {
+        with_layout!(*self, ref layout => layout.get_window_geometry(window, screen, tiles))
+    }
+
+    fn get_window_geometries(&self,
+                             screen: &Screen,
+                             tiles: &VecDeque<Window>)
+                             -> Vec<(Window, Geometry)> {
+        with_layout!(*self, ref layout => layout.get_window_geometries(screen, tiles))
+    }
+
+    fn handle_message(&mut self, message: LayoutMessage) -> bool {
+        with_layout!(*self, ref mut layout => layout.handle_message(message))
+    }
+
+    fn focus_shifted(&mut self, window: Option<Window>) {
+        with_layout!(*self, ref mut layout => layout.focus_shifted(window))
+    }
+
+    fn tile_added(&mut self, window: Window) {
+        with_layout!(*self, ref mut layout => layout.tile_added(window))
+    }
+
+    fn tile_removed(&mut self, window: Window) {
+        with_layout!(*self, ref mut layout => layout.tile_removed(window))
+    }
+
+    fn screen_resized(&mut self, screen: &Screen) {
+        with_layout!(*self, ref mut layout => layout.screen_resized(screen))
+    }
+
+    fn get_stacking_order(&self, tiles: &VecDeque<Window>) -> Vec<Window> {
+        with_layout!(*self, ref layout => layout.get_stacking_order(tiles))
+    }
+
+    fn get_tabs(&self, screen: &Screen, tiles: &VecDeque<Window>) -> Vec<Tab> {
+        with_layout!(*self, ref layout => layout.get_tabs(screen, tiles))
+    }
+
+    fn get_focus_neighbour(&self,
+                           window: Window,
+                           dir: PrevOrNext,
+                           tiles: &VecDeque<Window>)
+                           -> Option<Window> {
+        with_layout!(*self,
+                     ref layout => TilingLayout::get_focus_neighbour(layout, window, dir, tiles))
+    }
+}
+
+impl SelectableLayout {
+    /// the master area of the layout, only the VerticalLayout and the CenteredMasterLayout have
+    /// one
+    fn get_master_area(&self) -> Option<&MasterArea> {
+        match *self {
+            SelectableLayout::Vertical(ref layout) => Some(&layout.master),
+            SelectableLayout::CenteredMaster(ref layout) => Some(&layout.master),
+            _ => None,
+        }
+    }
+
+    /// the master area of the layout, see get_master_area
+    fn get_master_area_mut(&mut self) -> Option<&mut MasterArea> {
+        match *self {
+            SelectableLayout::Vertical(ref mut layout) => Some(&mut layout.master),
+            SelectableLayout::CenteredMaster(ref mut layout) => Some(&mut layout.master),
+            _ => None,
+        }
+    }
+}
+
+/// Layouts without a master area report a master area without ratio and windows and ignore
+/// changes to it.
+impl MasterTrait for SelectableLayout {
+    fn get_master_ratio(&self) -> u32 {
+        self.get_master_area().map_or(0, |master| master.get_master_ratio())
+    }
+
+    fn get_master_count(&self) -> usize {
+        self.get_master_area().map_or(0, |master| master.get_master_count())
+    }
+
+    fn shrink_master(&mut self) {
+        match self.get_master_area_mut() {
+            None => {}
+            Some(master) => master.shrink_master(),
+        }
+    }
+
+    fn expand_master(&mut self) {
+        match self.get_master_area_mut() {
+            None => {}
+            Some(master) => master.expand_master(),
+        }
+    }
+
+    fn inc_master(&mut self) {
+        match self.get_master_area_mut() {
+            None => {}
+            Some(master) => master.inc_master(),
+        }
+    }
+
+    fn dec_master(&mut self) {
+        match self.get_master_area_mut() {
+            None => {}
+            Some(master) => master.dec_master(),
+        }
+    }
+}
+
+/// Layout which holds a list of named layouts and lays out the tiles with the current one
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct LayoutSelector {
+    /// the names and the layouts, in the order of next_layout. There is always at least one.
+    pub layouts: Vec<(String, SelectableLayout)>,
+    /// index of the current layout
+    pub current: usize,
+}
+
+impl LayoutSelector {
+    /// A LayoutSelector with just the given layout
+    pub fn new(name: &str, layout: SelectableLayout) -> LayoutSelector {
+        LayoutSelector {
+            layouts: vec![(name.to_string(), layout)],
+            current: 0,
+        }
+    }
+
+    /// Add the given layout after the other layouts. Layouts which keep their own state about
+    /// the tiles should be added before there are any tiles.
+    pub fn with_layout(mut self, name: &str, layout: SelectableLayout) -> LayoutSelector {
+        self.layouts.push((name.to_string(), layout));
+        self
+    }
+
+    /// A LayoutSelector with all layouts of this crate, starting with the VerticalLayout
+    pub fn standard() -> LayoutSelector {
+        LayoutSelector::new("vertical", SelectableLayout::Vertical(VerticalLayout::new()))
+            .with_layout("dock", SelectableLayout::BasicDock(BasicDockLayout {}))
+            .with_layout("grid", SelectableLayout::Grid(GridLayout::new()))
+            .with_layout("dwindle", SelectableLayout::Spiral(SpiralLayout::dwindle()))
+            .with_layout("spiral", SelectableLayout::Spiral(SpiralLayout::spiral()))
+            .with_layout("centered",
+                         SelectableLayout::CenteredMaster(CenteredMasterLayout::new()))
+            .with_layout("monocle", SelectableLayout::Monocle(MonocleLayout::new()))
+            .with_layout("tabbed", SelectableLayout::Monocle(MonocleLayout::tabbed(20)))
+            .with_layout("bsp", SelectableLayout::Bsp(BspLayout::new()))
+            .with_layout("i3", SelectableLayout::Container(ContainerLayout::new(20)))
+    }
+
+    /// The names of the layouts, in the order of next_layout
+    pub fn get_layout_names(&self) -> Vec<&str> {
+        self.layouts.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    fn current(&self) -> &SelectableLayout {
+        &self.layouts[self.current].1
+    }
+
+    fn current_mut(&mut self) -> &mut SelectableLayout {
+        &mut self.layouts[self.current].1
+    }
+}
+
+impl LayoutSwitchTrait for LayoutSelector {
+    fn get_layout_name(&self) -> &str {
+        &self.layouts[self.current].0
+    }
+
+    fn next_layout(&mut self) {
+        self.current = (self.current + 1) % self.layouts.len();
+    }
+
+    fn prev_layout(&mut self) {
+        self.current = (self.current + self.layouts.len() - 1) % self.layouts.len();
+    }
+
+    fn select_layout(&mut self, name: &str) -> bool {
+        match self.layouts.iter().position(|(n, _)| n == name) {
+            None => false,
+            Some(index) => {
+                self.current = index;
+                true
+            }
+        }
+    }
+}
+
+impl MasterTrait for LayoutSelector {
+    fn get_master_ratio(&self) -> u32 {
+        self.current().get_master_ratio()
+    }
+
+    fn get_master_count(&self) -> usize {
+        self.current().get_master_count()
+    }
+
+    fn shrink_master(&mut self) {
+        self.current_mut().shrink_master()
+    }
+
+    fn expand_master(&mut self) {
+        self.current_mut().expand_master()
+    }
+
+    fn inc_master(&mut self) {
+        self.current_mut().inc_master()
+    }
+
+    fn dec_master(&mut self) {
+        self.current_mut().dec_master()
+    }
+}
+
+impl TilingLayout for LayoutSelector {
+    type Error = StandardError;
+
+    fn get_master_window(&self, tiles: &VecDeque<Window>) -> Option<Window> {
+        self.current().get_master_window(tiles)
+    }
+
+    fn swap_with_master(&mut self,
+                        window: Window,
+                        tiles: &mut VecDeque<Window>)
+                        -> Result<(), Self::Error> {
+        self.current_mut().swap_with_master(window, tiles)
+    }
+
+    fn swap_windows(&mut self, window: Window, dir: PrevOrNext, tiles: &mut VecDeque<Window>) {
+        self.current_mut().swap_windows(window, dir, tiles)
+    }
+
+    /// The current layout swaps the tiles, the other layouts are told about the swap with a copy
+    /// of the tiles, so the tiles are swapped once
+    fn swap_tiles(&mut self, window: Window, other: Window, tiles: &mut VecDeque<Window>) {
+        let current = self.current;
+        let original = tiles.clone();
+        for (index, &mut (_, ref mut layout)) in self.layouts.iter_mut().enumerate() {
+            if index == current {
+                layout.swap_tiles(window, other, tiles)
+            } else {
+                layout.swap_tiles(window, other, &mut original.clone())
+            }
+        }
+    }
+
+    fn get_window_geometry(&self,
+                           window: Window,
+                           screen: &Screen,
+                           tiles: &VecDeque<Window>)
+                           -> Result<Geometry, Self::Error> {
+        self.current().get_window_geometry(window, screen, tiles)
+    }
+
+    fn get_window_geometries(&self,
+                             screen: &Screen,
+                             tiles: &VecDeque<Window>)
+                             -> Vec<(Window, Geometry)> {
+        self.current().get_window_geometries(screen, tiles)
+    }
+
+    /// Only the current layout gets the message
+    fn handle_message(&mut self, message: LayoutMessage) -> bool {
+        self.current_mut().handle_message(message)
+    }
+
+    /// Every layout is told about the focus
+    fn focus_shifted(&mut self, window: Option<Window>) {
+        for &mut (_, ref mut layout) in &mut self.layouts {
+            layout.focus_shifted(window)
+        }
+    }
+
+    /// Every layout is told about the new tile
+    fn tile_added(&mut self, window: Window) {
+        for &mut (_, ref mut layout) in &mut self.layouts {
+            layout.tile_added(window)
+        }
+    }
+
+    /// Every layout is told about the removed tile
+    fn tile_removed(&mut self, window: Window) {
+        for &mut (_, ref mut layout) in &mut self.layouts {
+            layout.tile_removed(window)
+        }
+    }
+
+    /// Every layout is told about the screen
+    fn screen_resized(&mut self, screen: &Screen) {
+        for &mut (_, ref mut layout) in &mut self.layouts {
+            layout.screen_resized(screen)
+        }
+    }
+
+    fn get_stacking_order(&self, tiles: &VecDeque<Window>) -> Vec<Window> {
+        self.current().get_stacking_order(tiles)
+    }
+
+    fn get_tabs(&self, screen: &Screen, tiles: &VecDeque<Window>) -> Vec<Tab> {
+        self.current().get_tabs(screen, tiles)
+    }
+
+    fn get_focus_neighbour(&self,
+                           window: Window,
+                           dir: PrevOrNext,
+                           tiles: &VecDeque<Window>)
+                           -> Option<Window> {
+        self.current().get_focus_neighbour(window, dir, tiles)
+    }
+}
+
+#[cfg(test)]
+mod selector_layout_tests {
+    use super::{LayoutSelector, SelectableLayout};
+    use wm_common::{LayoutMessage, LayoutSwitchTrait, MasterTrait, TilingLayout};
+    use b_tiling_wm::VerticalLayout;
+    use layouts::bsp::BspLayout;
+    use layouts::grid::GridLayout;
+    use std::collections::VecDeque;
+    use cplwm_api::types::*;
+
+    static SCREEN1: Screen = Screen {
+        width: 800,
+        height: 600,
+    };
+
+    fn selector() -> LayoutSelector {
+        LayoutSelector::new("vertical", SelectableLayout::Vertical(VerticalLayout::new()))
+            .with_layout("grid", SelectableLayout::Grid(GridLayout::new()))
+    }
+
+    #[test]
+    fn test_selector_names() {
+        let mut layout = selector();
+        assert_eq!(vec!["vertical", "grid"], layout.get_layout_names());
+        assert_eq!("vertical", layout.get_layout_name());
+
+        layout.next_layout();
+        assert_eq!("grid", layout.get_layout_name());
+        layout.next_layout();
+        assert_eq!("vertical", layout.get_layout_name());
+
+        assert_eq!(10, LayoutSelector::standard().get_layout_names().len());
+    }
+
+    #[test]
+    fn test_selector_uses_current_layout() {
+        let mut layout = selector();
+        let tiles: VecDeque<Window> = (1..5).collect();
+
+        assert!(layout.handle_message(LayoutMessage::ShrinkMaster));
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 360,
+                       height: 600,
+                   },
+                   layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap());
+
+        // the grid has no master area, so it ignores the messages about it
+        assert!(layout.select_layout("grid"));
+        assert!(!layout.handle_message(LayoutMessage::ExpandMaster));
+        layout.expand_master();
+        assert_eq!(0, layout.get_master_ratio());
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 400,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap());
+
+        // the other layouts keep their settings
+        assert!(layout.select_layout("vertical"));
+        assert_eq!(45, layout.get_master_ratio());
+    }
+
+    #[test]
+    fn test_selector_swaps_in_every_layout() {
+        let mut layout = LayoutSelector::new("vertical",
+                                             SelectableLayout::Vertical(VerticalLayout::new()))
+            .with_layout("bsp", SelectableLayout::Bsp(BspLayout::new()));
+        let mut tiles: VecDeque<Window> = (1..3).collect();
+        layout.tile_added(1);
+        layout.tile_added(2);
+        let first = layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap();
+
+        // the tiles are swapped once, the BspLayout swaps its leaves
+        layout.swap_tiles(1, 2, &mut tiles);
+        assert_eq!(Some(&2), tiles.front());
+        assert!(layout.select_layout("bsp"));
+        assert_eq!(first, layout.get_window_geometry(2, &SCREEN1, &tiles).ok().unwrap());
+    }
+}
@@ -0,0 +1,422 @@
+//! Layouts in which every tile splits the space left by the previous tile, alternating between
+//! vertical and horizontal splits, like the Dwindle and Spiral layouts of xmonad.
+//!
+//! In the dwindle variant the next tiles always go to the right and to the bottom, in the spiral
+//! variant the direction rotates clockwise so the tiles spiral inwards.
+//!
+//! ```text
+//!       Dwindle                 Spiral
+//! +--------+--------+    +--------+--------+
+//! |        |   2    |    |        |   2    |
+//! |   1    +---+----+    |   1    +----+---+
+//! |        | 3 | 4  |    |        | 4  | 3 |
+//! +--------+---+----+    +--------+----+---+
+//! ```
+
+use cplwm_api::types::{Geometry, PrevOrNext, Screen, Window};
+use wm_common::{LayoutMessage, TilingLayout};
+use wm_common::error::StandardError;
+use layouts::{swap_with_front, swap_with_neighbour};
+use std::collections::VecDeque;
+
+/// The direction in which the tiles of a SpiralLayout move
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq)]
+pub enum SpiralKind {
+    /// every next tile goes to the right or the bottom
+    Dwindle,
+    /// every next tile rotates clockwise
+    Spiral,
+}
+
+/// Layout where every tile takes `ratio` percent of the space left by the previous tiles, the
+/// first tile is the master
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct SpiralLayout {
+    /// the direction in which the tiles move
+    pub kind: SpiralKind,
+    /// percentage of the remaining space taken by every tile
+    pub ratio: u32,
+    /// percentage by which the ratio changes with ShrinkMaster and ExpandMaster messages
+    pub ratio_step: u32,
+}
+
+impl SpiralLayout {
+    /// A dwindle layout in which every tile takes half of the remaining space
+    pub fn dwindle() -> SpiralLayout {
+        SpiralLayout {
+            kind: SpiralKind::Dwindle,
+            ratio: 50,
+            ratio_step: 5,
+        }
+    }
+
+    /// A spiral layout in which every tile takes half of the remaining space
+    pub fn spiral() -> SpiralLayout {
+        SpiralLayout { kind: SpiralKind::Spiral, ..SpiralLayout::dwindle() }
+    }
+
+    /// Split the tile at the given index of the remaining space, returns the geometry of the tile
+    /// and the space which remains for the next tiles.
+    fn split(&self, index: usize, space: Geometry) -> (Geometry, Geometry) {
+        let width = space.width * self.ratio / 100;
+        let height = space.height * self.ratio / 100;
+        let turn = match self.kind {
+            SpiralKind::Dwindle => index % 2,
+            SpiralKind::Spiral => index % 4,
+        };
+        match turn {
+            // left
+            0 => {
+                (Geometry { width, ..space },
+                 Geometry {
+                    x: space.x + width as i32,
+                    width: space.width - width,
+                    ..space
+                })
+            }
+            // top
+            1 => {
+                (Geometry { height, ..space },
+                 Geometry {
+                    y: space.y + height as i32,
+                    height: space.height - height,
+                    ..space
+                })
+            }
+            // right
+            2 => {
+                (Geometry {
+                    x: space.x + (space.width - width) as i32,
+                    width,
+                    ..space
+                },
+                 Geometry { width: space.width - width, ..space })
+            }
+            // bottom
+            _ => {
+                (Geometry {
+                    y: space.y + (space.height - height) as i32,
+                    height,
+                    ..space
+                },
+                 Geometry { height: space.height - height, ..space })
+            }
+        }
+    }
+}
+
+impl TilingLayout for SpiralLayout {
+    type Error = StandardError;
+
+    fn get_master_window(&self, tiles: &VecDeque<Window>) -> Option<Window> {
+        tiles.front().copied()
+    }
+
+    fn swap_with_master(&mut self,
+                        window: Window,
+                        tiles: &mut VecDeque<Window>)
+                        -> Result<(), Self::Error> {
+        swap_with_front(window, tiles)
+    }
+
+    fn swap_windows(&mut self, window: Window, dir: PrevOrNext, tiles: &mut VecDeque<Window>) {
+        swap_with_neighbour(window, dir, tiles)
+    }
+
+    fn get_window_geometry(&self,
+                           window: Window,
+                           screen: &Screen,
+                           tiles: &VecDeque<Window>)
+                           -> Result<Geometry, Self::Error> {
+        match tiles.iter().position(|w| *w == window) {
+            None => Err(StandardError::UnknownWindow(window)),
+            Some(index) => {
+                let mut space = screen.to_geometry();
+                for i in 0..index {
+                    space = self.split(i, space).1;
+                }
+                // the last tile gets all the remaining space
+                if index == tiles.len() - 1 {
+                    Ok(space)
+                } else {
+                    Ok(self.split(index, space).0)
+                }
+            }
+        }
+    }
+
+    /// Handles ShrinkMaster and ExpandMaster by changing the ratio, a tile never disappears
+    fn handle_message(&mut self, message: LayoutMessage) -> bool {
+        match message {
+            LayoutMessage::ShrinkMaster => {
+                if self.ratio > self.ratio_step {
+                    self.ratio -= self.ratio_step;
+                }
+                true
+            }
+            LayoutMessage::ExpandMaster => {
+                if self.ratio + self.ratio_step < 100 {
+                    self.ratio += self.ratio_step;
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod spiral_layout_tests {
+    use super::SpiralLayout;
+    use wm_common::{LayoutMessage, TilingLayout};
+    use f_gaps::GapLayout;
+    use std::collections::VecDeque;
+    use cplwm_api::types::*;
+
+    static SCREEN1: Screen = Screen {
+        width: 800,
+        height: 600,
+    };
+
+    static SCREEN2: Screen = Screen {
+        width: 301,
+        height: 401,
+    };
+
+    fn tiles(amount: Window) -> VecDeque<Window> {
+        (1..amount + 1).collect()
+    }
+
+    #[test]
+    fn test_spiral_layout_no_window() {
+        let layout = SpiralLayout::dwindle();
+        let tiles = VecDeque::new();
+
+        // make sure there is no geometry.
+        assert!(layout.get_window_geometry(1, &SCREEN1, &tiles).is_err());
+        assert_eq!(None, layout.get_master_window(&tiles));
+    }
+
+    #[test]
+    fn test_spiral_layout_one_window() {
+        let layout = SpiralLayout::spiral();
+        let tiles = tiles(1);
+
+        assert_eq!(SCREEN1.to_geometry(),
+                   layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap());
+    }
+
+    #[test]
+    fn test_dwindle_layout_multiple_windows() {
+        let layout = SpiralLayout::dwindle();
+        let tiles = tiles(4);
+
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 400,
+                       height: 600,
+                   },
+                   layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 400,
+                       y: 0,
+                       width: 400,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(2, &SCREEN1, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 400,
+                       y: 300,
+                       width: 200,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(3, &SCREEN1, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 600,
+                       y: 300,
+                       width: 200,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(4, &SCREEN1, &tiles).ok().unwrap());
+
+        // any other window should return an error
+        assert!(layout.get_window_geometry(5, &SCREEN1, &tiles).is_err());
+    }
+
+    #[test]
+    fn test_spiral_layout_multiple_windows() {
+        let layout = SpiralLayout::spiral();
+        let tiles = tiles(5);
+
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 400,
+                       height: 600,
+                   },
+                   layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 400,
+                       y: 0,
+                       width: 400,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(2, &SCREEN1, &tiles).ok().unwrap());
+
+        // the third tile goes to the right of the remaining space
+        assert_eq!(Geometry {
+                       x: 600,
+                       y: 300,
+                       width: 200,
+                       height: 300,
+                   },
+                   layout.get_window_geometry(3, &SCREEN1, &tiles).ok().unwrap());
+
+        // the fourth tile goes to the bottom of the remaining space
+        assert_eq!(Geometry {
+                       x: 400,
+                       y: 450,
+                       width: 200,
+                       height: 150,
+                   },
+                   layout.get_window_geometry(4, &SCREEN1, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 400,
+                       y: 300,
+                       width: 200,
+                       height: 150,
+                   },
+                   layout.get_window_geometry(5, &SCREEN1, &tiles).ok().unwrap());
+    }
+
+    // test to see this layout gives the remaining pixels of irregular screens to the next tiles
+    #[test]
+    fn test_spiral_layout_multiple_windows_irregular_screen() {
+        let layout = SpiralLayout::spiral();
+        let tiles = tiles(4);
+
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 150,
+                       height: 401,
+                   },
+                   layout.get_window_geometry(1, &SCREEN2, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 150,
+                       y: 0,
+                       width: 151,
+                       height: 200,
+                   },
+                   layout.get_window_geometry(2, &SCREEN2, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 226,
+                       y: 200,
+                       width: 75,
+                       height: 201,
+                   },
+                   layout.get_window_geometry(3, &SCREEN2, &tiles).ok().unwrap());
+
+        // last one should get remaining screen space.
+        assert_eq!(Geometry {
+                       x: 150,
+                       y: 200,
+                       width: 76,
+                       height: 201,
+                   },
+                   layout.get_window_geometry(4, &SCREEN2, &tiles).ok().unwrap());
+    }
+
+    #[test]
+    fn test_spiral_layout_tiles_cover_screen() {
+        for layout in &[SpiralLayout::dwindle(), SpiralLayout::spiral()] {
+            for amount in 1..12 {
+                let tiles = tiles(amount);
+                let area: u32 = tiles.iter()
+                    .map(|w| layout.get_window_geometry(*w, &SCREEN2, &tiles).ok().unwrap())
+                    .map(|g| g.width * g.height)
+                    .sum();
+                assert_eq!(SCREEN2.width * SCREEN2.height, area);
+            }
+        }
+    }
+
+    #[test]
+    fn test_spiral_layout_ratio() {
+        let mut layout = SpiralLayout::dwindle();
+        layout.ratio = 60;
+        let tiles = tiles(3);
+
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 480,
+                       height: 600,
+                   },
+                   layout.get_window_geometry(1, &SCREEN1, &tiles).ok().unwrap());
+
+        assert_eq!(Geometry {
+                       x: 480,
+                       y: 0,
+                       width: 320,
+                       height: 360,
+                   },
+                   layout.get_window_geometry(2, &SCREEN1, &tiles).ok().unwrap());
+
+        assert!(layout.handle_message(LayoutMessage::ShrinkMaster));
+        assert_eq!(55, layout.ratio);
+        assert!(layout.handle_message(LayoutMessage::ExpandMaster));
+        assert!(layout.handle_message(LayoutMessage::ExpandMaster));
+        assert_eq!(65, layout.ratio);
+        assert!(!layout.handle_message(LayoutMessage::IncMaster));
+
+        // a tile never disappears
+        for _ in 0..20 {
+            layout.handle_message(LayoutMessage::ShrinkMaster);
+        }
+        assert_eq!(5, layout.ratio);
+    }
+
+    #[test]
+    fn test_spiral_layout_master() {
+        let mut layout = SpiralLayout::spiral();
+        let mut tiles = tiles(4);
+
+        assert_eq!(Some(1), layout.get_master_window(&tiles));
+        assert!(layout.swap_with_master(3, &mut tiles).is_ok());
+        assert_eq!(Some(3), layout.get_master_window(&tiles));
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 400,
+                       height: 600,
+                   },
+                   layout.get_window_geometry(3, &SCREEN1, &tiles).ok().unwrap());
+        assert!(layout.swap_with_master(5, &mut tiles).is_err());
+
+        layout.swap_windows(3, PrevOrNext::Prev, &mut tiles);
+        assert_eq!(vec![4, 2, 1, 3], tiles.iter().cloned().collect::<Vec<Window>>());
+    }
+
+    #[test]
+    fn test_spiral_layout_with_gap() {
+        let layout = GapLayout::new(SpiralLayout::dwindle(), 5);
+        let tiles = tiles(2);
+
+        assert_eq!(Geometry {
+                       x: 405,
+                       y: 5,
+                       width: 390,
+                       height: 590,
+                   },
+                   layout.get_window_geometry(2, &SCREEN1, &tiles).ok().unwrap());
+    }
+}
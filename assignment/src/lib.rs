@@ -83,3 +83,7 @@ pub mod h_different_tiling_layout;
 
 // Declare additional modules below or declare them in other modules.
 pub mod wm_common;
+pub mod layouts;
+pub mod rules;
+pub mod struts;
+pub mod switcher;
@@ -0,0 +1,312 @@
+//! Window rules, e.g. "always float pavucontrol" or "send the browser to workspace 2".
+//!
+//! A rule matches the properties of a window, like its class or title, and lists the actions
+//! applied to a matching window when it is added. The rules are kept in order: all matching rules
+//! apply, so a later rule overrides the conflicting actions of an earlier one.
+//!
+//! Matching only looks at the WindowProperties, so it works without a display. Window managers
+//! apply the rules to the properties of the windows passed to `add_window`, and offer them
+//! through the WindowRuleSupport trait of wm_common.
+
+use cplwm_api::types::{FloatOrTile, Geometry, WindowProperties, WindowWithInfo, WorkspaceIndex};
+
+/// A pattern a single property of a window has to match
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone, PartialEq, Eq)]
+pub enum PropertyPattern {
+    /// the property equals the string
+    Equals(String),
+    /// the property contains the string
+    Contains(String),
+}
+
+impl PropertyPattern {
+    /// whether the property matches, a missing property never matches
+    pub fn matches(&self, property: &Option<String>) -> bool {
+        match (self, property) {
+            (_, &None) => false,
+            (PropertyPattern::Equals(pattern), Some(value)) => value == pattern,
+            (PropertyPattern::Contains(pattern), Some(value)) => value.contains(pattern),
+        }
+    }
+}
+
+/// The criteria of a rule, a window matches when all given patterns match
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone, PartialEq, Eq)]
+pub struct WindowMatcher {
+    /// pattern for the class of the window
+    pub class: Option<PropertyPattern>,
+    /// pattern for the instance of the window
+    pub instance: Option<PropertyPattern>,
+    /// pattern for the title of the window
+    pub title: Option<PropertyPattern>,
+    /// pattern for the role of the window
+    pub role: Option<PropertyPattern>,
+}
+
+impl WindowMatcher {
+    /// matcher without criteria, which matches every window
+    pub fn any() -> WindowMatcher {
+        WindowMatcher {
+            class: None,
+            instance: None,
+            title: None,
+            role: None,
+        }
+    }
+
+    /// builder adding a pattern for the class
+    pub fn with_class(self, pattern: PropertyPattern) -> WindowMatcher {
+        WindowMatcher { class: Some(pattern), ..self }
+    }
+
+    /// builder adding a pattern for the instance
+    pub fn with_instance(self, pattern: PropertyPattern) -> WindowMatcher {
+        WindowMatcher { instance: Some(pattern), ..self }
+    }
+
+    /// builder adding a pattern for the title
+    pub fn with_title(self, pattern: PropertyPattern) -> WindowMatcher {
+        WindowMatcher { title: Some(pattern), ..self }
+    }
+
+    /// builder adding a pattern for the role
+    pub fn with_role(self, pattern: PropertyPattern) -> WindowMatcher {
+        WindowMatcher { role: Some(pattern), ..self }
+    }
+
+    /// whether the window with the given properties meets all criteria
+    pub fn matches(&self, properties: &WindowProperties) -> bool {
+        let criteria = [(&self.class, &properties.class),
+                        (&self.instance, &properties.instance),
+                        (&self.title, &properties.title),
+                        (&self.role, &properties.role)];
+        criteria.iter().all(|&(pattern, property)| {
+            pattern.as_ref().map(|pattern| pattern.matches(property)).unwrap_or(true)
+        })
+    }
+}
+
+/// What a rule does with a matching window
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleAction {
+    /// float the window
+    Float,
+    /// tile the window
+    Tile,
+    /// show the window fullscreen, ignored by window managers without fullscreen windows
+    Fullscreen,
+    /// minimise the window
+    Minimise,
+    /// add the window to the workspace with the given index
+    Workspace(WorkspaceIndex),
+    /// float the window at the given geometry
+    Geometry(Geometry),
+    /// do not give the window the focus
+    NoFocus,
+}
+
+/// A rule: the actions applied to windows matching the matcher
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone, PartialEq, Eq)]
+pub struct WindowRule {
+    /// the criteria of the rule
+    pub matcher: WindowMatcher,
+    /// the actions, in order
+    pub actions: Vec<RuleAction>,
+}
+
+/// How a window is added after applying the rules
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleOutcome {
+    /// the window info with the float or tile state, fullscreen and geometry set by the rules
+    pub window_with_info: WindowWithInfo,
+    /// whether the window is minimised right after adding it
+    pub minimised: bool,
+    /// the workspace to add the window to, the current one when None
+    pub workspace: Option<WorkspaceIndex>,
+    /// whether the window gets the focus
+    pub focus: bool,
+}
+
+/// An ordered list of window rules
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone, PartialEq, Eq)]
+pub struct WindowRules {
+    /// the rules, in order
+    pub rules: Vec<WindowRule>,
+}
+
+impl WindowRules {
+    /// creates an empty rule list
+    pub fn new() -> WindowRules {
+        WindowRules { rules: Vec::new() }
+    }
+
+    /// builder adding a rule at the end of the list
+    pub fn with_rule(mut self, matcher: WindowMatcher, actions: Vec<RuleAction>) -> WindowRules {
+        self.rules.push(WindowRule {
+            matcher,
+            actions,
+        });
+        self
+    }
+
+    /// The actions of all rules matching the properties, in order.
+    pub fn get_actions(&self, properties: &WindowProperties) -> Vec<RuleAction> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.matcher.matches(properties))
+            .flat_map(|rule| rule.actions.iter().cloned())
+            .collect()
+    }
+
+    /// The workspace the matching rules send a window with the properties to, None when no rule
+    /// does.
+    pub fn get_workspace(&self, properties: &WindowProperties) -> Option<WorkspaceIndex> {
+        self.get_actions(properties)
+            .into_iter()
+            .filter_map(|action| match action {
+                RuleAction::Workspace(index) => Some(index),
+                _ => None,
+            })
+            .next_back()
+    }
+
+    /// Apply the actions of the rules matching the properties of the window, a later action
+    /// overrides an earlier conflicting one.
+    pub fn apply(&self, window_with_info: WindowWithInfo) -> RuleOutcome {
+        let actions = self.get_actions(&window_with_info.properties);
+        let mut outcome = RuleOutcome {
+            window_with_info,
+            minimised: false,
+            workspace: None,
+            focus: true,
+        };
+        for action in actions {
+            match action {
+                RuleAction::Float => outcome.window_with_info.float_or_tile = FloatOrTile::Float,
+                RuleAction::Tile => outcome.window_with_info.float_or_tile = FloatOrTile::Tile,
+                RuleAction::Fullscreen => outcome.window_with_info.fullscreen = true,
+                RuleAction::Minimise => outcome.minimised = true,
+                RuleAction::Workspace(index) => outcome.workspace = Some(index),
+                RuleAction::Geometry(geometry) => {
+                    outcome.window_with_info.geometry = geometry;
+                    outcome.window_with_info.float_or_tile = FloatOrTile::Float;
+                }
+                RuleAction::NoFocus => outcome.focus = false,
+            }
+        }
+        outcome
+    }
+}
+
+impl Default for WindowRules {
+    fn default() -> WindowRules {
+        WindowRules::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PropertyPattern, RuleAction, WindowMatcher, WindowRules};
+    use cplwm_api::types::*;
+
+    static SOME_GEOM: Geometry = Geometry {
+        x: 10,
+        y: 10,
+        width: 100,
+        height: 100,
+    };
+
+    fn equals(value: &str) -> PropertyPattern {
+        PropertyPattern::Equals(value.to_string())
+    }
+
+    fn contains(value: &str) -> PropertyPattern {
+        PropertyPattern::Contains(value.to_string())
+    }
+
+    fn pavucontrol() -> WindowProperties {
+        WindowProperties::new()
+            .with_class("Pavucontrol", "pavucontrol")
+            .with_title("Volume Control")
+    }
+
+    fn firefox() -> WindowProperties {
+        WindowProperties::new()
+            .with_class("Firefox", "Navigator")
+            .with_title("Mozilla Firefox")
+            .with_role("browser")
+    }
+
+    fn tiled(properties: WindowProperties) -> WindowWithInfo {
+        WindowWithInfo::new_tiled(1, SOME_GEOM).with_properties(properties)
+    }
+
+    #[test]
+    fn test_property_patterns() {
+        let title = Some("Mozilla Firefox".to_string());
+        assert!(equals("Mozilla Firefox").matches(&title));
+        assert!(!equals("Firefox").matches(&title));
+        assert!(contains("Firefox").matches(&title));
+        assert!(!contains("Firefox").matches(&None));
+    }
+
+    #[test]
+    fn test_window_matcher() {
+        let browser = WindowMatcher::any()
+            .with_class(equals("Firefox"))
+            .with_role(equals("browser"));
+
+        assert!(WindowMatcher::any().matches(&WindowProperties::new()));
+        assert!(browser.matches(&firefox()));
+        assert!(!browser.matches(&pavucontrol()));
+        // a window without role does not match a matcher on the role
+        assert!(!browser.matches(&WindowProperties::new().with_class("Firefox", "Navigator")));
+    }
+
+    #[test]
+    fn test_window_rules_apply() {
+        let rules = WindowRules::new()
+            .with_rule(WindowMatcher::any().with_class(equals("Pavucontrol")),
+                       vec![RuleAction::Float, RuleAction::NoFocus])
+            .with_rule(WindowMatcher::any().with_role(equals("browser")),
+                       vec![RuleAction::Workspace(2), RuleAction::Minimise]);
+        let outcome = rules.apply(tiled(pavucontrol()));
+        assert_eq!(FloatOrTile::Float, outcome.window_with_info.float_or_tile);
+        assert!(!outcome.focus);
+        assert_eq!(None, outcome.workspace);
+        assert_eq!(None, rules.get_workspace(&pavucontrol()));
+
+        let outcome = rules.apply(tiled(firefox()));
+        assert_eq!(tiled(firefox()), outcome.window_with_info);
+        assert_eq!(Some(2), outcome.workspace);
+        assert_eq!(Some(2), rules.get_workspace(&firefox()));
+        assert!(outcome.minimised);
+        assert!(outcome.focus);
+
+        let outcome = rules.apply(tiled(WindowProperties::new()));
+        assert_eq!(tiled(WindowProperties::new()), outcome.window_with_info);
+        assert!(!outcome.minimised);
+    }
+
+    // all matching rules apply in order, the last conflicting action wins
+    #[test]
+    fn test_window_rules_order() {
+        let rules = WindowRules::new()
+            .with_rule(WindowMatcher::any(), vec![RuleAction::Geometry(SOME_GEOM)])
+            .with_rule(WindowMatcher::any().with_title(contains("Firefox")),
+                       vec![RuleAction::Tile, RuleAction::Fullscreen]);
+        let geometry = Geometry { x: 0, ..SOME_GEOM };
+
+        assert_eq!(vec![RuleAction::Geometry(SOME_GEOM), RuleAction::Tile, RuleAction::Fullscreen],
+                   rules.get_actions(&firefox()));
+        let window_with_info = WindowWithInfo::new_tiled(1, geometry).with_properties(firefox());
+        let outcome = rules.apply(window_with_info);
+        assert_eq!(WindowWithInfo::new_fullscreen(1, SOME_GEOM).with_properties(firefox()),
+                   outcome.window_with_info);
+
+        let outcome =
+            rules.apply(WindowWithInfo::new_tiled(1, geometry).with_properties(pavucontrol()));
+        assert_eq!(WindowWithInfo::new_float(1, SOME_GEOM).with_properties(pavucontrol()),
+                   outcome.window_with_info);
+    }
+}
@@ -0,0 +1,106 @@
+//! Reserved screen areas of panels and docks.
+//!
+//! The StrutManager keeps the static strut and the struts of the dock windows, and stacks them
+//! into the strut reserving the area of all of them. The window managers give this strut to their
+//! LayoutManager, which keeps the tiles and the fullscreen window in the usable area of the
+//! screen, see StrutSupport.
+//!
+//! ```text
+//! +-----------------------+
+//! |        status bar     |
+//! +-----------+-----------+
+//! |           |           |
+//! |     1     |     2     |
+//! |           |           |
+//! +-----------+-----------+
+//! ```
+
+use cplwm_api::types::{Strut, Window};
+use std::collections::BTreeMap;
+
+/// Manager keeping the static strut and the strut of every dock window
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct StrutManager {
+    /// the strut which is not tied to a window
+    pub static_strut: Strut,
+    /// the strut of every window reserving an area
+    pub window_struts: BTreeMap<Window, Strut>,
+}
+
+impl StrutManager {
+    /// creates a StrutManager which reserves nothing
+    pub fn new() -> StrutManager {
+        StrutManager {
+            static_strut: Strut::none(),
+            window_struts: BTreeMap::new(),
+        }
+    }
+
+    /// the strut of the window, if any
+    pub fn get_window_strut(&self, window: Window) -> Option<Strut> {
+        self.window_struts.get(&window).copied()
+    }
+
+    /// set the strut of the window, replacing its previous one
+    pub fn set_window_strut(&mut self, window: Window, strut: Strut) {
+        self.window_struts.insert(window, strut);
+    }
+
+    /// forget the strut of the window, e.g. when it is removed from the window manager
+    pub fn remove_window_strut(&mut self, window: Window) {
+        self.window_struts.remove(&window);
+    }
+
+    /// The static strut and the struts of all windows stacked together.
+    pub fn get_strut(&self) -> Strut {
+        self.window_struts.values().fold(self.static_strut, |total, strut| total.stack(strut))
+    }
+}
+
+impl Default for StrutManager {
+    fn default() -> StrutManager {
+        StrutManager::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StrutManager;
+    use cplwm_api::types::*;
+
+    static SCREEN: Screen = Screen {
+        width: 800,
+        height: 600,
+    };
+
+    #[test]
+    fn test_struts_stack() {
+        let mut manager = StrutManager::new();
+        assert_eq!(Strut::none(), manager.get_strut());
+
+        manager.static_strut = Strut::new(0, 0, 0, 50);
+        manager.set_window_strut(1, Strut::new(24, 0, 0, 0));
+        manager.set_window_strut(2, Strut::new(20, 0, 30, 0));
+        assert_eq!(Strut::new(44, 0, 30, 50), manager.get_strut());
+        assert_eq!(Geometry {
+                       x: 50,
+                       y: 44,
+                       width: 750,
+                       height: 526,
+                   },
+                   manager.get_strut().get_usable_area(&SCREEN));
+
+        // a new strut replaces the previous one of the window
+        manager.set_window_strut(2, Strut::new(0, 10, 0, 0));
+        manager.remove_window_strut(1);
+        assert_eq!(Some(Strut::new(0, 10, 0, 0)), manager.get_window_strut(2));
+        assert_eq!(None, manager.get_window_strut(1));
+        assert_eq!(Strut::new(0, 10, 0, 50), manager.get_strut());
+    }
+
+    #[test]
+    fn test_struts_cover_screen() {
+        let area = Strut::new(400, 500, 300, 400).get_usable_area(&SCREEN);
+        assert_eq!((400, 400, 0, 0), (area.x, area.y, area.width, area.height));
+    }
+}
@@ -0,0 +1,176 @@
+//! Alt-tab like switching between the windows in most recently used order.
+//!
+//! A switch session starts with all windows in the order they were focused, the focused window
+//! first. Stepping moves the candidate through these windows, the window manager shows the
+//! candidate raised in its window layout but leaves the focus and the stacking alone. Committing
+//! the session focuses the candidate, cancelling it leaves everything as it was before the
+//! session. Window managers offer the sessions through the WindowSwitcherSupport trait of
+//! wm_common.
+
+use cplwm_api::types::{Geometry, PrevOrNext, Window};
+
+/// The state of a switch session
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct WindowSwitcher {
+    /// the windows of the session in most recently used order, empty without session
+    pub windows: Vec<Window>,
+    /// index of the candidate in the windows
+    pub index: usize,
+}
+
+impl WindowSwitcher {
+    /// creates a WindowSwitcher without session
+    pub fn new() -> WindowSwitcher {
+        WindowSwitcher {
+            windows: Vec::new(),
+            index: 0,
+        }
+    }
+
+    /// whether a session is going on
+    pub fn is_active(&self) -> bool {
+        !self.windows.is_empty()
+    }
+
+    /// Begin a session with the windows, most recently used first. The first candidate is the
+    /// second window, the one used before the focused one, unless there is only one window.
+    pub fn begin(&mut self, windows: Vec<Window>) {
+        self.index = if windows.len() > 1 { 1 } else { 0 };
+        self.windows = windows;
+    }
+
+    /// Step to the next, less recently used, or the previous candidate, wrapping around.
+    pub fn step(&mut self, dir: PrevOrNext) {
+        let len = self.windows.len();
+        if len > 0 {
+            self.index = match dir {
+                PrevOrNext::Next => (self.index + 1) % len,
+                PrevOrNext::Prev => (self.index + len - 1) % len,
+            }
+        }
+    }
+
+    /// the candidate, None without session
+    pub fn get_candidate(&self) -> Option<Window> {
+        self.windows.get(self.index).copied()
+    }
+
+    /// End the session, returns the candidate
+    pub fn end(&mut self) -> Option<Window> {
+        let candidate = self.get_candidate();
+        self.windows.clear();
+        self.index = 0;
+        candidate
+    }
+
+    /// Forget the window, e.g. when it is removed from the window manager. When the candidate is
+    /// removed the next window becomes the candidate.
+    pub fn remove_window(&mut self, window: Window) {
+        match self.windows.iter().position(|w| *w == window) {
+            None => {}
+            Some(i) => {
+                self.windows.remove(i);
+                if i < self.index {
+                    self.index -= 1;
+                }
+                if self.index >= self.windows.len() {
+                    self.index = 0;
+                }
+            }
+        }
+    }
+
+    /// Raise the candidate to the top of the windows of a window layout. A candidate which is not
+    /// in the layout, e.g. because it is minimised, is shown with the geometry `get_geometry`
+    /// gives it.
+    pub fn preview<F>(&self,
+                      mut windows: Vec<(Window, Geometry)>,
+                      get_geometry: F)
+                      -> Vec<(Window, Geometry)>
+        where F: Fn(Window) -> Option<Geometry>
+    {
+        match self.get_candidate() {
+            None => {}
+            Some(candidate) => {
+                let raised = match windows.iter().position(|&(w, _)| w == candidate) {
+                    Some(i) => Some(windows.remove(i)),
+                    None => get_geometry(candidate).map(|geometry| (candidate, geometry)),
+                };
+                windows.extend(raised);
+            }
+        }
+        windows
+    }
+}
+
+impl Default for WindowSwitcher {
+    fn default() -> WindowSwitcher {
+        WindowSwitcher::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WindowSwitcher;
+    use cplwm_api::types::*;
+
+    static SOME_GEOM: Geometry = Geometry {
+        x: 10,
+        y: 10,
+        width: 100,
+        height: 100,
+    };
+
+    #[test]
+    fn test_switch_session() {
+        let mut switcher = WindowSwitcher::new();
+        assert!(!switcher.is_active());
+        assert_eq!(None, switcher.get_candidate());
+        switcher.step(PrevOrNext::Next);
+        assert_eq!(None, switcher.end());
+
+        switcher.begin(vec![3, 1, 2]);
+        assert!(switcher.is_active());
+        assert_eq!(Some(1), switcher.get_candidate());
+        switcher.step(PrevOrNext::Next);
+        assert_eq!(Some(2), switcher.get_candidate());
+        switcher.step(PrevOrNext::Next);
+        assert_eq!(Some(3), switcher.get_candidate());
+        switcher.step(PrevOrNext::Prev);
+        assert_eq!(Some(2), switcher.end());
+        assert!(!switcher.is_active());
+
+        // with a single window the focused window is the candidate
+        switcher.begin(vec![3]);
+        assert_eq!(Some(3), switcher.get_candidate());
+    }
+
+    #[test]
+    fn test_switch_remove_window() {
+        let mut switcher = WindowSwitcher::new();
+        switcher.begin(vec![3, 1, 2]);
+        switcher.remove_window(3);
+        assert_eq!(Some(1), switcher.get_candidate());
+        switcher.remove_window(1);
+        assert_eq!(Some(2), switcher.get_candidate());
+        switcher.remove_window(2);
+        assert_eq!(None, switcher.get_candidate());
+        assert!(!switcher.is_active());
+    }
+
+    #[test]
+    fn test_switch_preview() {
+        let mut switcher = WindowSwitcher::new();
+        let geometry = Geometry { x: 0, ..SOME_GEOM };
+        let windows = vec![(1, SOME_GEOM), (2, geometry)];
+        assert_eq!(windows, switcher.preview(windows.clone(), |_| None));
+
+        switcher.begin(vec![2, 1, 3]);
+        assert_eq!(vec![(2, geometry), (1, SOME_GEOM)],
+                   switcher.preview(windows.clone(), |_| None));
+        // the minimised window 3 is shown on top
+        switcher.step(PrevOrNext::Next);
+        assert_eq!(vec![(1, SOME_GEOM), (2, geometry), (3, SOME_GEOM)],
+                   switcher.preview(windows.clone(), |_| Some(SOME_GEOM)));
+    }
+}
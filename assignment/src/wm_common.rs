@@ -5,8 +5,39 @@ use std::fmt::Debug;
 use std::collections::VecDeque;
 
 use cplwm_api::types::*;
-
-use a_fullscreen_wm::FocusManager;
+use cplwm_api::wm::{MinimiseSupport, TilingSupport, WindowManager};
+
+use a_fullscreen_wm::{FocusManager, FocusPolicy, RemovalFocus};
+use rules::WindowRules;
+use layouts::swap_with_tile;
+
+/// A message a window manager can send to its TilingLayout, e.g. when a keybinding is pressed.
+/// A layout ignores the messages it does not understand.
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq, Hash)]
+pub enum LayoutMessage {
+    /// Shrink the master area, see MasterTrait
+    ShrinkMaster,
+    /// Expand the master area, see MasterTrait
+    ExpandMaster,
+    /// Allow one more window in the master area, see MasterTrait
+    IncMaster,
+    /// Allow one window less in the master area, see MasterTrait
+    DecMaster,
+    /// Rotate the part of the layout around the focused tile clockwise by 90 degrees
+    Rotate,
+    /// Mirror the part of the layout around the focused tile
+    Flip,
+    /// Split the focused tile horizontally, the next tile is placed on its right
+    SplitHorizontal,
+    /// Split the focused tile vertically, the next tile is placed below it
+    SplitVertical,
+    /// Show the container of the focused tile as tabs
+    Tabbed,
+    /// Show the container of the focused tile as a stack of title bars
+    Stacked,
+    /// Toggle the container of the focused tile between splitting horizontally and vertically
+    ToggleSplit,
+}
 
 /// Trait which defines an interface to a Tiling Layout strategy
 pub trait TilingLayout: Encodable + Decodable + Debug + Clone {
@@ -17,13 +48,19 @@ pub trait TilingLayout: Encodable + Decodable + Debug + Clone {
     fn get_master_window(&self, tiles: &VecDeque<Window>) -> Option<Window>;
     /// Swap the given window with the current window in the master tile.
     /// Should return an error when the window is not in the given tiles VecDeque.
-    fn swap_with_master(&self,
+    fn swap_with_master(&mut self,
                         window: Window,
                         tiles: &mut VecDeque<Window>)
                         -> Result<(), Self::Error>;
     /// Swaps the given window with the next or previous window according to this TilingLayout.
     /// Does nothing when the given window is not in the given tiles.
-    fn swap_windows(&self, window: Window, dir: PrevOrNext, tiles: &mut VecDeque<Window>);
+    fn swap_windows(&mut self, window: Window, dir: PrevOrNext, tiles: &mut VecDeque<Window>);
+    /// Swap the places of the two given tiles. By default they swap their places in the given
+    /// tiles, layouts which keep their own state about the tiles swap them there.
+    /// Does nothing when one of the windows is not in the given tiles.
+    fn swap_tiles(&mut self, window: Window, other: Window, tiles: &mut VecDeque<Window>) {
+        swap_with_tile(window, other, tiles)
+    }
     /// Get the geometry of a window in this layout from the provided VecDeque of tiles.
     /// Returns an error if the given window is not in the given tiles.
     fn get_window_geometry(&self,
@@ -31,16 +68,162 @@ pub trait TilingLayout: Encodable + Decodable + Debug + Clone {
                            screen: &Screen,
                            tiles: &VecDeque<Window>)
                            -> Result<Geometry, Self::Error>;
+    /// Get the geometries of all the given tiles, in the order of the tiles. Layouts which look at
+    /// all tiles for the geometry of one tile do this once here.
+    fn get_window_geometries(&self,
+                             screen: &Screen,
+                             tiles: &VecDeque<Window>)
+                             -> Vec<(Window, Geometry)> {
+        tiles.iter()
+            .filter_map(|w| self.get_window_geometry(*w, screen, tiles).ok().map(|g| (*w, g)))
+            .collect()
+    }
+    /// Handle the given message. Returns true if this layout understood the message, false if
+    /// it ignored it.
+    fn handle_message(&mut self, message: LayoutMessage) -> bool;
+
+    /// Tell the layout which tile got the focus, layouts with overlapping tiles use this to put
+    /// the focused tile on top. Layouts which do not care about the focus ignore it.
+    fn focus_shifted(&mut self, _: Option<Window>) {}
+    /// Tell the layout a tile was added at the back of the tiles, layouts which keep their own
+    /// state about the tiles use this to update it.
+    fn tile_added(&mut self, _: Window) {}
+    /// Tell the layout a tile was removed from the tiles.
+    fn tile_removed(&mut self, _: Window) {}
+    /// Tell the layout the size of the screen of the tiles, when it is created and whenever the
+    /// screen is resized, e.g. because it rotated.
+    fn screen_resized(&mut self, _: &Screen) {}
+    /// The given tiles ordered from the bottom tile to the top tile. By default the tiles do not
+    /// overlap, so they keep their order.
+    fn get_stacking_order(&self, tiles: &VecDeque<Window>) -> Vec<Window> {
+        tiles.iter().copied().collect()
+    }
+    /// The tabs of the tab bar of this layout for the given tiles, empty when this layout has no
+    /// tab bar.
+    fn get_tabs(&self, _: &Screen, _: &VecDeque<Window>) -> Vec<Tab> {
+        Vec::new()
+    }
+    /// The tile which gets the focus when the focus moves from the given tile to the previous or
+    /// next tile, for layouts which decide this themselves. None when the window manager should
+    /// cycle the focus as usual, which is the default.
+    fn get_focus_neighbour(&self,
+                           _: Window,
+                           _: PrevOrNext,
+                           _: &VecDeque<Window>)
+                           -> Option<Window> {
+        None
+    }
 }
 
-/// Trait describing what a layoutmanager with gap support could do
-pub trait GapTrait: TilingLayout {
+/// Trait describing a layout with gap support, managers and window managers using such a layout
+/// forward it.
+pub trait GapTrait {
     /// get the current gap
     fn get_gap(&self) -> GapSize;
     /// set the current gap
     fn set_gap(&mut self, gap: GapSize);
 }
 
+/// Trait describing a layout which makes room for window borders, managers and window managers
+/// using such a layout forward it.
+pub trait BorderTrait {
+    /// get the current border width
+    fn get_border_width(&self) -> BorderWidth;
+    /// set the current border width
+    fn set_border_width(&mut self, width: BorderWidth);
+}
+
+/// Trait describing a layout with an adjustable master area, managers and window managers using
+/// such a layout forward it.
+pub trait MasterTrait {
+    /// get the percentage of the screen width taken by the master area
+    fn get_master_ratio(&self) -> u32;
+    /// get the maximum number of windows in the master area
+    fn get_master_count(&self) -> usize;
+    /// shrink the master area by one step, the master area never disappears
+    fn shrink_master(&mut self);
+    /// expand the master area by one step, the master area never covers the whole screen
+    fn expand_master(&mut self);
+    /// allow one more window in the master area
+    fn inc_master(&mut self);
+    /// allow one window less in the master area, there is always at least one
+    fn dec_master(&mut self);
+}
+
+/// The master area of a layout, layouts with a master area hold one and forward the MasterTrait
+/// to it.
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct MasterArea {
+    /// percentage of the screen width taken by the master area
+    pub ratio: u32,
+    /// percentage by which the master area shrinks or expands
+    pub ratio_step: u32,
+    /// maximum number of windows in the master area
+    pub count: usize,
+}
+
+impl MasterArea {
+    /// A MasterArea with one window taking half of the screen, resizable in steps of 5 percent
+    pub fn new() -> MasterArea {
+        MasterArea {
+            ratio: 50,
+            ratio_step: 5,
+            count: 1,
+        }
+    }
+}
+
+impl Default for MasterArea {
+    fn default() -> MasterArea {
+        MasterArea::new()
+    }
+}
+
+impl MasterTrait for MasterArea {
+    fn get_master_ratio(&self) -> u32 {
+        self.ratio
+    }
+
+    fn get_master_count(&self) -> usize {
+        self.count
+    }
+
+    fn shrink_master(&mut self) {
+        if self.ratio > self.ratio_step {
+            self.ratio -= self.ratio_step;
+        }
+    }
+
+    fn expand_master(&mut self) {
+        if self.ratio + self.ratio_step < 100 {
+            self.ratio += self.ratio_step;
+        }
+    }
+
+    fn inc_master(&mut self) {
+        self.count += 1;
+    }
+
+    fn dec_master(&mut self) {
+        if self.count > 1 {
+            self.count -= 1;
+        }
+    }
+}
+
+/// Trait describing a layout which can switch between other layouts while running, managers and
+/// window managers using such a layout forward it.
+pub trait LayoutSwitchTrait {
+    /// get the name of the current layout
+    fn get_layout_name(&self) -> &str;
+    /// switch to the next layout, after the last layout comes the first
+    fn next_layout(&mut self);
+    /// switch to the previous layout, before the first layout comes the last
+    fn prev_layout(&mut self);
+    /// switch to the layout with the given name, returns false when there is no such layout
+    fn select_layout(&mut self, name: &str) -> bool;
+}
+
 /// Trait which all Managers should have. A Manager is a component of a WindowManager with a
 /// specific task
 pub trait Manager {
@@ -71,6 +254,15 @@ pub trait LayoutManager: Manager {
     fn get_screen(&self) -> Screen;
     /// resize screen
     fn resize_screen(&mut self, screen: Screen);
+    /// get the strut reserving the areas of the panels and docks
+    fn get_strut(&self) -> Strut;
+    /// set the strut, the tiles are kept out of the areas it reserves
+    fn set_strut(&mut self, strut: Strut);
+
+    /// the part of the screen not reserved by the strut
+    fn get_usable_area(&self) -> Geometry {
+        self.get_strut().get_usable_area(&self.get_screen())
+    }
 }
 
 /// Trait which describes TilingSupport for Managers
@@ -84,6 +276,94 @@ pub trait TilingTrait: LayoutManager {
                         -> Result<(), Self::Error>;
     /// swap windows
     fn swap_windows(&mut self, dir: PrevOrNext, focus_manager: &FocusManager);
+    /// send a message to the layout, returns whether the layout handled it
+    fn send_layout_message(&mut self, message: LayoutMessage) -> bool;
+    /// get the tabs of the tab bar of the layout
+    fn get_tabs(&self) -> Vec<Tab>;
+    /// get the window the layout wants to focus after the given window, if the layout decides
+    /// this itself
+    fn get_focus_neighbour(&self, window: Window, dir: PrevOrNext) -> Option<Window>;
+    /// get the tile after the given tile, or the one before it when it is the last tile
+    fn get_next_tile(&self, window: Window) -> Option<Window>;
+    /// swap the places of the two windows, does nothing when they cannot swap
+    fn swap_places(&mut self, window: Window, other: Window) -> Result<(), Self::Error>;
+    /// whether the two windows can swap their places, by default all windows can
+    fn can_swap(&self, _window: Window, _other: Window) -> bool {
+        true
+    }
+    /// Swap the focused window with its neighbour in the given direction, only the windows it
+    /// can swap with are neighbours. The focus stays on the same window.
+    fn swap_direction(&mut self,
+                      dir: Direction,
+                      wrap: bool,
+                      focus_manager: &FocusManager)
+                      -> Result<(), Self::Error> {
+        match focus_manager.get_focused_window() {
+            None => Ok(()),
+            Some(window) => {
+                let windows = self.get_window_layout()
+                    .into_iter()
+                    .filter(|&(w, _)| w == window || self.can_swap(window, w))
+                    .collect::<Vec<_>>();
+                match dir.find_neighbour(window, &windows, wrap) {
+                    None => Ok(()),
+                    Some(other) => self.swap_places(window, other),
+                }
+            }
+        }
+    }
+}
+
+/// Trait for window managers which forward LayoutMessages to their TilingLayout
+pub trait LayoutMessageSupport: TilingSupport {
+    /// Send the message to the layout of the tiled windows. Returns true if the layout handled
+    /// the message, false if it ignored it.
+    fn send_layout_message(&mut self, message: LayoutMessage) -> bool;
+}
+
+/// Trait for window managers which let the user choose how their FocusManager moves the focus
+pub trait FocusPolicySupport: WindowManager {
+    /// get the order in which cycle_focus goes through the windows
+    fn get_focus_policy(&self) -> FocusPolicy;
+    /// set the order in which cycle_focus goes through the windows
+    fn set_focus_policy(&mut self, policy: FocusPolicy);
+    /// get which window is focused after removing the focused window
+    fn get_removal_focus(&self) -> RemovalFocus;
+    /// set which window is focused after removing the focused window
+    fn set_removal_focus(&mut self, removal_focus: RemovalFocus);
+}
+
+/// Trait for window managers with an alt-tab like window switcher, see WindowSwitcher
+pub trait WindowSwitcherSupport: MinimiseSupport {
+    /// Begin a switch session over all windows, also the floating and minimised ones, in most
+    /// recently used order. The first candidate is the previously focused window. A session
+    /// which is going on starts over.
+    fn begin_switch(&mut self);
+    /// Step to the next, less recently used, or the previous candidate. Does nothing without
+    /// session.
+    fn step_switch(&mut self, dir: PrevOrNext);
+    /// The candidate, which is raised in the window layout, None without session
+    fn get_switch_candidate(&self) -> Option<Window>;
+    /// End the session by focusing the candidate, unminimising it when it is minimised. Does
+    /// nothing without session.
+    fn commit_switch(&mut self) -> Result<(), Self::Error>;
+    /// End the session, the focus and the stacking stay as they were before it.
+    fn cancel_switch(&mut self);
+}
+
+/// Trait for window managers which apply WindowRules to the windows they add
+///
+/// `add_window` applies the rules matching the properties of the window before adding it.
+pub trait WindowRuleSupport: WindowManager {
+    /// get the rules
+    fn get_window_rules(&self) -> &WindowRules;
+    /// replace the rules, the windows already managed are left alone
+    fn set_window_rules(&mut self, rules: WindowRules);
+    /// add the window as it is, without applying the rules, e.g. when it moves to another
+    /// workspace
+    fn add_window_without_rules(&mut self,
+                                window_with_info: WindowWithInfo)
+                                -> Result<(), Self::Error>;
 }
 
 /// Trait which describes FloatSupport for Managers
@@ -117,6 +397,44 @@ pub trait FloatAndTileTrait: TilingTrait + FloatTrait {
     }
 }
 
+/// Trait which describes MinimiseSupport for Managers
+pub trait MinimiseTrait: FloatAndTileTrait {
+    /// get all minimised windows, in the order they were minimised
+    fn get_minimised_windows(&self) -> Vec<Window>;
+    /// minimise the window, or unminimise it when it is already minimised
+    fn toggle_minimised(&mut self,
+                        window: Window,
+                        focus_manager: &mut FocusManager)
+                        -> Result<(), Self::Error>;
+
+    /// true when window in get_minimised_windows
+    fn is_minimised(&self, window: Window) -> bool {
+        self.get_minimised_windows().contains(&window)
+    }
+    /// unminimise the window if it is minimised, otherwise do nothing
+    fn maximise_if_minimised(&mut self,
+                             window: Window,
+                             focus_manager: &mut FocusManager)
+                             -> Result<(), Self::Error> {
+        if self.is_minimised(window) {
+            self.toggle_minimised(window, focus_manager)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Trait which describes FullscreenSupport for Managers
+pub trait FullscreenTrait: LayoutManager {
+    /// get the fullscreen window, if any
+    fn get_fullscreen_window(&self) -> Option<Window>;
+    /// make the window fullscreen, or restore it when it is already fullscreen
+    fn toggle_fullscreen(&mut self,
+                         window: Window,
+                         focus_manager: &mut FocusManager)
+                         -> Result<(), Self::Error>;
+}
+
 /// Module for the used error types
 pub mod error {
     use cplwm_api::types::*;
@@ -208,42 +526,77 @@ pub mod error {
     }
 
 
-    /// A error for specific MultiWorkspaceErrors
+    /// A error for specific MultiWorkspaceErrors, generic over the error of the window manager
+    /// used for the workspaces
     #[derive(Debug)]
-    pub enum MultiWorkspaceError {
-        /// Wraps another error, or should wrap it, this is not entirely implemented
-        WrappedError,
+    pub enum MultiWorkspaceError<E: error::Error> {
+        /// Wraps an error of the window manager of a workspace
+        WrappedError(E),
         /// There is no workspace in this MultiWorkspaceWM, this should not happen
         NoWorkspaces,
         /// The requested WorkspaceIndex is out of bound
         WorkspaceIndexOutOfBound(WorkspaceIndex),
+        /// There is no output with this id
+        UnknownOutput(OutputId),
+        /// There already is an output with this id
+        AlreadyKnownOutput(OutputId),
+        /// The last output can not be removed
+        LastOutput,
+        /// This window is not known by any workspace.
+        UnknownWindow(Window),
+        /// This window is already managed by a workspace.
+        AlReadyManagedWindow(Window),
     }
 
     // This code is explained in the documentation of the associated [Error] type
     // of the `WindowManager` trait.
-    impl fmt::Display for MultiWorkspaceError {
+    impl<E: error::Error> fmt::Display for MultiWorkspaceError<E> {
         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
             match *self {
-                MultiWorkspaceError::WrappedError => write!(f, "Wrapped error occured!"),
+                MultiWorkspaceError::WrappedError(ref error) => {
+                    write!(f, "Error in workspace: {}", error)
+                }
                 MultiWorkspaceError::NoWorkspaces => {
                     write!(f, "No workspaces in this MultiWorkspaceWM!")
                 }
                 MultiWorkspaceError::WorkspaceIndexOutOfBound(index) => {
                     write!(f, "Index is out of bound {}", index)
                 }
-
+                MultiWorkspaceError::UnknownOutput(id) => write!(f, "Unknown output: {}", id),
+                MultiWorkspaceError::AlreadyKnownOutput(id) => {
+                    write!(f, "Already known output: {}", id)
+                }
+                MultiWorkspaceError::LastOutput => write!(f, "Can not remove the last output!"),
+                MultiWorkspaceError::UnknownWindow(ref window) => {
+                    write!(f, "Unknown window: {}", window)
+                }
+                MultiWorkspaceError::AlReadyManagedWindow(ref window) => {
+                    write!(f, "Already managed window: {}", window)
+                }
             }
         }
     }
 
     // This code is explained in the documentation of the associated [Error] type
     // of the `WindowManager` trait.
-    impl error::Error for MultiWorkspaceError {
-        fn description(&self) -> &'static str {
+    impl<E: error::Error + 'static> error::Error for MultiWorkspaceError<E> {
+        fn description(&self) -> &str {
             match *self {
-                MultiWorkspaceError::WrappedError => "Wrapped error",
+                MultiWorkspaceError::WrappedError(_) => "Error in workspace",
                 MultiWorkspaceError::NoWorkspaces => "NoWorkspaces",
                 MultiWorkspaceError::WorkspaceIndexOutOfBound(_) => "WorkspaceIndexOutOfBound",
+                MultiWorkspaceError::UnknownOutput(_) => "Unknown output",
+                MultiWorkspaceError::AlreadyKnownOutput(_) => "Already known output",
+                MultiWorkspaceError::LastOutput => "LastOutput",
+                MultiWorkspaceError::UnknownWindow(_) => "Unknown window",
+                MultiWorkspaceError::AlReadyManagedWindow(_) => "Already managed window",
+            }
+        }
+
+        fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+            match *self {
+                MultiWorkspaceError::WrappedError(ref error) => Some(error),
+                _ => None,
             }
         }
     }
@@ -392,7 +745,7 @@ pub mod tests {
             let mut wm = T::new(SCREEN);
             let window_with_info = WindowWithInfo::new_float(1, SOME_GEOM);
 
-            assert!(wm.add_window(window_with_info).is_ok());
+            assert!(wm.add_window(window_with_info.clone()).is_ok());
             assert_eq!(window_with_info, wm.get_window_info(1).unwrap());
 
             assert!(wm.toggle_minimised(1).is_ok());
@@ -713,7 +1066,7 @@ pub mod tests {
         }
 
         /// test swap_windows swaps the windows
-        pub fn test_swap_windows<TS: TilingSupport, TL: TilingLayout>(layout: TL) {
+        pub fn test_swap_windows<TS: TilingSupport, TL: TilingLayout>(mut layout: TL) {
             let mut wm = TS::new(SCREEN);
 
             wm.swap_windows(PrevOrNext::Next);
@@ -796,12 +1149,293 @@ pub mod tests {
 
     }
 
+    /// Module for testing window managers with an adjustable master area
+    pub mod master_support {
+        use cplwm_api::wm::TilingSupport;
+        use cplwm_api::types::*;
+        use super::super::MasterTrait;
+
+        static SCREEN: Screen = Screen {
+            width: 800,
+            height: 600,
+        };
+
+        // A random, unimportant Geometry
+        static SOME_GEOM: Geometry = Geometry {
+            x: 10,
+            y: 10,
+            width: 100,
+            height: 100,
+        };
+
+        /// test shrinking and expanding the master area changes the width of the master window
+        pub fn test_shrink_and_expand_master<T: TilingSupport + MasterTrait>() {
+            let mut wm = T::new(SCREEN);
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+            let ratio = wm.get_master_ratio();
+
+            wm.shrink_master();
+            assert!(wm.get_master_ratio() < ratio);
+            let master_width = SCREEN.width * wm.get_master_ratio() / 100;
+            assert_eq!(master_width, wm.get_window_info(1).unwrap().geometry.width);
+            assert_eq!(SCREEN.width - master_width,
+                       wm.get_window_info(2).unwrap().geometry.width);
+
+            wm.expand_master();
+            wm.expand_master();
+            assert!(wm.get_master_ratio() > ratio);
+            let master_width = SCREEN.width * wm.get_master_ratio() / 100;
+            assert_eq!(master_width, wm.get_window_info(1).unwrap().geometry.width);
+
+            // the master area never disappears or covers the whole screen
+            for _ in 0..100 {
+                wm.shrink_master();
+            }
+            assert!(wm.get_master_ratio() > 0);
+            assert!(wm.get_window_info(1).unwrap().geometry.width > 0);
+            for _ in 0..100 {
+                wm.expand_master();
+            }
+            assert!(wm.get_master_ratio() < 100);
+            assert!(wm.get_window_info(2).unwrap().geometry.width > 0);
+        }
+
+        /// test more windows can be put in the master area
+        pub fn test_inc_and_dec_master<T: TilingSupport + MasterTrait>() {
+            let mut wm = T::new(SCREEN);
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).is_ok());
+            assert_eq!(1, wm.get_master_count());
+
+            wm.inc_master();
+            assert_eq!(2, wm.get_master_count());
+            let master_width = SCREEN.width * wm.get_master_ratio() / 100;
+            assert_eq!(Geometry {
+                           x: 0,
+                           y: 0,
+                           width: master_width,
+                           height: SCREEN.height / 2,
+                       },
+                       wm.get_window_info(1).unwrap().geometry);
+            assert_eq!(Geometry {
+                           x: 0,
+                           y: (SCREEN.height / 2) as i32,
+                           width: master_width,
+                           height: SCREEN.height / 2,
+                       },
+                       wm.get_window_info(2).unwrap().geometry);
+            assert_eq!(Geometry {
+                           x: master_width as i32,
+                           y: 0,
+                           width: SCREEN.width - master_width,
+                           height: SCREEN.height,
+                       },
+                       wm.get_window_info(3).unwrap().geometry);
+            // the master window is still the first one
+            assert_eq!(Some(1), wm.get_master_window());
+
+            // with all windows in the master area, it takes the whole screen
+            wm.inc_master();
+            assert_eq!(SCREEN.width, wm.get_window_info(3).unwrap().geometry.width);
+
+            wm.dec_master();
+            wm.dec_master();
+            wm.dec_master();
+            assert_eq!(1, wm.get_master_count());
+            assert_eq!(SCREEN.height, wm.get_window_info(1).unwrap().geometry.height);
+        }
+    }
+
+    /// Module for testing LayoutMessageSupport
+    pub mod layout_message_support {
+        use cplwm_api::types::*;
+        use super::super::{LayoutMessage, LayoutMessageSupport, MasterTrait};
+
+        static SCREEN: Screen = Screen {
+            width: 800,
+            height: 600,
+        };
+
+        // A random, unimportant Geometry
+        static SOME_GEOM: Geometry = Geometry {
+            x: 10,
+            y: 10,
+            width: 100,
+            height: 100,
+        };
+
+        /// test the master messages reach the layout
+        pub fn test_master_messages<T: LayoutMessageSupport + MasterTrait>() {
+            let mut wm = T::new(SCREEN);
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+            let ratio = wm.get_master_ratio();
+            let master_width = wm.get_window_info(1).unwrap().geometry.width;
+
+            assert!(wm.send_layout_message(LayoutMessage::ShrinkMaster));
+            assert!(wm.get_master_ratio() < ratio);
+            assert!(wm.get_window_info(1).unwrap().geometry.width < master_width);
+
+            assert!(wm.send_layout_message(LayoutMessage::ExpandMaster));
+            assert_eq!(ratio, wm.get_master_ratio());
+            assert_eq!(master_width, wm.get_window_info(1).unwrap().geometry.width);
+
+            assert!(wm.send_layout_message(LayoutMessage::IncMaster));
+            assert_eq!(2, wm.get_master_count());
+            assert!(wm.send_layout_message(LayoutMessage::DecMaster));
+            assert_eq!(1, wm.get_master_count());
+
+            // the master area can not be rotated or flipped
+            assert!(!wm.send_layout_message(LayoutMessage::Rotate));
+            assert!(!wm.send_layout_message(LayoutMessage::Flip));
+        }
+
+        /// test a layout without anything to adjust ignores all messages
+        pub fn test_ignored_messages<T: LayoutMessageSupport>() {
+            let mut wm = T::new(SCREEN);
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+            let window_layout = wm.get_window_layout();
+
+            assert!(!wm.send_layout_message(LayoutMessage::ShrinkMaster));
+            assert!(!wm.send_layout_message(LayoutMessage::ExpandMaster));
+            assert!(!wm.send_layout_message(LayoutMessage::IncMaster));
+            assert!(!wm.send_layout_message(LayoutMessage::DecMaster));
+            assert!(!wm.send_layout_message(LayoutMessage::Rotate));
+            assert!(!wm.send_layout_message(LayoutMessage::Flip));
+            assert_eq!(window_layout, wm.get_window_layout());
+        }
+    }
+
+    /// Module for testing TabSupport
+    pub mod tab_support {
+        use cplwm_api::types::*;
+        use cplwm_api::wm::TabSupport;
+
+        static SCREEN: Screen = Screen {
+            width: 800,
+            height: 600,
+        };
+
+        // A random, unimportant Geometry
+        static SOME_GEOM: Geometry = Geometry {
+            x: 10,
+            y: 10,
+            width: 100,
+            height: 100,
+        };
+
+        /// test a window manager with a layout without tab bar has no tabs
+        pub fn test_no_tabs_without_tab_bar<T: TabSupport>() {
+            let mut wm = T::new(SCREEN);
+            assert_eq!(Vec::<Tab>::new(), wm.get_tabs());
+
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+            assert_eq!(Vec::<Tab>::new(), wm.get_tabs());
+
+            // focusing the other tile does not change the layout
+            let window_layout = wm.get_window_layout();
+            assert!(wm.focus_window(Some(1)).is_ok());
+            assert_eq!(window_layout.windows, wm.get_window_layout().windows);
+        }
+    }
+
+    /// Module for testing LayoutSwitchTrait
+    pub mod layout_switch_support {
+        use cplwm_api::types::*;
+        use cplwm_api::wm::{TilingSupport, WindowManager};
+        use super::super::LayoutSwitchTrait;
+
+        static SCREEN: Screen = Screen {
+            width: 800,
+            height: 600,
+        };
+
+        // A random, unimportant Geometry
+        static SOME_GEOM: Geometry = Geometry {
+            x: 10,
+            y: 10,
+            width: 100,
+            height: 100,
+        };
+
+        /// test switching the layout changes the geometries of the tiles, but not their order
+        pub fn test_select_layout<T: TilingSupport + LayoutSwitchTrait>() {
+            let mut wm = T::new(SCREEN);
+            for window in 1..4 {
+                assert!(wm.add_window(WindowWithInfo::new_tiled(window, SOME_GEOM)).is_ok());
+            }
+            assert_eq!("vertical", wm.get_layout_name());
+            let window_layout = wm.get_window_layout();
+
+            assert!(wm.select_layout("monocle"));
+            assert_eq!("monocle", wm.get_layout_name());
+            for window in 1..4 {
+                assert_eq!(SCREEN.to_geometry(), wm.get_window_info(window).unwrap().geometry);
+            }
+            assert_eq!(Some(1), wm.get_master_window());
+
+            // an unknown name keeps the current layout
+            assert!(!wm.select_layout("unknown"));
+            assert_eq!("monocle", wm.get_layout_name());
+
+            assert!(wm.select_layout("vertical"));
+            assert_eq!(window_layout.windows, wm.get_window_layout().windows);
+        }
+
+        /// test the first and the last layout are neighbours
+        pub fn test_next_and_prev_layout<T: WindowManager + LayoutSwitchTrait>() {
+            let mut wm = T::new(SCREEN);
+            let first = wm.get_layout_name().to_string();
+
+            wm.prev_layout();
+            let last = wm.get_layout_name().to_string();
+            assert!(first != last);
+            wm.next_layout();
+            assert_eq!(first, wm.get_layout_name());
+            wm.next_layout();
+            assert!(first != wm.get_layout_name());
+            wm.prev_layout();
+            assert_eq!(first, wm.get_layout_name());
+        }
+
+        /// test a layout with its own state about the tiles knows the tiles added before it was
+        /// selected
+        pub fn test_select_bsp_layout<T: TilingSupport + LayoutSwitchTrait>() {
+            let mut wm = T::new(SCREEN);
+            for window in 1..4 {
+                assert!(wm.add_window(WindowWithInfo::new_tiled(window, SOME_GEOM)).is_ok());
+            }
+
+            assert!(wm.select_layout("bsp"));
+            assert_eq!(Geometry {
+                           x: 400,
+                           y: 300,
+                           width: 400,
+                           height: 300,
+                       },
+                       wm.get_window_info(3).unwrap().geometry);
+
+            assert!(wm.remove_window(2).is_ok());
+            assert_eq!(Geometry {
+                           x: 400,
+                           y: 0,
+                           width: 400,
+                           height: 600,
+                       },
+                       wm.get_window_info(3).unwrap().geometry);
+        }
+    }
+
     /// Module for testing GapSupport
     pub mod gap_support {
         use std::collections::VecDeque;
         use cplwm_api::types::*;
-        use cplwm_api::wm::GapSupport;
-        use super::super::GapTrait;
+        use cplwm_api::wm::{FloatSupport, GapSupport};
+        use super::super::{GapTrait, TilingLayout};
 
 
         static SCREEN: Screen = Screen {
@@ -818,7 +1452,7 @@ pub mod tests {
         };
 
         /// test for changing the gap has the right effect
-        pub fn test_set_gap<G: GapSupport, L: GapTrait>(mut layout: L) {
+        pub fn test_set_gap<G: GapSupport, L: TilingLayout + GapTrait>(mut layout: L) {
             let mut wm = G::new(SCREEN);
             assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
             assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
@@ -851,13 +1485,32 @@ pub mod tests {
             }
 
         }
+
+        /// test that the gap leaves floating windows alone
+        pub fn test_gap_ignores_floating_windows<G: GapSupport + FloatSupport>() {
+            let mut wm = G::new(SCREEN);
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_float(2, SOME_GEOM)).is_ok());
+
+            wm.set_gap(5);
+            assert_eq!(5, wm.get_gap());
+            assert_eq!(SOME_GEOM, wm.get_window_info(2).unwrap().geometry);
+            assert_eq!(Geometry {
+                           x: 5,
+                           y: 5,
+                           width: 790,
+                           height: 590,
+                       },
+                       wm.get_window_info(1).unwrap().geometry);
+        }
     }
 
 
-    /// Module for all tests concerning the WindowManager trait.
-    pub mod window_manager {
-        use cplwm_api::wm::WindowManager;
+    /// Module for testing BorderSupport
+    pub mod border_support {
         use cplwm_api::types::*;
+        use cplwm_api::wm::{BorderSupport, FloatSupport};
+
 
         static SCREEN: Screen = Screen {
             width: 800,
@@ -872,26 +1525,1051 @@ pub mod tests {
             height: 100,
         };
 
-        /// test if the given window manager is initialized the right way
-        pub fn test_empty_wm<T: WindowManager>() {
-            let wm = T::new(SCREEN);
-            assert_eq!(WindowLayout::new(), wm.get_window_layout());
-            assert_eq!(0, wm.get_windows().len());
-            assert_eq!(SCREEN, wm.get_screen())
+        /// test that the tiles make room for their borders
+        pub fn test_set_border_width<B: BorderSupport>() {
+            let mut wm = B::new(SCREEN);
+            assert_eq!(0, wm.get_border_width());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+
+            wm.set_border_width(2);
+            assert_eq!(2, wm.get_border_width());
+            assert_eq!(Geometry {
+                           x: 2,
+                           y: 2,
+                           width: 396,
+                           height: 596,
+                       },
+                       wm.get_window_info(1).unwrap().geometry);
+
+            // with the borders included, the tiles cover the screen without overlapping
+            let layout = wm.get_bordered_window_layout();
+            assert_eq!(vec![(1,
+                             Geometry {
+                                 x: 0,
+                                 y: 0,
+                                 width: 400,
+                                 height: 600,
+                             }),
+                            (2,
+                             Geometry {
+                                 x: 400,
+                                 y: 0,
+                                 width: 400,
+                                 height: 600,
+                             })],
+                       layout.windows.iter().map(|b| (b.window, b.geometry)).collect::<Vec<_>>());
+            assert!(layout.windows.iter().all(|b| b.border_width == 2));
+            assert_eq!(wm.get_window_layout().focused_window, layout.focused_window);
         }
 
-        /// test for adding and removing windows to the WindowManager
-        pub fn test_adding_and_removing_windows<T: WindowManager>() {
-            let mut wm = T::new(SCREEN);
-            let window_a = WindowWithInfo::new_tiled(1, SOME_GEOM);
-            let window_b = WindowWithInfo::new_tiled(2, SOME_GEOM);
+        /// test that floating windows keep their geometry and get the border around it
+        pub fn test_floating_window_border<B: BorderSupport + FloatSupport>() {
+            let mut wm = B::new(SCREEN);
+            assert!(wm.add_window(WindowWithInfo::new_float(1, SOME_GEOM)).is_ok());
+            wm.set_border_width(3);
 
-            assert!(wm.add_window(window_a).is_ok());
+            assert_eq!(SOME_GEOM, wm.get_window_info(1).unwrap().geometry);
+            assert_eq!(vec![BorderedWindow {
+                                window: 1,
+                                geometry: Geometry {
+                                    x: 7,
+                                    y: 7,
+                                    width: 106,
+                                    height: 106,
+                                },
+                                border_width: 3,
+                                state: BorderState::Focused,
+                                colour: wm.get_border_colours().focused,
+                            }],
+                       wm.get_bordered_window_layout().windows);
+        }
+
+        /// test the focused, unfocused and urgent borders
+        pub fn test_border_states<B: BorderSupport>() {
+            let mut wm = B::new(SCREEN);
+            let colours = BorderColours {
+                focused: 1,
+                unfocused: 2,
+                urgent: 3,
+            };
+            wm.set_border_colours(colours);
+            assert_eq!(colours, wm.get_border_colours());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).is_ok());
+            assert!(wm.focus_window(Some(3)).is_ok());
+
+            assert!(wm.set_urgent(1, true).is_ok());
+            assert!(wm.set_urgent(3, true).is_ok());
+            assert!(wm.set_urgent(4, true).is_err());
+            assert!(wm.is_urgent(1));
+            assert!(!wm.is_urgent(2));
+
+            // the focused window shows the focused border, even when urgent
+            let states = |wm: &B| {
+                wm.get_bordered_window_layout()
+                    .windows
+                    .iter()
+                    .map(|b| (b.window, b.state, b.colour))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(vec![(1, BorderState::Urgent, 3),
+                            (2, BorderState::Unfocused, 2),
+                            (3, BorderState::Focused, 1)],
+                       states(&wm));
+
+            assert!(wm.set_urgent(1, false).is_ok());
+            assert!(wm.focus_window(None).is_ok());
+            assert_eq!(vec![(1, BorderState::Unfocused, 2),
+                            (2, BorderState::Unfocused, 2),
+                            (3, BorderState::Urgent, 3)],
+                       states(&wm));
+
+            // a removed window loses its urgency
+            assert!(wm.remove_window(3).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).is_ok());
+            assert!(!wm.is_urgent(3));
+        }
+    }
+
+    /// Module for testing the size hints of windows
+    pub mod size_hint_support {
+        use cplwm_api::types::*;
+        use cplwm_api::wm::{FloatSupport, TilingSupport};
+
+
+        static SCREEN: Screen = Screen {
+            width: 800,
+            height: 600,
+        };
+
+        // A random, unimportant Geometry
+        static SOME_GEOM: Geometry = Geometry {
+            x: 10,
+            y: 10,
+            width: 100,
+            height: 100,
+        };
+
+        /// test that tiles are snapped to their increments and shrunk to their maximum size,
+        /// centered in their tile, with the vertical layout
+        pub fn test_tiled_size_hints<WM: TilingSupport>() {
+            let mut wm = WM::new(SCREEN);
+            let terminal = SizeHints { size_increment: Some((7, 13)), ..SizeHints::none() };
+            let small = SizeHints { max_size: Some((300, 200)), ..SizeHints::none() };
+            let huge = SizeHints { min_size: Some((1000, 1000)), ..SizeHints::none() };
+            for &(window, hints) in &[(1, terminal), (2, small), (3, huge)] {
+                let info = WindowWithInfo::new_tiled(window, SOME_GEOM).with_size_hints(hints);
+                assert!(wm.add_window(info).is_ok());
+            }
+
+            assert_eq!(terminal, wm.get_window_info(1).unwrap().size_hints);
+            assert_eq!(Geometry {
+                           x: 0,
+                           y: 1,
+                           width: 399,
+                           height: 598,
+                       },
+                       wm.get_window_info(1).unwrap().geometry);
+            assert_eq!(Geometry {
+                           x: 450,
+                           y: 50,
+                           width: 300,
+                           height: 200,
+                       },
+                       wm.get_window_info(2).unwrap().geometry);
+            // a minimum size larger than the tile would cover the neighbours
+            assert_eq!(Geometry {
+                           x: 400,
+                           y: 300,
+                           width: 400,
+                           height: 300,
+                       },
+                       wm.get_window_info(3).unwrap().geometry);
+        }
+
+        /// test that floating windows are clamped to their size hints
+        pub fn test_floating_size_hints<WM: FloatSupport>() {
+            let mut wm = WM::new(SCREEN);
+            let bounded = SizeHints {
+                min_size: Some((50, 50)),
+                max_size: Some((200, 150)),
+                ..SizeHints::none()
+            };
+            let square = SizeHints {
+                min_aspect: Some((1, 1)),
+                max_aspect: Some((1, 1)),
+                ..SizeHints::none()
+            };
+            let zero_aspect = SizeHints {
+                min_aspect: Some((0, 1)),
+                max_aspect: Some((0, 1)),
+                ..SizeHints::none()
+            };
+            for &(window, hints) in &[(1, bounded), (2, square), (3, zero_aspect)] {
+                let info = WindowWithInfo::new_float(window, SOME_GEOM).with_size_hints(hints);
+                assert!(wm.add_window(info).is_ok());
+            }
+
+            assert!(wm.set_window_geometry(1,
+                                     Geometry {
+                                         x: 5,
+                                         y: 6,
+                                         width: 10,
+                                         height: 400,
+                                     })
+                .is_ok());
+            assert_eq!(Geometry {
+                           x: 5,
+                           y: 6,
+                           width: 50,
+                           height: 150,
+                       },
+                       wm.get_window_info(1).unwrap().geometry);
+
+            // the aspect ratio is met by shrinking the widest side
+            assert!(wm.set_window_geometry(2,
+                                     Geometry {
+                                         x: 0,
+                                         y: 0,
+                                         width: 300,
+                                         height: 200,
+                                     })
+                .is_ok());
+            assert_eq!(Geometry {
+                           x: 0,
+                           y: 0,
+                           width: 200,
+                           height: 200,
+                       },
+                       wm.get_window_info(2).unwrap().geometry);
+
+            // an aspect ratio of zero is ignored, it would shrink the window to nothing
+            let geometry = Geometry {
+                x: 0,
+                y: 0,
+                width: 300,
+                height: 200,
+            };
+            assert!(wm.set_window_geometry(3, geometry).is_ok());
+            assert_eq!(geometry, wm.get_window_info(3).unwrap().geometry);
+        }
+    }
+
+    /// Module for testing StrutSupport
+    pub mod strut_support {
+        use cplwm_api::types::*;
+        use cplwm_api::wm::{FloatSupport, FullscreenSupport, StrutSupport};
+
+        static SCREEN: Screen = Screen {
+            width: 800,
+            height: 600,
+        };
+
+        // A random, unimportant Geometry
+        static SOME_GEOM: Geometry = Geometry {
+            x: 10,
+            y: 10,
+            width: 100,
+            height: 100,
+        };
+
+        fn is_inside(geometry: Geometry, area: Geometry) -> bool {
+            geometry.x >= area.x && geometry.y >= area.y &&
+            geometry.x + geometry.width as i32 <= area.x + area.width as i32 &&
+            geometry.y + geometry.height as i32 <= area.y + area.height as i32
+        }
+
+        /// test that the struts stack and the tiles stay in the usable area
+        pub fn test_tiles_in_usable_area<WM: StrutSupport>() {
+            let mut wm = WM::new(SCREEN);
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert_eq!(SCREEN.to_geometry(), wm.get_usable_area());
+            assert_eq!(SCREEN.to_geometry(), wm.get_window_info(1).unwrap().geometry);
+
+            // a status bar from the configuration and two docks which are not managed
+            wm.set_static_strut(Strut::new(24, 0, 0, 0));
+            wm.set_window_strut(10, Strut::new(16, 0, 0, 0));
+            wm.set_window_strut(11, Strut::new(0, 0, 30, 50));
+            let usable_area = Geometry {
+                x: 50,
+                y: 40,
+                width: 750,
+                height: 530,
+            };
+            assert_eq!(Strut::new(24, 0, 0, 0), wm.get_static_strut());
+            assert_eq!(Some(Strut::new(16, 0, 0, 0)), wm.get_window_strut(10));
+            assert_eq!(usable_area, wm.get_usable_area());
+            assert_eq!(usable_area, wm.get_window_info(1).unwrap().geometry);
+
+            assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).is_ok());
+            for (_, geometry) in wm.get_window_layout().windows {
+                assert!(is_inside(geometry, usable_area));
+            }
+
+            wm.remove_window_strut(10);
+            wm.remove_window_strut(11);
+            assert_eq!(None, wm.get_window_strut(10));
+            assert_eq!(Geometry {
+                           x: 0,
+                           y: 24,
+                           width: 800,
+                           height: 576,
+                       },
+                       wm.get_usable_area());
+        }
+
+        /// test that removing a managed window removes its strut
+        pub fn test_remove_window_with_strut<WM: StrutSupport>() {
+            let mut wm = WM::new(SCREEN);
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+            wm.set_window_strut(2, Strut::new(0, 0, 20, 0));
+            assert!(wm.remove_window(1).is_ok());
+            assert_eq!(Some(Strut::new(0, 0, 20, 0)), wm.get_window_strut(2));
+
+            assert!(wm.remove_window(2).is_ok());
+            assert_eq!(None, wm.get_window_strut(2));
+            assert_eq!(SCREEN.to_geometry(), wm.get_usable_area());
+        }
+
+        /// test that floating windows may cover the reserved areas
+        pub fn test_floating_window_ignores_struts<WM: StrutSupport + FloatSupport>() {
+            let mut wm = WM::new(SCREEN);
+            let geometry = Geometry {
+                x: 0,
+                y: 0,
+                width: 200,
+                height: 100,
+            };
+            wm.set_static_strut(Strut::new(24, 0, 0, 0));
+            assert!(wm.add_window(WindowWithInfo::new_float(1, geometry)).is_ok());
+            assert_eq!(geometry, wm.get_window_info(1).unwrap().geometry);
+
+            assert!(wm.set_window_geometry(1, SOME_GEOM).is_ok());
+            assert_eq!(SOME_GEOM, wm.get_window_info(1).unwrap().geometry);
+        }
+
+        /// test that the fullscreen window covers the usable area only
+        pub fn test_fullscreen_in_usable_area<WM: StrutSupport + FullscreenSupport>() {
+            let mut wm = WM::new(SCREEN);
+            assert!(wm.add_window(WindowWithInfo::new_fullscreen(1, SOME_GEOM)).is_ok());
+            wm.set_window_strut(10, Strut::new(24, 0, 0, 0));
+            let usable_area = Geometry {
+                x: 0,
+                y: 24,
+                width: 800,
+                height: 576,
+            };
+            assert_eq!(usable_area, wm.get_window_info(1).unwrap().geometry);
+            assert_eq!(vec![(1, usable_area)], wm.get_window_layout().windows);
+        }
+    }
+
+    /// Module for testing focus_direction and swap_direction
+    pub mod direction_support {
+        use cplwm_api::types::*;
+        use cplwm_api::wm::{FloatSupport, TilingSupport};
+
+        static SCREEN: Screen = Screen {
+            width: 800,
+            height: 600,
+        };
+
+        static SOME_GEOM: Geometry = Geometry {
+            x: 10,
+            y: 10,
+            width: 100,
+            height: 100,
+        };
+
+        fn get_geometry<WM: TilingSupport>(wm: &WM, window: Window) -> Geometry {
+            wm.get_window_info(window).unwrap().geometry
+        }
+
+        /// Test moving the focus between the master on the left and the two tiles on the right
+        pub fn test_focus_direction<WM: TilingSupport>() {
+            let mut wm = WM::new(SCREEN);
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            // without focused window nothing happens
+            assert!(wm.focus_window(None).is_ok());
+            assert!(wm.focus_direction(Direction::Right, true).is_ok());
+            assert_eq!(None, wm.get_focused_window());
+
+            assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).is_ok());
+            assert!(wm.focus_direction(Direction::Left, false).is_ok());
+            assert_eq!(Some(1), wm.get_focused_window());
+            assert!(wm.focus_direction(Direction::Left, false).is_ok());
+            assert_eq!(Some(1), wm.get_focused_window());
+            assert!(wm.focus_direction(Direction::Right, false).is_ok());
+            assert_eq!(Some(3), wm.get_focused_window());
+
+            assert!(wm.focus_window(Some(2)).is_ok());
+            assert!(wm.focus_direction(Direction::Down, false).is_ok());
+            assert_eq!(Some(3), wm.get_focused_window());
+            assert!(wm.focus_direction(Direction::Down, false).is_ok());
+            assert_eq!(Some(3), wm.get_focused_window());
+            // wrapping around goes to the top of the same column
+            assert!(wm.focus_direction(Direction::Down, true).is_ok());
+            assert_eq!(Some(2), wm.get_focused_window());
+            assert!(wm.focus_direction(Direction::Right, true).is_ok());
+            assert_eq!(Some(1), wm.get_focused_window());
+        }
+
+        /// Test swapping the focused tile with its neighbour keeps the focus on it
+        pub fn test_swap_direction<WM: TilingSupport>() {
+            let mut wm = WM::new(SCREEN);
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).is_ok());
+            let master_geometry = get_geometry(&wm, 1);
+            let geometry = get_geometry(&wm, 3);
+
+            assert!(wm.swap_direction(Direction::Left, false).is_ok());
+            assert_eq!(Some(3), wm.get_focused_window());
+            assert_eq!(master_geometry, get_geometry(&wm, 3));
+            assert_eq!(geometry, get_geometry(&wm, 1));
+            assert_eq!(Some(3), wm.get_master_window());
+
+            // nothing on the left of the master
+            let windows = wm.get_window_layout().windows;
+            assert!(wm.swap_direction(Direction::Left, false).is_ok());
+            assert_eq!(windows, wm.get_window_layout().windows);
+            assert_eq!(Some(3), wm.get_focused_window());
+
+            assert!(wm.swap_direction(Direction::Left, true).is_ok());
+            assert_eq!(Some(3), wm.get_focused_window());
+            assert!(wm.get_master_window() != Some(3));
+        }
+
+        /// Test floating windows are neighbours for the focus, but only swap with each other
+        pub fn test_direction_with_floats<WM: TilingSupport + FloatSupport>() {
+            let mut wm = WM::new(SCREEN);
+            let float = Geometry {
+                x: 0,
+                y: 0,
+                width: 100,
+                height: 100,
+            };
+            let other_float = Geometry {
+                x: 600,
+                y: 400,
+                width: 100,
+                height: 50,
+            };
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_float(3, float)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_float(4, other_float)).is_ok());
+            let master_geometry = get_geometry(&wm, 1);
+
+            assert!(wm.focus_window(Some(3)).is_ok());
+            assert!(wm.focus_direction(Direction::Right, false).is_ok());
+            assert_eq!(Some(1), wm.get_focused_window());
+            assert!(wm.focus_direction(Direction::Left, false).is_ok());
+            assert_eq!(Some(3), wm.get_focused_window());
+
+            // the floating windows swap their positions and keep their sizes
+            assert!(wm.swap_direction(Direction::Right, false).is_ok());
+            assert_eq!(Some(3), wm.get_focused_window());
+            assert_eq!(Geometry { width: 100, height: 100, ..other_float },
+                       get_geometry(&wm, 3));
+            assert_eq!(Geometry { width: 100, height: 50, ..float }, get_geometry(&wm, 4));
+            assert_eq!(master_geometry, get_geometry(&wm, 1));
+
+            // the master does not swap with the floating window on its left
+            assert!(wm.focus_window(Some(1)).is_ok());
+            assert!(wm.swap_direction(Direction::Left, false).is_ok());
+            assert_eq!(Geometry { width: 100, height: 50, ..float }, get_geometry(&wm, 4));
+            assert_eq!(master_geometry, get_geometry(&wm, 1));
+            assert_eq!(Some(1), wm.get_master_window());
+        }
+    }
+
+    /// Module for testing FocusPolicySupport
+    pub mod focus_policy_support {
+        use a_fullscreen_wm::{FocusPolicy, RemovalFocus};
+        use cplwm_api::types::*;
+        use cplwm_api::wm::{FloatSupport, TilingSupport};
+        use wm_common::FocusPolicySupport;
+
+        static SCREEN: Screen = Screen {
+            width: 800,
+            height: 600,
+        };
+
+        static SOME_GEOM: Geometry = Geometry {
+            x: 10,
+            y: 10,
+            width: 100,
+            height: 100,
+        };
+
+        /// Test StackOrder follows the window layout instead of the focus history
+        pub fn test_stack_order<WM: FocusPolicySupport>() {
+            let mut wm = WM::new(SCREEN);
+            assert_eq!(FocusPolicy::History, wm.get_focus_policy());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).is_ok());
+            wm.set_focus_policy(FocusPolicy::StackOrder);
+            assert_eq!(FocusPolicy::StackOrder, wm.get_focus_policy());
+
+            let order: Vec<Window> = wm.get_window_layout()
+                .windows
+                .iter()
+                .map(|&(w, _)| w)
+                .collect();
+            assert!(wm.focus_window(Some(order[2])).is_ok());
+            assert!(wm.focus_window(Some(order[0])).is_ok());
+            wm.cycle_focus(PrevOrNext::Next);
+            assert_eq!(Some(order[1]), wm.get_focused_window());
+            wm.cycle_focus(PrevOrNext::Next);
+            assert_eq!(Some(order[2]), wm.get_focused_window());
+            wm.cycle_focus(PrevOrNext::Next);
+            assert_eq!(Some(order[0]), wm.get_focused_window());
+            wm.cycle_focus(PrevOrNext::Prev);
+            assert_eq!(Some(order[2]), wm.get_focused_window());
+
+            // without focused window Next starts at the bottom
+            assert!(wm.focus_window(None).is_ok());
+            wm.cycle_focus(PrevOrNext::Next);
+            assert_eq!(Some(order[0]), wm.get_focused_window());
+        }
+
+        /// Test MostRecentlyUsed switches back and forth between the last two focused windows
+        pub fn test_most_recently_used<WM: FocusPolicySupport>() {
+            let mut wm = WM::new(SCREEN);
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).is_ok());
+            wm.set_focus_policy(FocusPolicy::MostRecentlyUsed);
+            assert!(wm.focus_window(Some(1)).is_ok());
+
+            // every window is reached, from the most to the least recently used one
+            wm.cycle_focus(PrevOrNext::Next);
+            assert_eq!(Some(3), wm.get_focused_window());
+            wm.cycle_focus(PrevOrNext::Next);
+            assert_eq!(Some(2), wm.get_focused_window());
+            wm.cycle_focus(PrevOrNext::Next);
+            assert_eq!(Some(1), wm.get_focused_window());
+            // the least recently used window
+            wm.cycle_focus(PrevOrNext::Prev);
+            assert_eq!(Some(2), wm.get_focused_window());
+            wm.cycle_focus(PrevOrNext::Next);
+            assert_eq!(Some(1), wm.get_focused_window());
+        }
+
+        /// Test ReadingOrder goes through the windows from left to right, floating windows
+        /// included
+        pub fn test_reading_order<WM: FocusPolicySupport + FloatSupport>() {
+            let mut wm = WM::new(SCREEN);
+            let float = Geometry {
+                x: 100,
+                y: 0,
+                width: 100,
+                height: 100,
+            };
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_float(3, float)).is_ok());
+            assert!(wm.focus_window(Some(1)).is_ok());
+
+            wm.set_focus_policy(FocusPolicy::ReadingOrder);
+            wm.cycle_focus(PrevOrNext::Next);
+            assert_eq!(Some(3), wm.get_focused_window());
+            wm.cycle_focus(PrevOrNext::Next);
+            assert_eq!(Some(2), wm.get_focused_window());
+            wm.cycle_focus(PrevOrNext::Next);
+            assert_eq!(Some(1), wm.get_focused_window());
+
+            // the floating window is on top of the stack
+            wm.set_focus_policy(FocusPolicy::StackOrder);
+            wm.cycle_focus(PrevOrNext::Next);
+            assert_eq!(Some(2), wm.get_focused_window());
+        }
+
+        /// Test the window focused after removing the focused window
+        pub fn test_removal_focus<WM: FocusPolicySupport + TilingSupport>() {
+            let mut wm = WM::new(SCREEN);
+            assert_eq!(RemovalFocus::PreviousFocused, wm.get_removal_focus());
+            for window in 1..6 {
+                assert!(wm.add_window(WindowWithInfo::new_tiled(window, SOME_GEOM)).is_ok());
+            }
+
+            wm.set_removal_focus(RemovalFocus::NextTile);
+            assert_eq!(RemovalFocus::NextTile, wm.get_removal_focus());
+            assert!(wm.focus_window(Some(2)).is_ok());
+            assert!(wm.remove_window(2).is_ok());
+            assert_eq!(Some(3), wm.get_focused_window());
+            // the last tile has no next tile
+            assert!(wm.focus_window(Some(5)).is_ok());
+            assert!(wm.remove_window(5).is_ok());
+            assert_eq!(Some(4), wm.get_focused_window());
+
+            wm.set_removal_focus(RemovalFocus::Master);
+            assert!(wm.remove_window(4).is_ok());
+            assert_eq!(Some(1), wm.get_focused_window());
+            assert!(wm.remove_window(1).is_ok());
+            assert_eq!(Some(3), wm.get_focused_window());
+            assert_eq!(Some(3), wm.get_master_window());
+
+            // removing another window keeps the focus
+            assert!(wm.add_window(WindowWithInfo::new_tiled(6, SOME_GEOM)).is_ok());
+            assert!(wm.focus_window(Some(3)).is_ok());
+            assert!(wm.remove_window(6).is_ok());
+            assert_eq!(Some(3), wm.get_focused_window());
+        }
+    }
+
+    /// Module for testing WindowSwitcherSupport
+    pub mod window_switcher_support {
+        use cplwm_api::types::*;
+        use cplwm_api::wm::FloatSupport;
+        use wm_common::WindowSwitcherSupport;
+
+        static SCREEN: Screen = Screen {
+            width: 800,
+            height: 600,
+        };
+
+        static SOME_GEOM: Geometry = Geometry {
+            x: 10,
+            y: 10,
+            width: 100,
+            height: 100,
+        };
+
+        fn get_raised_window<WM: WindowSwitcherSupport>(wm: &WM) -> Option<Window> {
+            wm.get_window_layout().windows.last().map(|&(w, _)| w)
+        }
+
+        /// Test stepping through tiles, floating and minimised windows in most recently used
+        /// order, and committing to a minimised window
+        pub fn test_switch_commit<WM: WindowSwitcherSupport + FloatSupport>() {
+            let mut wm = WM::new(SCREEN);
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_float(3, SOME_GEOM)).is_ok());
+            assert!(wm.focus_window(Some(1)).is_ok());
+            assert!(wm.toggle_minimised(2).is_ok());
+            assert_eq!(None, wm.get_switch_candidate());
+
+            wm.begin_switch();
+            assert_eq!(Some(3), wm.get_switch_candidate());
+            wm.step_switch(PrevOrNext::Next);
+            assert_eq!(Some(2), wm.get_switch_candidate());
+            // the minimised window is shown on top, but keeps being minimised and unfocused
+            assert_eq!(Some(2), get_raised_window(&wm));
+            assert!(wm.is_minimised(2));
+            assert_eq!(Some(1), wm.get_focused_window());
+            assert_eq!(Some(1), wm.get_window_layout().focused_window);
+
+            wm.step_switch(PrevOrNext::Next);
+            assert_eq!(Some(1), wm.get_switch_candidate());
+            wm.step_switch(PrevOrNext::Prev);
+            assert!(wm.commit_switch().is_ok());
+            assert_eq!(None, wm.get_switch_candidate());
+            assert_eq!(Some(2), wm.get_focused_window());
+            assert!(!wm.is_minimised(2));
+
+            // the next session starts at the window focused before
+            wm.begin_switch();
+            assert_eq!(Some(1), wm.get_switch_candidate());
+            assert!(wm.commit_switch().is_ok());
+            assert_eq!(Some(1), wm.get_focused_window());
+        }
+
+        /// Test cancelling a session restores the focus and the stacking
+        pub fn test_switch_cancel<WM: WindowSwitcherSupport + FloatSupport>() {
+            let mut wm = WM::new(SCREEN);
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_float(2, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).is_ok());
+            let layout = wm.get_window_layout();
+
+            wm.begin_switch();
+            wm.step_switch(PrevOrNext::Next);
+            assert_eq!(Some(1), wm.get_switch_candidate());
+            assert_eq!(Some(1), get_raised_window(&wm));
+            wm.cancel_switch();
+            assert_eq!(layout, wm.get_window_layout());
+            assert_eq!(Some(3), wm.get_focused_window());
+
+            // without session nothing happens
+            wm.step_switch(PrevOrNext::Next);
+            assert!(wm.commit_switch().is_ok());
+            assert_eq!(layout, wm.get_window_layout());
+
+            // removing the candidate makes the next window the candidate
+            wm.begin_switch();
+            assert_eq!(Some(2), wm.get_switch_candidate());
+            assert!(wm.remove_window(2).is_ok());
+            assert_eq!(Some(1), wm.get_switch_candidate());
+            assert!(wm.commit_switch().is_ok());
+            assert_eq!(Some(1), wm.get_focused_window());
+        }
+    }
+
+    /// Module for testing FullscreenSupport
+    pub mod fullscreen_support {
+        use cplwm_api::wm::{FloatSupport, FullscreenSupport, MinimiseSupport, TilingSupport};
+        use cplwm_api::types::*;
+
+        static SCREEN: Screen = Screen {
+            width: 800,
+            height: 600,
+        };
+
+        static SOME_GEOM: Geometry = Geometry {
+            x: 10,
+            y: 10,
+            width: 100,
+            height: 100,
+        };
+
+        /// Test toggle_fullscreen puts the window with the screen geometry on top of the layout
+        /// and focuses it. Toggling it again restores it.
+        pub fn test_toggle_fullscreen<T: FullscreenSupport>() {
+            let mut wm = T::new(SCREEN);
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+            assert_eq!(None, wm.get_fullscreen_window());
+
+            assert!(wm.toggle_fullscreen(1).is_ok());
+            assert_eq!(Some(1), wm.get_fullscreen_window());
+            assert_eq!(Some(1), wm.get_focused_window());
+
+            let windows = wm.get_window_layout().windows;
+            assert_eq!(Some(&(1, SCREEN.to_geometry())), windows.last());
+            assert_eq!(1, windows.iter().filter(|&&(w, _)| w == 1).count());
+
+            let info = wm.get_window_info(1).unwrap();
+            assert!(info.fullscreen);
+            assert_eq!(SCREEN.to_geometry(), info.geometry);
+
+            assert!(wm.toggle_fullscreen(1).is_ok());
+            assert_eq!(None, wm.get_fullscreen_window());
+            assert_eq!(Some(1), wm.get_focused_window());
+            assert!(!wm.get_window_info(1).unwrap().fullscreen);
+
+            assert!(wm.toggle_fullscreen(404).is_err());
+        }
+
+        /// Test toggling fullscreen twice on the focused window keeps the same layout
+        pub fn test_toggle_fullscreen_twice<T: FullscreenSupport>() {
+            let mut wm = T::new(SCREEN);
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).is_ok());
+            assert!(wm.focus_window(Some(2)).is_ok());
+
+            let initial_layout = wm.get_window_layout();
+            assert!(wm.toggle_fullscreen(2).is_ok());
+            assert!(wm.toggle_fullscreen(2).is_ok());
+            assert_eq!(initial_layout, wm.get_window_layout());
+        }
+
+        /// Test a window added with the fullscreen flag is fullscreen, and adding another window
+        /// ends it.
+        pub fn test_add_fullscreen_window<T: FullscreenSupport>() {
+            let mut wm = T::new(SCREEN);
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_fullscreen(2, SOME_GEOM)).is_ok());
+            assert_eq!(Some(2), wm.get_fullscreen_window());
+            assert_eq!(Some(2), wm.get_focused_window());
+
+            assert!(wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).is_ok());
+            assert_eq!(None, wm.get_fullscreen_window());
+            assert_eq!(Some(3), wm.get_focused_window());
+        }
+
+        /// Test focusing another window ends the fullscreen state
+        pub fn test_fullscreen_and_focus<T: FullscreenSupport>() {
+            let mut wm = T::new(SCREEN);
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+
+            assert!(wm.toggle_fullscreen(1).is_ok());
+            assert!(wm.focus_window(Some(1)).is_ok());
+            assert_eq!(Some(1), wm.get_fullscreen_window());
+
+            assert!(wm.focus_window(Some(2)).is_ok());
+            assert_eq!(None, wm.get_fullscreen_window());
+
+            assert!(wm.toggle_fullscreen(1).is_ok());
+            wm.cycle_focus(PrevOrNext::Next);
+            assert_eq!(None, wm.get_fullscreen_window());
+
+            assert!(wm.toggle_fullscreen(1).is_ok());
+            assert!(wm.focus_window(None).is_ok());
+            assert_eq!(None, wm.get_fullscreen_window());
+        }
+
+        /// Test removing the fullscreen window, removing another window keeps the fullscreen
+        /// window
+        pub fn test_remove_fullscreen_window<T: FullscreenSupport>() {
+            let mut wm = T::new(SCREEN);
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).is_ok());
+
+            assert!(wm.toggle_fullscreen(2).is_ok());
+            assert!(wm.remove_window(3).is_ok());
+            assert_eq!(Some(2), wm.get_fullscreen_window());
+
+            assert!(wm.remove_window(2).is_ok());
+            assert_eq!(None, wm.get_fullscreen_window());
+            assert!(!wm.get_window_layout().windows.iter().any(|&(w, _)| w == 2));
+        }
+
+        /// Test the fullscreen window follows the size of the screen
+        pub fn test_fullscreen_resize_screen<T: FullscreenSupport>() {
+            let mut wm = T::new(SCREEN);
+            let new_screen = Screen {
+                width: 1000,
+                height: 1000,
+            };
+            assert!(wm.add_window(WindowWithInfo::new_fullscreen(1, SOME_GEOM)).is_ok());
+            wm.resize_screen(new_screen);
+            assert_eq!(Some(&(1, new_screen.to_geometry())),
+                       wm.get_window_layout().windows.last());
+        }
+
+        /// Test a floating window floats at the same place after being fullscreen
+        pub fn test_fullscreen_of_floating_window<T: FullscreenSupport + FloatSupport>() {
+            let mut wm = T::new(SCREEN);
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_float(2, SOME_GEOM)).is_ok());
+
+            assert!(wm.toggle_fullscreen(2).is_ok());
+            assert!(wm.is_floating(2));
+            assert_eq!(FloatOrTile::Float,
+                       wm.get_window_info(2).unwrap().float_or_tile);
+
+            assert!(wm.toggle_fullscreen(2).is_ok());
+            assert_eq!(WindowWithInfo::new_float(2, SOME_GEOM),
+                       wm.get_window_info(2).unwrap());
+
+            // toggling floating ends the fullscreen state
+            assert!(wm.toggle_fullscreen(2).is_ok());
+            assert!(wm.toggle_floating(2).is_ok());
+            assert_eq!(None, wm.get_fullscreen_window());
+            assert!(!wm.is_floating(2));
+        }
+
+        /// Test minimising the fullscreen window ends the fullscreen state and unminimising it
+        /// makes it fullscreen again.
+        pub fn test_minimise_fullscreen_window<T: FullscreenSupport + MinimiseSupport>() {
+            let mut wm = T::new(SCREEN);
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+
+            assert!(wm.toggle_fullscreen(1).is_ok());
+            assert!(wm.toggle_minimised(1).is_ok());
+            assert_eq!(None, wm.get_fullscreen_window());
+            assert!(wm.get_window_info(1).unwrap().fullscreen);
+            assert!(!wm.get_window_layout().windows.iter().any(|&(w, _)| w == 1));
+
+            assert!(wm.toggle_minimised(1).is_ok());
+            assert_eq!(Some(1), wm.get_fullscreen_window());
+            assert_eq!(Some(1), wm.get_focused_window());
+
+            // unminimising by focusing should also restore the fullscreen state
+            assert!(wm.toggle_minimised(1).is_ok());
+            assert!(wm.focus_window(Some(1)).is_ok());
+            assert_eq!(Some(1), wm.get_fullscreen_window());
+        }
+
+        /// Test toggle_fullscreen unminimises a minimised window
+        pub fn test_fullscreen_minimised_window<T: FullscreenSupport + MinimiseSupport>() {
+            let mut wm = T::new(SCREEN);
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+
+            assert!(wm.toggle_minimised(1).is_ok());
+            assert!(wm.toggle_fullscreen(1).is_ok());
+            assert!(!wm.is_minimised(1));
+            assert_eq!(Some(1), wm.get_fullscreen_window());
+            assert_eq!(Some(1), wm.get_focused_window());
+        }
+
+        /// Test swap_with_master keeps the fullscreen window fullscreen if it is swapped, but ends
+        /// it if another window is swapped with the master.
+        pub fn test_swap_with_master_fullscreen_window<T: FullscreenSupport + TilingSupport>
+            () {
+            let mut wm = T::new(SCREEN);
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+
+            assert!(wm.toggle_fullscreen(2).is_ok());
+            assert!(wm.swap_with_master(2).is_ok());
+            assert_eq!(Some(2), wm.get_master_window());
+            assert_eq!(Some(2), wm.get_fullscreen_window());
+
+            assert!(wm.swap_with_master(1).is_ok());
+            assert_eq!(Some(1), wm.get_master_window());
+            assert_eq!(None, wm.get_fullscreen_window());
+        }
+    }
+
+
+    /// Module for testing MultiOutputSupport
+    pub mod multi_output_support {
+        use cplwm_api::wm::{MultiOutputSupport, MultiWorkspaceSupport, WindowManager};
+        use cplwm_api::types::*;
+
+        static SCREEN: Screen = Screen {
+            width: 800,
+            height: 600,
+        };
+
+        static SECOND_SCREEN: Screen = Screen {
+            width: 1024,
+            height: 768,
+        };
+
+        static SOME_GEOM: Geometry = Geometry {
+            x: 10,
+            y: 10,
+            width: 100,
+            height: 100,
+        };
+
+        /// Test adding, resizing and removing outputs
+        pub fn test_add_and_remove_outputs<T: MultiOutputSupport>() {
+            let mut wm = T::new(SCREEN);
+            assert_eq!(vec![Output::new(0, 0, 0, SCREEN)], wm.get_outputs());
+            assert_eq!(0, wm.get_focused_output());
+
+            let second = Output::new(1, 800, 0, SECOND_SCREEN);
+            assert!(wm.add_output(second).is_ok());
+            assert!(wm.add_output(second).is_err());
+            assert_eq!(vec![Output::new(0, 0, 0, SCREEN), second], wm.get_outputs());
+            assert_eq!(0, wm.get_focused_output());
+
+            let resized = Output::new(1, 800, 0, SCREEN);
+            assert!(wm.resize_output(resized).is_ok());
+            assert_eq!(vec![Output::new(0, 0, 0, SCREEN), resized], wm.get_outputs());
+            assert!(wm.resize_output(Output::new(5, 0, 0, SCREEN)).is_err());
+
+            assert!(wm.focus_output(1).is_ok());
+            assert!(wm.remove_output(1).is_ok());
+            assert_eq!(0, wm.get_focused_output());
+            assert!(wm.remove_output(1).is_err());
+            assert!(wm.remove_output(0).is_err(), "the last output can not be removed");
+        }
+
+        /// Test the layout of an output is offset by the position of the output
+        pub fn test_output_layout<T: MultiOutputSupport>() {
+            let mut wm = T::new(SCREEN);
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert!(wm.add_output(Output::new(1, 800, 0, SECOND_SCREEN)).is_ok());
+            assert!(wm.focus_output(1).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+
+            let first_layout = wm.get_output_layout(0).unwrap();
+            assert_eq!(None, first_layout.focused_window);
+            assert_eq!(vec![(1, SCREEN.to_geometry())], first_layout.windows);
+
+            let second_layout = wm.get_output_layout(1).unwrap();
+            assert_eq!(Some(2), second_layout.focused_window);
+            assert_eq!(vec![(2, Output::new(1, 800, 0, SECOND_SCREEN).to_geometry())],
+                       second_layout.windows);
+            assert_eq!(second_layout.windows[0].1,
+                       wm.get_window_info(2).unwrap().geometry);
+
+            // the complete layout contains both outputs
+            let layout = wm.get_window_layout();
+            assert_eq!(Some(2), layout.focused_window);
+            assert_eq!(2, layout.windows.len());
+
+            assert!(wm.get_output_layout(5).is_err());
+        }
+
+        /// Test focusing a window on another output focuses that output
+        pub fn test_focus_moves_between_outputs<T: MultiOutputSupport>() {
+            let mut wm = T::new(SCREEN);
+            assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_ok());
+            assert!(wm.add_output(Output::new(1, 800, 0, SECOND_SCREEN)).is_ok());
+            assert!(wm.focus_output(1).is_ok());
+            assert!(wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).is_ok());
+            assert_eq!(SECOND_SCREEN, wm.get_screen());
+
+            assert!(wm.focus_window(Some(1)).is_ok());
+            assert_eq!(0, wm.get_focused_output());
+            assert_eq!(Some(1), wm.get_focused_window());
+            assert_eq!(SCREEN, wm.get_screen());
+
+            assert!(wm.focus_output(1).is_ok());
+            assert_eq!(Some(2), wm.get_focused_window());
+            assert!(wm.focus_output(5).is_err());
+        }
+
+        /// Test every output shows a different workspace, switching to a workspace shown on
+        /// another output swaps the workspaces of both outputs.
+        pub fn test_workspace_per_output<WM: WindowManager,
+                                         T: MultiOutputSupport + MultiWorkspaceSupport<WM>>
+            () {
+            let mut wm = T::new(SCREEN);
+            assert!(wm.add_output(Output::new(1, 800, 0, SECOND_SCREEN)).is_ok());
+            assert_eq!(0, wm.get_current_workspace_index());
+            assert!(wm.focus_output(1).is_ok());
+            assert_eq!(1, wm.get_current_workspace_index());
+            assert_eq!(SECOND_SCREEN, wm.get_workspace(1).unwrap().get_screen());
+
+            assert!(wm.switch_workspace(0).is_ok());
+            assert_eq!(0, wm.get_current_workspace_index());
+            assert_eq!(SECOND_SCREEN, wm.get_workspace(0).unwrap().get_screen());
+            assert_eq!(SCREEN, wm.get_workspace(1).unwrap().get_screen());
+
+            assert!(wm.focus_output(0).is_ok());
+            assert_eq!(1, wm.get_current_workspace_index());
+        }
+    }
+
+
+    /// Module for all tests concerning the WindowManager trait.
+    pub mod window_manager {
+        use cplwm_api::wm::WindowManager;
+        use cplwm_api::types::*;
+
+        static SCREEN: Screen = Screen {
+            width: 800,
+            height: 600,
+        };
+
+        // A random, unimportant Geometry
+        static SOME_GEOM: Geometry = Geometry {
+            x: 10,
+            y: 10,
+            width: 100,
+            height: 100,
+        };
+
+        /// test if the given window manager is initialized the right way
+        pub fn test_empty_wm<T: WindowManager>() {
+            let wm = T::new(SCREEN);
+            assert_eq!(WindowLayout::new(), wm.get_window_layout());
+            assert_eq!(0, wm.get_windows().len());
+            assert_eq!(SCREEN, wm.get_screen())
+        }
+
+        /// test for adding and removing windows to the WindowManager
+        pub fn test_adding_and_removing_windows<T: WindowManager>() {
+            let mut wm = T::new(SCREEN);
+            let window_a = WindowWithInfo::new_tiled(1, SOME_GEOM);
+            let window_b = WindowWithInfo::new_tiled(2, SOME_GEOM);
+
+            assert!(wm.add_window(window_a.clone()).is_ok());
             assert!(wm.is_managed(1));
             assert_eq!(vec![1], wm.get_windows());
             assert_eq!(Some(1), wm.get_focused_window());
 
-            assert!(wm.add_window(window_b).is_ok());
+            assert!(wm.add_window(window_b.clone()).is_ok());
             assert!(wm.is_managed(2));
 
             assert_eq!(vec![1, 2], wm.get_windows());